@@ -1,47 +1,346 @@
-use crate::data::{claude::ClaudeData, Agent, ChatMessage, FileChange, FileStatus, Session};
-use crate::terminal::EmbeddedTerminal;
+use crate::clipboard::{Clipboard, SystemClipboard};
 use crate::config::presets::{Preset, PresetManager};
+use crate::config::settings::Settings;
+use crate::data::sidecar::{parse_tags, terms_score, SidecarStore};
+use crate::data::{
+    churn::{churn_rows, ChurnRow},
+    claude::{ClaudeData, LoadCache, TailCursor},
+    edited_files, git, render_transcript, transcript_markdown, Agent, ChatMessage, FileChange,
+    Session,
+};
+use crate::highlight::Highlighter;
+use crate::keymap::{self, Binding};
+use crate::process::hook::SelectHook;
 use crate::process::registry::ProcessRegistry;
-use anyhow::Result;
+use crate::process::resume::ResumeCommand;
+use crate::process::which::{self, CLAUDE_INSTALL_HINT};
+use crate::process::worktree;
+use crate::redact::Redactor;
+use crate::terminal::EmbeddedTerminal;
+use crate::text::wrap_columns;
+use crate::ui::minimap;
+use anyhow::{Context, Result};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use ratatui::layout::{Position, Rect};
+use std::cell::OnceCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+/// `chat_scroll` value that keeps the detail view pinned to its last line
+pub const SCROLL_BOTTOM: u16 = u16::MAX;
+
+/// Braille spinner shown for working sessions and running tools
+pub const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Auto-follow stays off for this long after the user moves the selection
+pub const FOLLOW_PAUSE: Duration = Duration::from_secs(5);
+
+/// How long a project's churn stats are reused before git runs again
+pub const CHURN_TTL: Duration = Duration::from_secs(10);
+
+/// Lists longer than this apply the session filter only once typing pauses
+pub const FILTER_DEBOUNCE_SESSIONS: usize = 500;
+
+/// Pause in typing before a debounced session filter is applied
+pub const FILTER_DEBOUNCE: Duration = Duration::from_millis(120);
+
+/// Same-role messages at most this many seconds apart render as one block
+pub const MERGE_WINDOW_SECS: i64 = 60;
+
+/// Opening more changed files than this at once asks first
+pub const OPEN_ALL_CONFIRM_OVER: usize = 20;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Focus {
-    Presets,   // Left panel - preset selection
+    Presets, // Left panel - preset selection
     Sessions,
     Todos,
     Files,
     Detail,
 }
 
+/// Layout state saved before the editor takes over, restored when it closes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewState {
+    pub focus: Focus,
+    pub fullscreen: bool,
+    pub diff_mode: bool,
+}
+
+/// Chat messages wrapped for one session at one width; rebuilt only when the
+/// session, width or message count changes
+#[derive(Debug, Default)]
+pub struct WrapCache {
+    key: Option<(String, usize, usize)>,
+    lines: Vec<Vec<String>>,
+    /// How many times the messages were (re)wrapped
+    pub wraps: usize,
+}
+
+impl WrapCache {
+    /// Wrapped lines per message, recomputed only on a cache miss
+    pub fn lines(
+        &mut self,
+        session_key: &str,
+        width: usize,
+        messages: &[ChatMessage],
+    ) -> &[Vec<String>] {
+        let key = (session_key.to_string(), width, messages.len());
+        if self.key.as_ref() != Some(&key) {
+            self.lines = messages.iter().map(|m| m.display_content(width)).collect();
+            self.key = Some(key);
+            self.wraps += 1;
+        }
+        &self.lines
+    }
+
+    pub fn clear(&mut self) {
+        self.key = None;
+        self.lines.clear();
+    }
+}
+
+/// Where `load_data` gets sessions from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataSource {
+    /// Read `projects/`, `todos/`, `history.jsonl`, ... under this directory
+    ClaudeDir(PathBuf),
+    /// Keep the sessions the app was built with (tests, demos)
+    Fixed,
+}
+
+/// Which parts of the chat are shown
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ChatFilter {
+    #[default]
+    All,
+    TextOnly,
+    ToolsOnly,
+}
+
+impl ChatFilter {
+    pub fn next(self) -> Self {
+        match self {
+            ChatFilter::All => ChatFilter::TextOnly,
+            ChatFilter::TextOnly => ChatFilter::ToolsOnly,
+            ChatFilter::ToolsOnly => ChatFilter::All,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChatFilter::All => "all",
+            ChatFilter::TextOnly => "text only",
+            ChatFilter::ToolsOnly => "tools only",
+        }
+    }
+
+    /// Indices of the messages this filter shows
+    pub fn select(self, messages: &[ChatMessage]) -> Vec<usize> {
+        messages
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| match self {
+                ChatFilter::All => true,
+                ChatFilter::TextOnly => m.has_text(),
+                ChatFilter::ToolsOnly => !m.tool_calls.is_empty(),
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The parts of `msg` this filter shows: prose only for TextOnly, tool
+    /// calls only for ToolsOnly
+    pub fn parts(self, msg: &ChatMessage) -> ChatMessage {
+        let mut msg = msg.clone();
+        match self {
+            ChatFilter::All => {}
+            ChatFilter::TextOnly => msg.tool_calls.clear(),
+            ChatFilter::ToolsOnly => msg.content.clear(),
+        }
+        msg
+    }
+}
+
+/// Chat row of the nearest user message after (`forward`) or before
+/// `offset`, from the `(row, message index)` starts of the last draw
+pub fn user_message_target(
+    starts: &[(usize, usize)],
+    messages: &[ChatMessage],
+    offset: usize,
+    forward: bool,
+) -> Option<usize> {
+    let mut rows = starts
+        .iter()
+        .filter(|&&(_, idx)| messages.get(idx).is_some_and(|m| m.role == "user"))
+        .map(|&(row, _)| row);
+    if forward {
+        rows.find(|&row| row > offset)
+    } else {
+        rows.rfind(|&row| row < offset)
+    }
+}
+
+/// Group the shown message indices into rendered blocks. With `merge`,
+/// consecutive same-role messages within `MERGE_WINDOW_SECS` share one block.
+pub fn message_blocks(messages: &[ChatMessage], shown: &[usize], merge: bool) -> Vec<Vec<usize>> {
+    let mut blocks: Vec<Vec<usize>> = Vec::new();
+    for &idx in shown {
+        let msg = &messages[idx];
+        let joins = merge
+            && blocks.last().and_then(|b| b.last()).is_some_and(|&prev| {
+                let prev = &messages[prev];
+                prev.role == msg.role
+                    && match (prev.timestamp, msg.timestamp) {
+                        (Some(a), Some(b)) => (b - a).num_seconds().abs() <= MERGE_WINDOW_SECS,
+                        _ => true,
+                    }
+            });
+        match blocks.last_mut() {
+            Some(block) if joins => block.push(idx),
+            _ => blocks.push(vec![idx]),
+        }
+    }
+    blocks
+}
+
+/// One side of the compare view: a session's chat with its own scroll
+#[derive(Debug, Default)]
+pub struct ComparePane {
+    pub session_key: String,
+    pub messages: Vec<ChatMessage>,
+    pub scroll: u16,
+    pub scroll_max: u16,
+    // Swapped with the detail view's fields while the pane is drawn or scrolled
+    loaded_key: Option<String>,
+    wrap_cache: WrapCache,
+    line_starts: Vec<(usize, usize)>,
+    anchor: Option<usize>,
+}
+
+/// Two sessions' chats side by side
+#[derive(Debug, Default)]
+pub struct CompareState {
+    pub panes: [ComparePane; 2],
+    /// Pane that scroll keys move
+    pub active: usize,
+}
+
+impl CompareState {
+    pub fn session_keys(&self) -> [&str; 2] {
+        [&self.panes[0].session_key, &self.panes[1].session_key]
+    }
+}
+
+/// What the next key does after a mark prefix
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkAction {
+    Set,
+    Jump,
+}
+
 pub struct App {
     pub should_quit: bool,
+    /// Draw ticks since startup, drives the spinner animation
+    pub frame: usize,
+    /// When session data was last reloaded by the main loop
+    pub last_refresh: Option<Instant>,
+    /// Flips on every refresh so the footer shows the data is live
+    pub refresh_pulse: bool,
+    /// Time of the last key press, for the idle screen
+    pub last_input: Instant,
+    /// Showing the idle screen instead of the normal UI
+    pub dimmed: bool,
+    /// Command to exec in the real terminal once the TUI is torn down
+    pub resume_on_exit: Option<ResumeCommand>,
     pub show_help: bool,
+    /// First visible row of the help popup; clamped when drawn
+    pub help_scroll: u16,
+    /// Keys for the focused panel, filtered by `cheatsheet_query`
+    pub show_cheatsheet: bool,
+    pub cheatsheet_query: String,
 
     // Status message (shows temporarily)
     pub status_message: Option<String>,
+    /// Commands a dry run would have started, oldest first (printed on exit)
+    pub dry_run_log: Vec<String>,
     pub status_is_error: bool,
 
     // Focus
     pub focus: Focus,
 
     // Data
+    pub data_source: DataSource,
+    /// Why the last `load_data` failed; `None` after a successful load
+    pub load_error: Option<String>,
+    /// Transcript reads reused across refreshes
+    load_cache: LoadCache,
+    /// `.lazychat.toml` problems already reported
+    project_errors: Vec<String>,
     pub sessions: Vec<Session>,
     pub agents: Vec<Agent>,
 
+    // Sub-agent tree for selected session
+    pub session_agents: Vec<Agent>,
+    pub show_agents: bool,
+    /// Churn overview across active sessions in the detail view
+    pub show_churn: bool,
+    /// Uncommitted stats per project dir, and when each was read
+    pub churn_stats: HashMap<String, HashMap<String, git::FileStat>>,
+    churn_read_at: HashMap<String, Instant>,
+    /// Session marked with `x`, waiting for a second one to compare with
+    pub compare_mark: Option<String>,
+    /// Two chats side by side, replacing the main layout while open
+    pub compare: Option<CompareState>,
+    /// Per-status session counts in the footer
+    pub show_status_summary: bool,
+
     // Chat messages for selected session
     pub current_messages: Vec<ChatMessage>,
     pub messages_loading: bool,
+    /// How far the loaded transcript has been parsed, for tailing
+    pub transcript_tail: Option<TailCursor>,
+    /// Wrapped chat lines reused across draws
+    pub wrap_cache: WrapCache,
+    /// First rendered chat row and index of each shown message, from the last draw
+    pub message_line_starts: Vec<(usize, usize)>,
+    /// Parts of the chat being shown
+    pub chat_filter: ChatFilter,
+    /// Message to bring to the top on the next draw (after a filter change)
+    pub scroll_anchor: Option<usize>,
+    /// Pretty-printed source JSON shown in the debug popup
+    pub raw_json: Option<String>,
+    pub raw_json_scroll: u16,
 
     // Selection state
     pub session_list_state: ratatui::widgets::ListState,
+    /// When the user last moved the session selection (pauses auto-follow)
+    pub last_manual_nav: Option<Instant>,
 
     // Scroll state for chat view
+    /// Lines skipped from the top of the chat/diff; `SCROLL_BOTTOM` pins the view to the end
     pub chat_scroll: u16,
     pub chat_scroll_max: u16,
+    /// Where the diff minimap was last drawn (for mouse clicks)
+    pub minimap_area: Option<Rect>,
+    /// Diff pane inside its border, as last drawn; hunk jumps wrap to its width
+    pub diff_pane: Rect,
+    /// Chat scroll saved per session key, restored when the session is reselected
+    pub session_scrolls: HashMap<String, u16>,
+    /// Key of the session whose messages are currently loaded
+    pub loaded_session_key: Option<String>,
+    /// Message count per session key when it was last viewed
+    pub seen_message_counts: HashMap<String, u64>,
 
     // Scroll state for todos panel
     pub todos_scroll: u16,
     pub todos_scroll_max: u16,
+    /// Wrap long todos instead of truncating them
+    pub todos_wrap: bool,
+    /// Fold completed todos into a `(+N done)` line
+    pub hide_done_todos: bool,
 
     // Scroll state for files panel
     pub files_scroll: u16,
@@ -51,12 +350,56 @@ pub struct App {
     pub current_file_changes: Vec<FileChange>,
     pub selected_file_idx: usize,
     pub current_diff: String,
+    /// Working-tree content of the selected file (None when deleted/unreadable)
+    pub current_file_content: Option<String>,
+    /// Show the whole file instead of its diff
+    pub raw_file_view: bool,
+    /// Syntax sets are loaded on first use, then reused for every frame
+    highlighter: OnceCell<Highlighter>,
     pub diff_mode: bool,  // True when viewing diff in detail pane
     pub fullscreen: bool, // True when detail view is fullscreen
 
     // Rename input
     pub renaming: bool,
     pub rename_buffer: String,
+    /// Key of the session being renamed / tagged / annotated
+    pub edit_target: Option<String>,
+    /// Waiting for the mark key after `m` (set) or `'` (jump)
+    pub pending_mark: Option<MarkAction>,
+    /// PID awaiting y/n confirmation before it is killed
+    pub pending_kill: Option<u32>,
+    /// Opening every changed file awaits y/n confirmation
+    pub pending_open_all: bool,
+
+    // Open-file-by-path prompt
+    pub opening_path: bool,
+    pub open_path_buffer: String,
+    /// Directory prompt before starting a new session (`new_session.prompt_cwd`)
+    pub new_session_prompt: bool,
+    pub new_session_dir: String,
+    /// Prompt for one-off `--add-dir` paths when resuming a session
+    pub add_dir_prompt: bool,
+    pub add_dir_input: String,
+    /// Fuzzy "go to file" prompt over the changed files
+    pub file_jump_active: bool,
+    pub file_jump_query: String,
+
+    // Note editor state
+    pub editing_note: bool,
+    pub note_buffer: String,
+
+    // Tag editing and session filter state
+    pub editing_tags: bool,
+    pub tags_buffer: String,
+    pub session_filter_active: bool,
+    /// Applied session filter
+    pub session_filter: String,
+    /// Text typed into the filter input; applied on the next keystroke, or
+    /// once typing pauses on large lists
+    pub session_filter_draft: String,
+    session_filter_typed_at: Option<Instant>,
+    /// Only list sessions active today (local time)
+    pub today_only: bool,
 
     // File filter
     pub file_filter_active: bool,
@@ -67,6 +410,8 @@ pub struct App {
     pub embedded_terminal: Option<EmbeddedTerminal>,
     pub terminal_mode: bool,
     pub editor_mode: bool, // True when terminal is running editor (vs claude)
+    /// Where the editor was opened from
+    pub pre_editor_view: Option<ViewState>,
 
     // Preset management (Phase 2)
     pub preset_manager: Option<PresetManager>,
@@ -77,6 +422,31 @@ pub struct App {
 
     // Process registry (Phase 1)
     pub process_registry: Option<ProcessRegistry>,
+    /// Registered processes found alive at the last refresh
+    pub running_processes: usize,
+
+    // General settings (config.toml)
+    pub settings: Settings,
+    /// Where `settings` is read from; watched for theme edits
+    pub settings_path: PathBuf,
+    settings_mtime: Option<SystemTime>,
+
+    /// Whether the `claude` CLI was found on PATH at startup
+    pub claude_available: bool,
+    /// Executable started for preset instances
+    pub claude_program: String,
+
+    // Per-session metadata (custom names)
+    pub sidecar: Option<SidecarStore>,
+
+    /// Target of every copy action (OS clipboard; swapped out in tests)
+    pub clipboard: Box<dyn Clipboard>,
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl App {
@@ -86,34 +456,96 @@ impl App {
 
         Self {
             should_quit: false,
+            frame: 0,
+            last_refresh: None,
+            refresh_pulse: false,
+            last_input: Instant::now(),
+            dimmed: false,
+            resume_on_exit: None,
             show_help: false,
+            help_scroll: 0,
+            show_cheatsheet: false,
+            cheatsheet_query: String::new(),
             status_message: None,
+            dry_run_log: Vec::new(),
             status_is_error: false,
             focus: Focus::Sessions,
+            data_source: DataSource::ClaudeDir(ClaudeData::claude_dir()),
+            load_error: None,
+            load_cache: LoadCache::default(),
+            project_errors: Vec::new(),
             sessions: Vec::new(),
             agents: Vec::new(),
+            session_agents: Vec::new(),
+            show_agents: false,
+            show_churn: false,
+            compare_mark: None,
+            compare: None,
+            churn_stats: HashMap::new(),
+            churn_read_at: HashMap::new(),
+            show_status_summary: true,
             current_messages: Vec::new(),
             messages_loading: false,
+            transcript_tail: None,
+            wrap_cache: WrapCache::default(),
+            message_line_starts: Vec::new(),
+            chat_filter: ChatFilter::All,
+            scroll_anchor: None,
+            raw_json: None,
+            raw_json_scroll: 0,
             session_list_state,
-            chat_scroll: 0,
+            last_manual_nav: None,
+            chat_scroll: SCROLL_BOTTOM,
             chat_scroll_max: 0,
+            session_scrolls: HashMap::new(),
+            seen_message_counts: HashMap::new(),
+            loaded_session_key: None,
             todos_scroll: 0,
             todos_scroll_max: 0,
+            todos_wrap: false,
+            hide_done_todos: false,
             files_scroll: 0,
             files_scroll_max: 0,
             current_file_changes: Vec::new(),
             selected_file_idx: 0,
             current_diff: String::new(),
+            current_file_content: None,
+            raw_file_view: false,
+            minimap_area: None,
+            diff_pane: Rect::default(),
+            highlighter: OnceCell::new(),
             diff_mode: false,
             fullscreen: false,
             renaming: false,
             rename_buffer: String::new(),
+            edit_target: None,
+            pending_mark: None,
+            pending_kill: None,
+            pending_open_all: false,
+            opening_path: false,
+            open_path_buffer: String::new(),
+            new_session_prompt: false,
+            new_session_dir: String::new(),
+            add_dir_prompt: false,
+            add_dir_input: String::new(),
+            file_jump_active: false,
+            file_jump_query: String::new(),
+            editing_note: false,
+            note_buffer: String::new(),
+            editing_tags: false,
+            tags_buffer: String::new(),
+            session_filter_active: false,
+            session_filter: String::new(),
+            session_filter_draft: String::new(),
+            session_filter_typed_at: None,
+            today_only: false,
             file_filter_active: false,
             file_filter: String::new(),
             file_tree_mode: true, // Default to tree view
             embedded_terminal: None,
             terminal_mode: false,
             editor_mode: false,
+            pre_editor_view: None,
 
             // Preset management
             preset_manager: None,
@@ -124,36 +556,231 @@ impl App {
 
             // Process registry
             process_registry: None,
+            running_processes: 0,
+            settings: Settings::default(),
+            settings_path: Settings::config_path(),
+            settings_mtime: None,
+            claude_available: true,
+            claude_program: "claude".to_string(),
+            sidecar: None,
+            clipboard: Box::new(SystemClipboard),
         }
     }
 
+    /// App over a fixed set of sessions; `load_data` never touches the filesystem
+    pub fn with_sessions(sessions: Vec<Session>) -> Self {
+        let mut app = Self::new();
+        app.data_source = DataSource::Fixed;
+        app.sessions = sessions;
+        app.record_message_counts();
+        app
+    }
+
+    /// Read session data from another Claude directory instead of ~/.claude
+    pub fn with_claude_dir(mut self, claude_dir: impl Into<PathBuf>) -> Self {
+        self.data_source = DataSource::ClaudeDir(claude_dir.into());
+        self
+    }
+
     pub async fn load_data(&mut self) -> Result<()> {
-        let data = ClaudeData::load().await?;
-        self.sessions = data.sessions;
-        self.agents = data.agents;
+        let selected_key = self.selected_session().map(|s| s.key());
+        if let DataSource::ClaudeDir(dir) = &self.data_source {
+            let data =
+                match ClaudeData::load_cached(dir, &self.settings, &mut self.load_cache).await {
+                    Ok(data) => data,
+                    Err(e) => {
+                        // Keep the last good sessions; report each new failure once
+                        let message = format!("Failed to load sessions: {e:#}");
+                        if self.load_error.as_ref() != Some(&message) {
+                            self.set_error(&message);
+                        }
+                        self.load_error = Some(message);
+                        return Err(e);
+                    }
+                };
+            self.load_error = None;
+            self.report_project_errors(data.project_errors);
+            self.sessions = data.sessions;
+            self.agents = data.agents;
+        }
+        if let Some(ref sidecar) = self.sidecar {
+            sidecar.apply(&mut self.sessions);
+        }
+        self.prune_session_scrolls();
+        self.record_message_counts();
+        if let Some(key) = selected_key {
+            self.reselect_session(&key);
+        }
+        self.clamp_selections();
         Ok(())
     }
 
+    /// Show broken `.lazychat.toml` files, each new set of problems once
+    fn report_project_errors(&mut self, errors: Vec<String>) {
+        if errors != self.project_errors {
+            if let Some(first) = errors.first() {
+                match errors.len() {
+                    1 => self.set_error(first),
+                    n => self.set_error(&format!("{first} (+{} more)", n - 1)),
+                }
+            }
+        }
+        self.project_errors = errors;
+    }
+
+    /// Point the list selection back at the session with `key` after the
+    /// list was rebuilt; if it is gone, the old row is kept (and clamped)
+    fn reselect_session(&mut self, key: &str) {
+        let visible = self.visible_sessions();
+        if let Some(row) = visible.iter().position(|&i| self.sessions[i].key() == key) {
+            self.session_list_state.select(Some(row));
+        }
+    }
+
+    /// Keep every selection index inside its collection after a reload
+    pub fn clamp_selections(&mut self) {
+        let last = |len: usize| len.saturating_sub(1);
+        let rows = self.visible_sessions().len();
+        if let Some(row) = self.session_list_state.selected() {
+            self.session_list_state.select(Some(row.min(last(rows))));
+        }
+        self.selected_file_idx = self
+            .selected_file_idx
+            .min(last(self.current_file_changes.len()));
+        self.selected_preset_idx = self.selected_preset_idx.min(last(self.presets.len()));
+    }
+
+    /// After a refresh: baseline sessions seen for the first time and forget
+    /// sessions that disappeared, so only growth since the last view counts
+    pub fn record_message_counts(&mut self) {
+        let sessions = &self.sessions;
+        self.seen_message_counts
+            .retain(|key, _| sessions.iter().any(|s| &s.key() == key));
+        for session in sessions {
+            self.seen_message_counts
+                .entry(session.key())
+                .or_insert(session.message_count);
+        }
+    }
+
+    /// Messages added to a session since it was last viewed
+    pub fn unseen_message_count(&self, session: &Session) -> u64 {
+        self.seen_message_counts
+            .get(&session.key())
+            .map(|&seen| session.message_count.saturating_sub(seen))
+            .unwrap_or(0)
+    }
+
+    /// Mark everything in the selected session as seen
+    pub fn mark_selected_seen(&mut self) {
+        if let Some(session) = self.selected_session() {
+            let (key, count) = (session.key(), session.message_count);
+            self.seen_message_counts.insert(key, count);
+        }
+    }
+
+    /// Append messages written to the loaded transcript since the last read,
+    /// following them if the chat is scrolled to the end
+    pub async fn tail_selected_session(&mut self) -> Result<()> {
+        let Some(session) = self.selected_session() else {
+            return Ok(());
+        };
+        if self.loaded_session_key.as_deref() != Some(session.key().as_str()) {
+            return Ok(());
+        }
+        let (Some(path), Some(cursor)) = (session.file_path.clone(), self.transcript_tail) else {
+            return Ok(());
+        };
+
+        let len = tokio::fs::metadata(&path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        if len == cursor.offset {
+            return Ok(());
+        }
+        if len < cursor.offset {
+            // Rewritten rather than appended: start over
+            return self.load_session_messages().await;
+        }
+
+        let (messages, cursor) = ClaudeData::tail_messages(&path, cursor).await?;
+        self.transcript_tail = Some(cursor);
+        if messages.is_empty() {
+            return Ok(());
+        }
+        if self.scroll_offset() >= self.chat_scroll_max {
+            self.scroll_to_bottom();
+        }
+        self.current_messages.extend(messages);
+        self.mark_selected_seen();
+        Ok(())
+    }
+
+    /// Reload the selected session if it has new messages, then pin to the end
+    pub async fn jump_to_newest(&mut self) -> Result<()> {
+        let unseen = self
+            .selected_session()
+            .map(|s| self.unseen_message_count(s))
+            .unwrap_or(0);
+        if unseen > 0 {
+            self.load_session_messages().await?;
+        }
+        self.scroll_to_bottom();
+        Ok(())
+    }
+
+    /// Forget saved scroll positions for sessions that no longer exist
+    pub fn prune_session_scrolls(&mut self) {
+        let sessions = &self.sessions;
+        self.session_scrolls
+            .retain(|key, _| sessions.iter().any(|s| &s.key() == key));
+    }
+
     pub async fn load_session_messages(&mut self) -> Result<()> {
-        if let Some(i) = self.session_list_state.selected() {
+        if let Some(i) = self.selected_session_index() {
             if let Some(session) = self.sessions.get(i) {
-                self.messages_loading = true;
-                self.current_messages = ClaudeData::load_session_messages(session).await?;
-                self.messages_loading = false;
-                self.chat_scroll = 0;
-
-                // Extract unique edited files from tool calls
-                let mut file_paths: Vec<String> = self
-                    .current_messages
-                    .iter()
-                    .flat_map(|m| &m.tool_calls)
-                    .filter_map(|tc| tc.file_path.clone())
-                    .collect();
-                file_paths.sort();
-                file_paths.dedup();
-
-                // Get git diff info for each file
-                self.current_file_changes = Self::get_file_changes(&file_paths).await;
+                if let Some(prev) = self.loaded_session_key.take() {
+                    self.session_scrolls.insert(prev, self.chat_scroll);
+                }
+                let session_key = session.key();
+                let project_dir = PathBuf::from(&session.project);
+
+                self.transcript_tail = None;
+                if session.transcript_missing() {
+                    // Nothing to load; the UI flags the session instead
+                    self.current_messages = Vec::new();
+                    self.session_agents = Vec::new();
+                } else {
+                    self.messages_loading = true;
+                    self.current_messages = match &session.file_path {
+                        Some(path) => {
+                            let (messages, cursor) =
+                                ClaudeData::tail_messages(path, TailCursor::default()).await?;
+                            self.transcript_tail = Some(cursor);
+                            messages
+                        }
+                        None => Vec::new(),
+                    };
+                    self.messages_loading = false;
+                    self.session_agents = self
+                        .load_cache
+                        .session_agents(session)
+                        .await
+                        .unwrap_or_default();
+                }
+
+                self.wrap_cache.clear();
+
+                // Restore the previous position; rendering clamps it to the new chat_scroll_max
+                self.chat_scroll = self
+                    .session_scrolls
+                    .get(&session_key)
+                    .copied()
+                    .unwrap_or(SCROLL_BOTTOM);
+                self.loaded_session_key = Some(session_key);
+
+                self.load_file_changes(&project_dir).await;
                 self.selected_file_idx = 0;
                 self.current_diff = String::new();
                 self.files_scroll = 0;
@@ -163,9 +790,56 @@ impl App {
                 self.diff_mode = false;
             }
         }
+        self.clamp_selections();
+        self.mark_selected_seen();
         Ok(())
     }
 
+    /// Git stats for the files edited in the loaded chat, in one pass
+    async fn load_file_changes(&mut self, project_dir: &Path) {
+        let file_paths = edited_files(
+            &self.current_messages,
+            self.settings.display.files_by_recency,
+        );
+        self.current_file_changes = git::file_changes(project_dir, &file_paths).await;
+    }
+
+    /// Re-read git stats and the open diff for the selected session, for
+    /// files changed on disk since they were loaded
+    pub async fn refresh_file_changes(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        let project_dir = PathBuf::from(&session.project);
+        let churn_key = session.project_dir();
+        let selected = self
+            .current_file_changes
+            .get(self.selected_file_idx)
+            .map(|f| f.path.clone());
+
+        self.churn_stats.remove(&churn_key);
+        self.churn_read_at.remove(&churn_key);
+        self.load_file_changes(&project_dir).await;
+        self.selected_file_idx = selected
+            .and_then(|path| {
+                self.current_file_changes
+                    .iter()
+                    .position(|f| f.path == path)
+            })
+            .unwrap_or(0);
+        self.clamp_selections();
+
+        self.current_diff.clear();
+        self.current_file_content = None;
+        if self.diff_mode || self.focus == Focus::Files {
+            self.load_file_diff().await;
+        }
+        self.set_status(&format!(
+            "Refreshed {} changed files",
+            self.current_file_changes.len()
+        ));
+    }
+
     pub fn toggle_focus(&mut self) {
         match self.focus {
             Focus::Presets => self.focus = Focus::Detail,
@@ -179,10 +853,41 @@ impl App {
         }
     }
 
+    /// Leave the detail view (Ctrl+q), returning to the panel it was opened from
+    pub fn exit_detail_view(&mut self) {
+        if self.focus == Focus::Detail || self.fullscreen {
+            self.fullscreen = false;
+            if self.diff_mode {
+                self.diff_mode = false;
+                self.focus = Focus::Files;
+            } else {
+                self.focus = Focus::Sessions;
+            }
+        }
+    }
+
     pub fn selected_session_todos_count(&self) -> usize {
         self.selected_session().map(|s| s.todos.len()).unwrap_or(0)
     }
 
+    /// Switch the Todos panel between truncated and wrapped rows
+    pub fn toggle_todos_wrap(&mut self) {
+        self.todos_wrap = !self.todos_wrap;
+        self.todos_scroll = 0;
+    }
+
+    /// Hide or show completed todos in the Todos panel and preview
+    pub fn toggle_hide_done_todos(&mut self) {
+        self.hide_done_todos = !self.hide_done_todos;
+        self.todos_scroll = 0;
+        let state = if self.hide_done_todos {
+            "hidden"
+        } else {
+            "shown"
+        };
+        self.set_status(&format!("Completed todos {state}"));
+    }
+
     pub fn todos_scroll_up(&mut self) {
         if self.todos_scroll > 0 {
             self.todos_scroll = self.todos_scroll.saturating_sub(1);
@@ -195,17 +900,296 @@ impl App {
         }
     }
 
+    /// Enter the idle screen once input has been idle past `dim_after_secs`
+    pub fn update_idle(&mut self, now: Instant) {
+        let timeout = self.settings.display.dim_after_secs;
+        // Never hide a live embedded terminal
+        self.dimmed = timeout > 0
+            && !self.terminal_mode
+            && now.duration_since(self.last_input).as_secs() >= timeout;
+    }
+
+    /// Record a key press; returns true if it only woke the idle screen
+    pub fn register_input(&mut self, now: Instant) -> bool {
+        self.last_input = now;
+        std::mem::replace(&mut self.dimmed, false)
+    }
+
     pub fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    pub fn open_cheatsheet(&mut self) {
+        self.show_cheatsheet = true;
+        self.cheatsheet_query.clear();
+    }
+
+    pub fn close_cheatsheet(&mut self) {
+        self.show_cheatsheet = false;
+        self.cheatsheet_query.clear();
+    }
+
+    pub fn cheatsheet_input(&mut self, c: char) {
+        self.cheatsheet_query.push(c);
+    }
+
+    pub fn cheatsheet_backspace(&mut self) {
+        self.cheatsheet_query.pop();
+    }
+
+    /// Bindings shown in the cheatsheet for the current focus and query
+    pub fn cheatsheet_bindings(&self) -> Vec<&'static Binding> {
+        keymap::cheatsheet(self.focus, &self.cheatsheet_query)
+    }
+
+    pub fn toggle_agents(&mut self) {
+        self.show_agents = !self.show_agents;
+    }
+
+    pub fn toggle_churn(&mut self) {
+        self.show_churn = !self.show_churn;
+    }
+
+    /// Re-read stats for active sessions' projects older than `CHURN_TTL`
+    pub async fn refresh_churn(&mut self, now: Instant) {
+        let mut projects: Vec<String> = self
+            .sessions
+            .iter()
+            .filter(|s| s.status != "inactive")
+            .map(|s| s.project_dir())
+            .collect();
+        projects.sort();
+        projects.dedup();
+        self.churn_stats
+            .retain(|project, _| projects.contains(project));
+        self.churn_read_at
+            .retain(|project, _| projects.contains(project));
+
+        for project in projects {
+            let fresh = self
+                .churn_read_at
+                .get(&project)
+                .is_some_and(|at| now.saturating_duration_since(*at) < CHURN_TTL);
+            if !fresh {
+                let stats = git::repo_stats(Path::new(&project))
+                    .await
+                    .unwrap_or_default();
+                self.churn_stats.insert(project.clone(), stats);
+                self.churn_read_at.insert(project, now);
+            }
+        }
+    }
+
+    /// Overview rows from the cached stats
+    pub fn churn_rows(&self) -> Vec<ChurnRow> {
+        churn_rows(&self.sessions, &self.churn_stats, self.redactor())
+    }
+
+    /// Mark the selected session for compare, or compare it with the marked one
+    pub async fn mark_for_compare(&mut self) {
+        let Some(key) = self.selected_session().map(|s| s.key()) else {
+            return;
+        };
+        match self.compare_mark.take() {
+            None => {
+                self.compare_mark = Some(key);
+                self.set_status("Marked for compare: press x on another session");
+            }
+            Some(marked) if marked == key => self.set_status("Compare mark cleared"),
+            Some(marked) => self.open_compare(&marked, &key).await,
+        }
+    }
+
+    /// Load both sessions' transcripts into the compare view
+    pub async fn open_compare(&mut self, left: &str, right: &str) {
+        let mut compare = CompareState::default();
+        for (pane, key) in compare.panes.iter_mut().zip([left, right]) {
+            let Some(session) = self.sessions.iter().find(|s| s.key() == key) else {
+                self.set_error("Session to compare no longer exists");
+                return;
+            };
+            pane.messages = match ClaudeData::load_session_messages(session).await {
+                Ok(messages) => messages,
+                Err(e) => {
+                    self.set_error(&format!("Failed to load transcript: {e}"));
+                    return;
+                }
+            };
+            pane.session_key = key.to_string();
+            pane.loaded_key = Some(key.to_string());
+            pane.scroll = SCROLL_BOTTOM;
+        }
+        self.compare = Some(compare);
+    }
+
+    pub fn close_compare(&mut self) {
+        self.compare = None;
+    }
+
+    /// Switch which compare pane the scroll keys move
+    pub fn toggle_compare_pane(&mut self) {
+        if let Some(compare) = self.compare.as_mut() {
+            compare.active = 1 - compare.active;
+        }
+    }
+
+    /// Run `f` with compare pane `i` swapped into the detail view's chat
+    /// fields, so scrolling and drawing work on it unchanged
+    pub fn with_compare_pane<R>(&mut self, i: usize, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        self.swap_compare_pane(i)?;
+        let result = f(self);
+        self.swap_compare_pane(i);
+        Some(result)
+    }
+
+    fn swap_compare_pane(&mut self, i: usize) -> Option<()> {
+        let pane = self.compare.as_mut()?.panes.get_mut(i)?;
+        std::mem::swap(&mut self.current_messages, &mut pane.messages);
+        std::mem::swap(&mut self.chat_scroll, &mut pane.scroll);
+        std::mem::swap(&mut self.chat_scroll_max, &mut pane.scroll_max);
+        std::mem::swap(&mut self.loaded_session_key, &mut pane.loaded_key);
+        std::mem::swap(&mut self.wrap_cache, &mut pane.wrap_cache);
+        std::mem::swap(&mut self.message_line_starts, &mut pane.line_starts);
+        std::mem::swap(&mut self.scroll_anchor, &mut pane.anchor);
+        Some(())
+    }
+
+    /// Message shown at the top of the chat view
+    pub fn message_at_scroll(&self) -> Option<&ChatMessage> {
+        let offset = self.scroll_offset() as usize;
+        let shown = self
+            .message_line_starts
+            .partition_point(|&(start, _)| start <= offset)
+            .checked_sub(1)?;
+        let (_, idx) = self.message_line_starts[shown];
+        self.current_messages.get(idx)
+    }
+
+    /// Scroll to the previous/next of your own prompts
+    pub fn jump_user_message(&mut self, forward: bool) {
+        let offset = self.scroll_offset() as usize;
+        let Some(row) = user_message_target(
+            &self.message_line_starts,
+            &self.current_messages,
+            offset,
+            forward,
+        ) else {
+            let which = if forward { "later" } else { "earlier" };
+            self.set_status(&format!("No {which} prompt"));
+            return;
+        };
+        // Past the last full page, pin to the bottom like scrolling there would
+        self.chat_scroll = if row >= self.chat_scroll_max as usize {
+            SCROLL_BOTTOM
+        } else {
+            row as u16
+        };
+    }
+
+    /// Cycle all / text only / tools only, keeping the top message in view
+    pub fn cycle_chat_filter(&mut self) {
+        self.chat_filter = self.chat_filter.next();
+        if self.chat_scroll != SCROLL_BOTTOM {
+            let offset = self.scroll_offset() as usize;
+            let shown = self
+                .message_line_starts
+                .partition_point(|&(start, _)| start <= offset);
+            self.scroll_anchor = shown.checked_sub(1).map(|i| self.message_line_starts[i].1);
+        }
+        self.set_status(&format!("Chat: {}", self.chat_filter.label()));
+    }
+
+    /// Open the source JSON of the message under the chat cursor
+    pub async fn open_raw_json(&mut self) {
+        let line = self.message_at_scroll().and_then(|m| m.line);
+        let (Some(session), Some(line)) = (self.selected_session(), line) else {
+            self.set_status("No message selected");
+            return;
+        };
+        match ClaudeData::load_raw_message(session, line).await {
+            Ok(Some(json)) => {
+                self.raw_json = Some(json);
+                self.raw_json_scroll = 0;
+            }
+            Ok(None) => self.set_error("Message no longer in transcript"),
+            Err(e) => self.set_error(&format!("Failed to read transcript: {e}")),
+        }
+    }
+
+    pub fn close_raw_json(&mut self) {
+        self.raw_json = None;
+    }
+
+    /// Advance the animation by one draw tick
+    pub fn tick(&mut self) {
+        self.frame = self.frame.wrapping_add(1);
+    }
+
+    /// Spinner glyph for the current tick
+    pub fn spinner_frame(&self) -> &'static str {
+        SPINNER_FRAMES[self.frame % SPINNER_FRAMES.len()]
+    }
+
+    /// Note a completed data refresh; flips the footer heartbeat
+    pub fn record_refresh(&mut self, now: Instant) {
+        self.last_refresh = Some(now);
+        self.refresh_pulse = !self.refresh_pulse;
+    }
+
+    /// Footer heartbeat glyph; changes on every refresh
+    pub fn refresh_indicator(&self) -> &'static str {
+        match (self.last_refresh, self.refresh_pulse) {
+            (None, _) => "·",
+            (Some(_), true) => "●",
+            (Some(_), false) => "○",
+        }
+    }
+
+    pub fn toggle_status_summary(&mut self) {
+        self.show_status_summary = !self.show_status_summary;
+    }
+
+    pub fn toggle_auto_follow(&mut self) {
+        self.settings.auto_follow = !self.settings.auto_follow;
+        let state = if self.settings.auto_follow {
+            "on"
+        } else {
+            "off"
+        };
+        self.set_status(&format!("Auto-follow {state}"));
+    }
+
+    pub fn toggle_merge_messages(&mut self) {
+        let display = &mut self.settings.display;
+        display.merge_messages = !display.merge_messages;
+        let state = if display.merge_messages { "on" } else { "off" };
+        self.set_status(&format!("Merge consecutive messages {state}"));
+    }
+
+    /// Whether a refresh should move the selection to the newest session
+    pub fn should_follow(&self, now: Instant) -> bool {
+        self.settings.auto_follow
+            && self
+                .last_manual_nav
+                .is_none_or(|at| now.saturating_duration_since(at) >= FOLLOW_PAUSE)
+    }
+
+    /// Select the top (most recently active) session if auto-follow allows it
+    pub fn follow_newest(&mut self, now: Instant) {
+        if self.should_follow(now) && !self.visible_sessions().is_empty() {
+            self.session_list_state.select(Some(0));
+        }
     }
 
     pub fn start_rename(&mut self) {
-        if let Some(session) = self.selected_session() {
+        if let Some(session) = self.selected_session().cloned() {
             self.rename_buffer = session
                 .custom_name
                 .clone()
                 .or_else(|| session.description.clone())
                 .unwrap_or_default();
+            self.edit_target = Some(session.key());
             self.renaming = true;
         }
     }
@@ -213,22 +1197,48 @@ impl App {
     pub fn cancel_rename(&mut self) {
         self.renaming = false;
         self.rename_buffer.clear();
+        self.edit_target = None;
     }
 
     pub fn confirm_rename(&mut self) {
-        if let Some(i) = self.session_list_state.selected() {
-            if let Some(session) = self.sessions.get_mut(i) {
+        if let Some(target) = self.edit_target.take() {
+            if let Some(session) = self.sessions.iter_mut().find(|s| s.key() == target) {
                 if self.rename_buffer.is_empty() {
                     session.custom_name = None;
                 } else {
                     session.custom_name = Some(self.rename_buffer.clone());
                 }
+
+                let key = session.key();
+                let name = session.custom_name.clone();
+                match self.sidecar {
+                    Some(ref mut sidecar) => {
+                        if let Err(e) = sidecar.set_name(&key, name) {
+                            self.set_error(&format!("Failed to save name: {e}"));
+                        }
+                    }
+                    None => self.set_error("Session sidecar not loaded"),
+                }
             }
         }
         self.renaming = false;
         self.rename_buffer.clear();
     }
 
+    /// True while a text input or editor popup has the keyboard
+    pub fn input_active(&self) -> bool {
+        self.renaming
+            || self.editing_tags
+            || self.editing_note
+            || self.session_filter_active
+            || self.file_filter_active
+            || self.opening_path
+            || self.file_jump_active
+            || self.new_session_prompt
+            || self.add_dir_prompt
+            || self.show_cheatsheet
+    }
+
     pub fn rename_input(&mut self, c: char) {
         self.rename_buffer.push(c);
     }
@@ -237,6 +1247,165 @@ impl App {
         self.rename_buffer.pop();
     }
 
+    pub fn start_tags(&mut self) {
+        if let Some(session) = self.selected_session().cloned() {
+            self.tags_buffer = self.session_tags(&session).join(", ");
+            self.edit_target = Some(session.key());
+            self.editing_tags = true;
+        }
+    }
+
+    pub fn cancel_tags(&mut self) {
+        self.editing_tags = false;
+        self.tags_buffer.clear();
+        self.edit_target = None;
+    }
+
+    pub fn confirm_tags(&mut self) {
+        if let Some(key) = self.edit_target.take() {
+            let tags = parse_tags(&self.tags_buffer);
+            match self.sidecar {
+                Some(ref mut sidecar) => {
+                    if let Err(e) = sidecar.set_tags(&key, tags) {
+                        self.set_error(&format!("Failed to save tags: {e}"));
+                    }
+                }
+                None => self.set_error("Session sidecar not loaded"),
+            }
+        }
+        self.editing_tags = false;
+        self.tags_buffer.clear();
+    }
+
+    pub fn tags_input(&mut self, c: char) {
+        self.tags_buffer.push(c);
+    }
+
+    pub fn tags_backspace(&mut self) {
+        self.tags_buffer.pop();
+    }
+
+    pub fn start_session_filter(&mut self) {
+        self.session_filter_active = true;
+        self.session_filter_draft = self.session_filter.clone();
+    }
+
+    pub fn cancel_session_filter(&mut self) {
+        self.session_filter_active = false;
+        self.session_filter.clear();
+        self.session_filter_draft.clear();
+        self.session_filter_typed_at = None;
+        self.session_list_state.select(Some(0));
+    }
+
+    /// Close the filter input, keeping the typed filter applied, and open
+    /// the top match
+    pub fn confirm_session_filter(&mut self) {
+        self.session_filter_active = false;
+        self.apply_session_filter();
+        if self.selected_session().is_some() {
+            self.focus = Focus::Detail;
+            self.diff_mode = false;
+            self.fullscreen = true;
+        }
+    }
+
+    pub fn session_filter_input(&mut self, c: char) {
+        self.session_filter_draft.push(c);
+        self.session_filter_typed(Instant::now());
+    }
+
+    pub fn session_filter_backspace(&mut self) {
+        self.session_filter_draft.pop();
+        self.session_filter_typed(Instant::now());
+    }
+
+    fn session_filter_typed(&mut self, now: Instant) {
+        if self.sessions.len() > FILTER_DEBOUNCE_SESSIONS {
+            self.session_filter_typed_at = Some(now);
+        } else {
+            self.apply_session_filter();
+        }
+    }
+
+    /// Apply a debounced filter once typing has paused
+    pub fn settle_session_filter(&mut self, now: Instant) {
+        if self
+            .session_filter_typed_at
+            .is_some_and(|at| now.saturating_duration_since(at) >= FILTER_DEBOUNCE)
+        {
+            self.apply_session_filter();
+        }
+    }
+
+    /// Filter by the typed text and select the best-scoring match
+    fn apply_session_filter(&mut self) {
+        self.session_filter_typed_at = None;
+        self.session_filter = self.session_filter_draft.clone();
+        let best = self
+            .visible_sessions()
+            .into_iter()
+            .enumerate()
+            .filter_map(|(row, i)| {
+                self.session_filter_score(&self.sessions[i])
+                    .map(|score| (row, score))
+            })
+            .min_by_key(|&(row, score)| (std::cmp::Reverse(score), row))
+            .map_or(0, |(row, _)| row);
+        self.session_list_state.select(Some(best));
+    }
+
+    pub fn toggle_today_filter(&mut self) {
+        self.today_only = !self.today_only;
+        self.session_list_state.select(Some(0));
+        let state = if self.today_only { "on" } else { "off" };
+        self.set_status(&format!("Today-only filter {state}"));
+    }
+
+    /// Note attached to the selected session
+    pub fn selected_session_note(&self) -> Option<&str> {
+        let session = self.selected_session()?;
+        self.sidecar.as_ref()?.note(&session.key())
+    }
+
+    pub fn start_note(&mut self) {
+        if let Some(key) = self.selected_session().map(|s| s.key()) {
+            self.note_buffer = self.selected_session_note().unwrap_or_default().to_string();
+            self.edit_target = Some(key);
+            self.editing_note = true;
+        }
+    }
+
+    pub fn cancel_note(&mut self) {
+        self.editing_note = false;
+        self.note_buffer.clear();
+        self.edit_target = None;
+    }
+
+    pub fn confirm_note(&mut self) {
+        if let Some(key) = self.edit_target.take() {
+            let note = Some(std::mem::take(&mut self.note_buffer));
+            match self.sidecar {
+                Some(ref mut sidecar) => {
+                    if let Err(e) = sidecar.set_note(&key, note) {
+                        self.set_error(&format!("Failed to save note: {e}"));
+                    }
+                }
+                None => self.set_error("Session sidecar not loaded"),
+            }
+        }
+        self.editing_note = false;
+        self.note_buffer.clear();
+    }
+
+    pub fn note_input(&mut self, c: char) {
+        self.note_buffer.push(c);
+    }
+
+    pub fn note_backspace(&mut self) {
+        self.note_buffer.pop();
+    }
+
     pub fn start_file_filter(&mut self) {
         self.file_filter_active = true;
         self.file_filter.clear();
@@ -258,6 +1427,67 @@ impl App {
         }
     }
 
+    pub fn start_file_jump(&mut self) {
+        self.file_jump_active = true;
+        self.file_jump_query.clear();
+    }
+
+    pub fn cancel_file_jump(&mut self) {
+        self.file_jump_active = false;
+        self.file_jump_query.clear();
+    }
+
+    /// Typing moves the selection to the best match as a preview
+    pub fn file_jump_input(&mut self, c: char) {
+        self.file_jump_query.push(c);
+        self.preview_file_jump();
+    }
+
+    pub fn file_jump_backspace(&mut self) {
+        self.file_jump_query.pop();
+        self.preview_file_jump();
+    }
+
+    fn preview_file_jump(&mut self) {
+        if let Some(idx) = self.best_file_match(&self.file_jump_query) {
+            self.file_filter.clear();
+            self.selected_file_idx = idx;
+        }
+    }
+
+    /// Index of the changed file scoring highest for `query` (path or name)
+    pub fn best_file_match(&self, query: &str) -> Option<usize> {
+        if query.is_empty() {
+            return None;
+        }
+        let matcher = SkimMatcherV2::default();
+        self.current_file_changes
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| {
+                let score = matcher
+                    .fuzzy_match(&f.path, query)
+                    .max(matcher.fuzzy_match(&f.filename, query))?;
+                Some((i, score))
+            })
+            // Earlier files win ties
+            .max_by_key(|&(i, score)| (score, std::cmp::Reverse(i)))
+            .map(|(i, _)| i)
+    }
+
+    /// Close the prompt and open the best match's diff
+    pub async fn confirm_file_jump(&mut self) {
+        let query = std::mem::take(&mut self.file_jump_query);
+        self.file_jump_active = false;
+        match self.best_file_match(&query) {
+            Some(idx) => {
+                self.file_filter.clear();
+                self.show_file_diff(idx).await;
+            }
+            None => self.set_status(&format!("No file matches '{query}'")),
+        }
+    }
+
     pub fn filtered_files(&self) -> Vec<&FileChange> {
         if self.file_filter.is_empty() {
             self.current_file_changes.iter().collect()
@@ -273,6 +1503,64 @@ impl App {
         }
     }
 
+    pub fn toggle_relative_paths(&mut self) {
+        let display = &mut self.settings.display;
+        display.relative_paths = !display.relative_paths;
+        let state = if display.relative_paths {
+            "relative to project"
+        } else {
+            "absolute"
+        };
+        self.set_status(&format!("File paths {state}"));
+    }
+
+    /// Switch the Files panel between path order and most recently edited
+    /// first; the latter selects the newest edit
+    pub async fn toggle_files_by_recency(&mut self) {
+        let display = &mut self.settings.display;
+        display.files_by_recency = !display.files_by_recency;
+        let by_recency = display.files_by_recency;
+
+        let selected = self.selected_file_path().map(str::to_string);
+        let order = edited_files(&self.current_messages, by_recency);
+        self.current_file_changes
+            .sort_by_key(|f| order.iter().position(|p| *p == f.path));
+        self.selected_file_idx = selected
+            .filter(|_| !by_recency)
+            .and_then(|path| {
+                self.current_file_changes
+                    .iter()
+                    .position(|f| f.path == path)
+            })
+            .unwrap_or(0);
+        self.files_scroll = 0;
+
+        self.current_diff.clear();
+        self.current_file_content = None;
+        if self.diff_mode || self.focus == Focus::Files {
+            self.load_file_diff().await;
+        }
+        let state = if by_recency { "last edit" } else { "path" };
+        self.set_status(&format!("Files sorted by {state}"));
+    }
+
+    /// Mask paths and ids (and optionally chat text) on screen
+    pub fn toggle_redact(&mut self) {
+        let display = &mut self.settings.display;
+        display.redact = !display.redact;
+        let state = if display.redact { "on" } else { "off" };
+        self.set_status(&format!("Redact mode {state}"));
+    }
+
+    /// Masking for this frame, from the display settings
+    pub fn redactor(&self) -> Redactor {
+        let display = &self.settings.display;
+        Redactor {
+            on: display.redact,
+            content: display.redact_content,
+        }
+    }
+
     pub fn toggle_file_tree_mode(&mut self) {
         self.file_tree_mode = !self.file_tree_mode;
     }
@@ -286,23 +1574,31 @@ impl App {
 
     /// Copy the selected file's full path to clipboard
     pub fn yank_file_path(&mut self) -> bool {
-        if let Some(path) = self.selected_file_path() {
-            use std::io::Write;
-            use std::process::{Command, Stdio};
-
-            // Use pbcopy on macOS
-            if let Ok(mut child) = Command::new("pbcopy").stdin(Stdio::piped()).spawn() {
-                if let Some(mut stdin) = child.stdin.take() {
-                    if stdin.write_all(path.as_bytes()).is_ok() {
-                        drop(stdin);
-                        if child.wait().is_ok() {
-                            return true;
-                        }
-                    }
-                }
-            }
+        match self.selected_file_path() {
+            Some(path) => self.clipboard.set(path).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Paths of all files in the Files panel (respecting the filter), one per line
+    pub fn all_file_paths_text(&self) -> String {
+        self.filtered_files()
+            .iter()
+            .map(|f| f.path.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Copy every visible file path; returns how many were copied
+    pub fn yank_all_file_paths(&mut self) -> Option<usize> {
+        let count = self.filtered_files().len();
+        if count == 0 {
+            return Some(0);
         }
-        false
+        self.clipboard
+            .set(&self.all_file_paths_text())
+            .is_ok()
+            .then_some(count)
     }
 
     pub fn set_status(&mut self, message: &str) {
@@ -319,88 +1615,512 @@ impl App {
         self.status_message = None;
     }
 
-    pub fn list_next(&mut self) {
-        let len = self.sessions.len();
-        if len > 0 {
-            let i = self.session_list_state.selected().unwrap_or(0);
-            if i + 1 < len {
-                self.session_list_state.select(Some(i + 1));
-            }
+    pub fn list_next(&mut self) {
+        self.last_manual_nav = Some(Instant::now());
+        let len = self.visible_sessions().len();
+        if len > 0 {
+            let i = self.session_list_state.selected().unwrap_or(0);
+            if i + 1 < len {
+                self.session_list_state.select(Some(i + 1));
+            }
+        }
+    }
+
+    pub fn list_prev(&mut self) {
+        self.last_manual_nav = Some(Instant::now());
+        let len = self.visible_sessions().len();
+        if len > 0 {
+            let i = self.session_list_state.selected().unwrap_or(0);
+            if i > 0 {
+                self.session_list_state.select(Some(i - 1));
+            }
+        }
+    }
+
+    /// Current top-anchored offset of the detail view, clamped to the content
+    pub fn scroll_offset(&self) -> u16 {
+        self.chat_scroll.min(self.chat_scroll_max)
+    }
+
+    /// Scroll the detail view by `delta` lines; positive moves the viewport down.
+    /// Reaching the bottom pins the view there so it follows new content.
+    pub fn scroll_lines(&mut self, delta: i32) {
+        let offset =
+            (self.scroll_offset() as i32 + delta).clamp(0, self.chat_scroll_max as i32) as u16;
+        self.chat_scroll = if delta > 0 && offset >= self.chat_scroll_max {
+            SCROLL_BOTTOM
+        } else {
+            offset
+        };
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.chat_scroll = 0;
+    }
+
+    pub fn scroll_to_bottom(&mut self) {
+        self.chat_scroll = SCROLL_BOTTOM;
+    }
+
+    /// Jump the diff to the part shown at a clicked screen position on the minimap;
+    /// returns false when the position is outside it
+    pub fn click_minimap(&mut self, column: u16, row: u16) -> bool {
+        let Some(area) = self.minimap_area else {
+            return false;
+        };
+        if !area.contains(Position::new(column, row)) {
+            return false;
+        }
+        // The minimap is as tall as the viewport, so total = max + height
+        let total = self.chat_scroll_max.saturating_add(area.height);
+        self.chat_scroll = minimap::scroll_for_row(row - area.y, area.height, total, area.height);
+        true
+    }
+
+    /// Check once for the `claude` CLI; spawning is disabled when it is missing
+    pub fn check_claude_available(&mut self) {
+        self.claude_available = which::claude_available();
+    }
+
+    /// Show and keep a command a dry run did not start
+    fn record_dry_run(&mut self, command: String) {
+        self.set_status(&format!("Dry run: {command}"));
+        self.dry_run_log.push(command);
+    }
+
+    /// Report the missing CLI and return `false` when spawning is impossible
+    fn ensure_claude(&mut self) -> bool {
+        if !self.claude_available {
+            self.set_error(CLAUDE_INSTALL_HINT);
+        }
+        self.claude_available
+    }
+
+    /// Resume the selected session, granting `add_dirs` for this launch only
+    pub fn open_embedded_terminal(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        add_dirs: &[String],
+    ) -> anyhow::Result<()> {
+        if !self.settings.dry_run && !self.ensure_claude() {
+            return Ok(());
+        }
+        if let Some(session) = self.selected_session().cloned() {
+            let project_dir = session.project_dir();
+            // The project's own add_dirs come first, then the one-off ones
+            let add_dirs: Vec<String> = session
+                .project_meta
+                .iter()
+                .flat_map(|m| m.add_dirs.iter())
+                .chain(add_dirs)
+                .cloned()
+                .collect();
+
+            if self.settings.dry_run {
+                let script =
+                    crate::terminal::resume_claude_script(&project_dir, &session.id, &add_dirs);
+                self.record_dry_run(script);
+                return Ok(());
+            }
+
+            let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+            terminal.spawn_claude(&project_dir, &session.id, &add_dirs)?;
+            self.embedded_terminal = Some(terminal);
+            self.terminal_mode = true;
+            self.focus = Focus::Detail;
+        }
+        Ok(())
+    }
+
+    /// Start `on_select_command` for the selected session in the background;
+    /// returns the hook it ran. Off in safe mode.
+    pub fn run_select_hook(&mut self) -> Option<SelectHook> {
+        let program = &self.settings.on_select_command;
+        if program.trim().is_empty() || self.settings.safe_mode {
+            return None;
+        }
+        let session = self.selected_session()?;
+        let hook = match SelectHook::for_session(program, session) {
+            Ok(hook) => hook,
+            Err(e) => {
+                self.set_error(&e.to_string());
+                return None;
+            }
+        };
+        if let Err(e) = hook.spawn() {
+            self.set_error(&format!("on_select_command failed: {e}"));
+        }
+        Some(hook)
+    }
+
+    /// Ask for extra `--add-dir` paths before resuming the selected session
+    pub fn start_add_dir_prompt(&mut self) {
+        if self.selected_session().is_none() {
+            self.set_error("No session selected");
+            return;
+        }
+        self.add_dir_prompt = true;
+        self.add_dir_input.clear();
+    }
+
+    pub fn cancel_add_dir_prompt(&mut self) {
+        self.add_dir_prompt = false;
+        self.add_dir_input.clear();
+    }
+
+    pub fn add_dir_input_char(&mut self, c: char) {
+        self.add_dir_input.push(c);
+    }
+
+    pub fn add_dir_backspace(&mut self) {
+        self.add_dir_input.pop();
+    }
+
+    /// Space-separated dirs as absolute paths; relative ones resolve against
+    /// the selected session's project and every one must exist
+    pub fn resolve_add_dirs(&self, input: &str) -> Result<Vec<String>> {
+        let base = self
+            .selected_session()
+            .map(|s| PathBuf::from(s.project_dir()))
+            .unwrap_or_default();
+        input
+            .split_whitespace()
+            .map(|entry| {
+                let path = match entry.strip_prefix("~/") {
+                    Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+                    None => base.join(entry),
+                };
+                match path.canonicalize() {
+                    Ok(dir) if dir.is_dir() => Ok(dir.to_string_lossy().to_string()),
+                    _ => anyhow::bail!("Not a directory: {entry}"),
+                }
+            })
+            .collect()
+    }
+
+    /// Resume the selected session with the entered dirs added
+    pub fn confirm_add_dir_prompt(&mut self, cols: u16, rows: u16) {
+        let input = std::mem::take(&mut self.add_dir_input);
+        self.add_dir_prompt = false;
+        let add_dirs = match self.resolve_add_dirs(&input) {
+            Ok(dirs) => dirs,
+            Err(e) => {
+                self.set_error(&e.to_string());
+                return;
+            }
+        };
+        match self.open_embedded_terminal(cols, rows, &add_dirs) {
+            Ok(_) if self.terminal_mode => self.set_status(&format!(
+                "Opening Claude with {} extra dirs... (Ctrl+q to exit)",
+                add_dirs.len()
+            )),
+            Ok(_) => {}
+            Err(e) => self.set_error(&format!("Failed: {e}")),
+        }
+    }
+
+    /// Start a new session in `cwd` (lazychat's own directory when `None`)
+    pub fn open_new_embedded_terminal(
+        &mut self,
+        cols: u16,
+        rows: u16,
+        cwd: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if !self.settings.dry_run && !self.ensure_claude() {
+            return Ok(());
+        }
+        if self.settings.dry_run {
+            let script = crate::terminal::new_claude_script(&self.settings.new_session.args, cwd);
+            self.record_dry_run(script);
+            return Ok(());
+        }
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_new_claude(&self.settings.new_session.args, cwd)?;
+        self.embedded_terminal = Some(terminal);
+        self.terminal_mode = true;
+        self.focus = Focus::Detail;
+        Ok(())
+    }
+
+    /// Ask where to start a new session, prefilled with the selected session's project
+    pub fn start_new_session_prompt(&mut self) {
+        self.new_session_dir = match self.selected_session() {
+            Some(session) => session.project_dir(),
+            None => std::env::current_dir()
+                .map(|d| d.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        self.new_session_prompt = true;
+    }
+
+    pub fn cancel_new_session_prompt(&mut self) {
+        self.new_session_prompt = false;
+        self.new_session_dir.clear();
+    }
+
+    pub fn new_session_dir_input(&mut self, c: char) {
+        self.new_session_dir.push(c);
+    }
+
+    pub fn new_session_dir_backspace(&mut self) {
+        self.new_session_dir.pop();
+    }
+
+    /// Start the new session in the entered directory (`~/` expands to home)
+    pub fn confirm_new_session_prompt(&mut self, cols: u16, rows: u16) {
+        let input = std::mem::take(&mut self.new_session_dir);
+        self.new_session_prompt = false;
+        let dir = match input.trim().strip_prefix("~/") {
+            Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+            None => PathBuf::from(input.trim()),
+        };
+        if !dir.is_dir() {
+            self.set_error(&format!("No such directory: {}", dir.display()));
+            return;
+        }
+        match self.open_new_embedded_terminal(cols, rows, Some(&dir.to_string_lossy())) {
+            Ok(_) if self.terminal_mode => {
+                self.set_status("Starting new Claude... (Ctrl+q to exit)")
+            }
+            Ok(_) => {}
+            Err(e) => self.set_error(&format!("Failed: {e}")),
+        }
+    }
+
+    pub fn open_editor(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        // Get the currently selected file path
+        let Some(file) = self.current_file_changes.get(self.selected_file_idx) else {
+            return Ok(());
+        };
+        let file_path = &file.path;
+
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_editor(file_path)?;
+        self.enter_editor(terminal);
+        Ok(())
+    }
+
+    /// Open the selected file in `git difftool`, from the session's project
+    pub fn open_difftool(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        let Some(file) = self.current_file_changes.get(self.selected_file_idx) else {
+            return Ok(());
+        };
+        let project_dir = self
+            .selected_session()
+            .map(|s| s.project_dir())
+            .unwrap_or_default();
+
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_difftool(&project_dir, &file.path)?;
+        self.enter_editor(terminal);
+        Ok(())
+    }
+
+    /// Open every changed file in one editor, asking first when there are many
+    pub fn request_open_all_files(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        let count = self.current_file_changes.len();
+        if count > OPEN_ALL_CONFIRM_OVER {
+            self.pending_open_all = true;
+            self.set_status(&format!("Open all {count} files in the editor? (y/n)"));
+            return Ok(());
+        }
+        self.open_all_files(cols, rows)
+    }
+
+    /// Open every changed file in one editor, from the session's project
+    pub fn open_all_files(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        if self.current_file_changes.is_empty() {
+            return Ok(());
         }
+        let files: Vec<String> = self
+            .current_file_changes
+            .iter()
+            .map(|f| f.path.clone())
+            .collect();
+        let project_dir = self
+            .selected_session()
+            .map(|s| s.project_dir())
+            .unwrap_or_default();
+
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_files_editor(&project_dir, &files)?;
+        self.enter_editor(terminal);
+        self.set_status(&format!(
+            "Opening {} files... (Ctrl+q to exit)",
+            files.len()
+        ));
+        Ok(())
     }
 
-    pub fn list_prev(&mut self) {
-        let len = self.sessions.len();
-        if len > 0 {
-            let i = self.session_list_state.selected().unwrap_or(0);
-            if i > 0 {
-                self.session_list_state.select(Some(i - 1));
+    /// Quit and resume the selected session directly in the real terminal
+    pub fn resume_in_shell(&mut self) {
+        if !self.ensure_claude() {
+            return;
+        }
+        match self.selected_session() {
+            Some(session) => {
+                self.resume_on_exit = Some(ResumeCommand::for_session(session));
+                self.should_quit = true;
             }
+            None => self.set_error("No session selected"),
         }
     }
 
-    pub fn scroll_up(&mut self) {
-        if self.chat_scroll < self.chat_scroll_max {
-            self.chat_scroll = (self.chat_scroll + 3).min(self.chat_scroll_max);
-        }
+    /// Preset whose cwd is the session's project directory
+    pub fn preset_for_session(&self, session: &Session) -> Option<&Preset> {
+        let dir = std::path::PathBuf::from(session.project_dir());
+        self.presets
+            .iter()
+            .find(|p| std::path::Path::new(&p.cwd) == dir)
+    }
+
+    /// Shell line resuming the selected session (see `ResumeCommand::for_clipboard`)
+    pub fn resume_command_line(&self) -> Option<String> {
+        let session = self.selected_session()?;
+        let preset = self.preset_for_session(session);
+        Some(ResumeCommand::for_clipboard(session, preset).shell_line())
     }
 
-    pub fn scroll_down(&mut self) {
-        if self.chat_scroll > 0 {
-            self.chat_scroll = self.chat_scroll.saturating_sub(3);
+    /// Copy the selected session's resume command to the clipboard
+    pub fn yank_resume_command(&mut self) {
+        match self.resume_command_line() {
+            Some(line) if self.clipboard.set(&line).is_ok() => {
+                self.set_status(&format!("Copied: {line}"))
+            }
+            Some(_) => self.set_error("Failed to copy to clipboard"),
+            None => self.set_error("No session selected"),
         }
     }
 
-    pub fn scroll_top(&mut self) {
-        self.chat_scroll = self.chat_scroll_max;
+    /// Loaded conversation as plain text, limited to the active chat filter
+    pub fn conversation_text(&self) -> String {
+        let shown: Vec<ChatMessage> = self
+            .chat_filter
+            .select(&self.current_messages)
+            .into_iter()
+            .map(|i| self.chat_filter.parts(&self.current_messages[i]))
+            .collect();
+        render_transcript(&shown, true)
     }
 
-    pub fn scroll_bottom(&mut self) {
-        self.chat_scroll = 0;
+    /// Copy the loaded conversation as plain text to the clipboard
+    pub fn yank_conversation(&mut self) {
+        let text = self.conversation_text();
+        if text.is_empty() {
+            self.set_status("No messages to copy");
+        } else if self.clipboard.set(&text).is_ok() {
+            let count = self.chat_filter.select(&self.current_messages).len();
+            self.set_status(&format!("Copied {count} messages"));
+        } else {
+            self.set_error("Failed to copy to clipboard");
+        }
     }
 
-    pub fn open_embedded_terminal(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
-        if let Some(session) = self.selected_session().cloned() {
-            let project_dir = if session.project.starts_with('/') {
-                session.project.clone()
-            } else {
-                format!("/{}", session.project.replace('-', "/"))
-            };
+    /// Show the loaded transcript read-only in $PAGER
+    pub fn open_pager(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
+        use std::io::Write;
 
-            let mut terminal = EmbeddedTerminal::new(cols, rows)?;
-            terminal.spawn_claude(&project_dir, &session.id)?;
-            self.embedded_terminal = Some(terminal);
-            self.terminal_mode = true;
-            self.focus = Focus::Detail;
+        let Some(session) = self.selected_session() else {
+            self.set_error("No session selected");
+            return Ok(());
+        };
+        if self.current_messages.is_empty() {
+            self.set_status("Transcript is empty");
+            return Ok(());
         }
-        Ok(())
-    }
 
-    pub fn open_new_embedded_terminal(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
-        let mut terminal = EmbeddedTerminal::new(cols, rows)?;
-        terminal.spawn_new_claude()?;
+        // Unpredictable name, created exclusively; removed when the pager closes
+        let mut file = tempfile::Builder::new()
+            .prefix(&format!("lazychat-{}-", session.id))
+            .suffix(".md")
+            .tempfile()?;
+        file.write_all(transcript_markdown(&self.current_messages).as_bytes())?;
+
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_pager(file)?;
         self.embedded_terminal = Some(terminal);
         self.terminal_mode = true;
         self.focus = Focus::Detail;
+        self.set_status("Opening pager... (Ctrl+q to exit)");
         Ok(())
     }
 
-    pub fn open_editor(&mut self, cols: u16, rows: u16) -> anyhow::Result<()> {
-        if self.current_file_changes.is_empty() {
-            return Ok(());
-        }
-
-        // Get the currently selected file path
-        let file_path = &self.current_file_changes[self.selected_file_idx].path;
+    /// Open any existing file in $EDITOR (plain edit, no diff)
+    pub fn open_editor_at(&mut self, path: &Path, cols: u16, rows: u16) -> anyhow::Result<()> {
+        let mut terminal = EmbeddedTerminal::new(cols, rows)?.with_shell(&self.settings.shell);
+        terminal.spawn_file_editor(&path.to_string_lossy())?;
+        self.enter_editor(terminal);
+        Ok(())
+    }
 
-        let mut terminal = EmbeddedTerminal::new(cols, rows)?;
-        terminal.spawn_editor(file_path)?;
+    fn enter_editor(&mut self, terminal: EmbeddedTerminal) {
+        self.pre_editor_view = Some(ViewState {
+            focus: self.focus,
+            fullscreen: self.fullscreen,
+            diff_mode: self.diff_mode,
+        });
         self.embedded_terminal = Some(terminal);
         self.terminal_mode = true;
         self.editor_mode = true;
         self.focus = Focus::Detail;
         self.fullscreen = true;
-        Ok(())
+    }
+
+    pub fn start_open_path(&mut self) {
+        self.open_path_buffer.clear();
+        self.opening_path = true;
+    }
+
+    pub fn cancel_open_path(&mut self) {
+        self.opening_path = false;
+        self.open_path_buffer.clear();
+    }
+
+    pub fn open_path_input(&mut self, c: char) {
+        self.open_path_buffer.push(c);
+    }
+
+    pub fn open_path_backspace(&mut self) {
+        self.open_path_buffer.pop();
+    }
+
+    /// Resolve an entered path: `~/` expands to home, relative paths are
+    /// taken from the selected session's project (or the cwd without one)
+    pub fn resolve_open_path(&self, input: &str) -> Result<PathBuf> {
+        let input = input.trim();
+        if input.is_empty() {
+            anyhow::bail!("No path entered");
+        }
+
+        let path = if let Some(rest) = input.strip_prefix("~/") {
+            dirs::home_dir().unwrap_or_default().join(rest)
+        } else if Path::new(input).is_absolute() {
+            PathBuf::from(input)
+        } else {
+            let base = match self.selected_session() {
+                Some(session) => PathBuf::from(session.project_dir()),
+                None => std::env::current_dir()?,
+            };
+            base.join(input)
+        };
+
+        if !path.is_file() {
+            anyhow::bail!("No such file: {}", path.display());
+        }
+        Ok(path)
+    }
+
+    pub fn confirm_open_path(&mut self, cols: u16, rows: u16) {
+        let input = std::mem::take(&mut self.open_path_buffer);
+        self.opening_path = false;
+        let result = self
+            .resolve_open_path(&input)
+            .and_then(|path| self.open_editor_at(&path, cols, rows));
+        match result {
+            Ok(_) => self.set_status("Opening editor... (Ctrl+q to exit)"),
+            Err(e) => self.set_error(&format!("Failed: {e}")),
+        }
     }
 
     pub fn close_embedded_terminal(&mut self) {
@@ -410,12 +2130,17 @@ impl App {
         self.embedded_terminal = None;
         self.terminal_mode = false;
 
-        // If we were in editor mode, return to diff view (not fullscreen)
+        // If we were in editor mode, return exactly to where the editor was opened
         if self.editor_mode {
             self.editor_mode = false;
-            self.fullscreen = false;
-            self.diff_mode = true;
-            self.focus = Focus::Files;
+            let view = self.pre_editor_view.take().unwrap_or(ViewState {
+                focus: Focus::Files,
+                fullscreen: false,
+                diff_mode: true,
+            });
+            self.focus = view.focus;
+            self.fullscreen = view.fullscreen;
+            self.diff_mode = view.diff_mode;
         }
     }
 
@@ -433,96 +2158,116 @@ impl App {
         Ok(())
     }
 
-    /// Get selected session
-    pub fn selected_session(&self) -> Option<&Session> {
-        self.session_list_state
-            .selected()
-            .and_then(|i| self.sessions.get(i))
+    /// Indices into `sessions` of the sessions shown in the list (after filters)
+    pub fn visible_sessions(&self) -> Vec<usize> {
+        let now = chrono::Local::now();
+        self.sessions
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| self.session_filter_score(s).is_some())
+            .filter(|(_, s)| !self.today_only || s.touched_today(now))
+            .map(|(i, _)| i)
+            .collect()
     }
 
-    /// Get selected preset
-    pub fn selected_preset(&self) -> Option<&Preset> {
-        self.presets.get(self.selected_preset_idx)
+    /// Fuzzy score of `session` against the session filter: every term must
+    /// match its name, description, project or one of its tags
+    fn session_filter_score(&self, session: &Session) -> Option<i64> {
+        let mut fields: Vec<&str> = self
+            .session_tags(session)
+            .iter()
+            .map(String::as_str)
+            .collect();
+        fields.extend(session.custom_name.as_deref());
+        fields.extend(session.description.as_deref());
+        fields.push(&session.project);
+        terms_score(&fields, &self.session_filter)
     }
 
-    /// Get git diff info for files
-    async fn get_file_changes(file_paths: &[String]) -> Vec<FileChange> {
-        let mut changes = Vec::new();
+    /// Index into `sessions` of the selected list row
+    pub fn selected_session_index(&self) -> Option<usize> {
+        let row = self.session_list_state.selected()?;
+        self.visible_sessions().get(row).copied()
+    }
 
-        for path in file_paths {
-            let filename = std::path::Path::new(path)
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or(path)
-                .to_string();
+    /// Get selected session
+    pub fn selected_session(&self) -> Option<&Session> {
+        self.selected_session_index()
+            .and_then(|i| self.sessions.get(i))
+    }
 
-            // Try to get git diff stats for this file
-            let (status, additions, deletions) = Self::get_git_stats(path).await;
+    /// Mark assigned to a session, if any
+    pub fn session_mark(&self, session: &Session) -> Option<char> {
+        self.sidecar.as_ref().and_then(|s| s.mark(&session.key()))
+    }
 
-            changes.push(FileChange {
-                path: path.clone(),
-                filename,
-                status,
-                additions,
-                deletions,
-            });
+    /// Assign `mark` to the selected session
+    pub fn set_mark(&mut self, mark: char) {
+        let Some(key) = self.selected_session().map(|s| s.key()) else {
+            return;
+        };
+        match self.sidecar {
+            Some(ref mut sidecar) => match sidecar.set_mark(&key, mark) {
+                Ok(_) => self.set_status(&format!("Marked '{mark}")),
+                Err(e) => self.set_error(&format!("Failed to save mark: {e}")),
+            },
+            None => self.set_error("Session sidecar not loaded"),
         }
-
-        changes
     }
 
-    async fn get_git_stats(file_path: &str) -> (FileStatus, u32, u32) {
-        use tokio::process::Command;
-
-        // Get diff stats
-        let output = Command::new("git")
-            .args(["diff", "--numstat", "--", file_path])
-            .output()
-            .await;
+    /// Select the session holding `mark`, clearing filters that hide it
+    pub fn jump_to_mark(&mut self, mark: char) {
+        let Some(key) = self
+            .sidecar
+            .as_ref()
+            .and_then(|s| s.find_mark(mark))
+            .map(str::to_string)
+        else {
+            self.set_error(&format!("No session marked '{mark}"));
+            return;
+        };
+        let Some(idx) = self.sessions.iter().position(|s| s.key() == key) else {
+            self.set_error(&format!("Session marked '{mark} no longer exists"));
+            return;
+        };
 
-        if let Ok(output) = output {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if let Some(line) = stdout.lines().next() {
-                let parts: Vec<&str> = line.split_whitespace().collect();
-                if parts.len() >= 2 {
-                    let additions = parts[0].parse().unwrap_or(0);
-                    let deletions = parts[1].parse().unwrap_or(0);
-                    return (FileStatus::Modified, additions, deletions);
-                }
-            }
+        if !self.visible_sessions().contains(&idx) {
+            self.session_filter.clear();
+            self.session_filter_draft.clear();
+            self.today_only = false;
         }
-
-        // Check if file is untracked
-        let status_output = Command::new("git")
-            .args(["status", "--porcelain", "--", file_path])
-            .output()
-            .await;
-
-        if let Ok(output) = status_output {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            if let Some(line) = stdout.lines().next() {
-                let status_code = &line[..2];
-                match status_code {
-                    "??" => return (FileStatus::Untracked, 0, 0),
-                    "A " | " A" => return (FileStatus::Added, 0, 0),
-                    "D " | " D" => return (FileStatus::Deleted, 0, 0),
-                    "R " => return (FileStatus::Renamed, 0, 0),
-                    _ => {}
-                }
-            }
+        if let Some(row) = self.visible_sessions().iter().position(|&i| i == idx) {
+            self.session_list_state.select(Some(row));
+            self.focus = Focus::Sessions;
         }
+    }
+
+    /// Tags attached to a session
+    pub fn session_tags(&self, session: &Session) -> &[String] {
+        self.sidecar
+            .as_ref()
+            .map(|s| s.tags(&session.key()))
+            .unwrap_or_default()
+    }
 
-        (FileStatus::Modified, 0, 0)
+    /// Get selected preset
+    pub fn selected_preset(&self) -> Option<&Preset> {
+        self.presets.get(self.selected_preset_idx)
     }
 
     pub async fn load_file_diff(&mut self) {
         if let Some(file) = self.current_file_changes.get(self.selected_file_idx) {
             use tokio::process::Command;
 
-            let output = Command::new("git")
-                .args(["diff", "--color=never", "--", &file.path])
-                .output()
-                .await;
+            let mut cmd = Command::new("git");
+            match &file.renamed_from {
+                Some(old) => cmd.args(self.settings.diff.rename_git_args(old, &file.path)),
+                None => cmd.args(self.settings.diff.git_args(&file.path)),
+            };
+            if let Some(dir) = git::repo_dir(&file.path) {
+                cmd.current_dir(dir);
+            }
+            let output = cmd.output().await;
 
             if let Ok(output) = output {
                 self.current_diff = String::from_utf8_lossy(&output.stdout).to_string();
@@ -535,6 +2280,46 @@ impl App {
             } else {
                 self.current_diff = "Failed to load diff".to_string();
             }
+            self.current_file_content = tokio::fs::read_to_string(&file.path).await.ok();
+            self.scroll_to_top();
+        }
+    }
+
+    /// Shared highlighter, or None when disabled in settings
+    pub fn highlighter(&self) -> Option<&Highlighter> {
+        if !self.settings.display.syntax_highlighting {
+            return None;
+        }
+        Some(self.highlighter.get_or_init(Highlighter::new))
+    }
+
+    /// Toggle `git diff -w` and reload the current file's diff
+    pub async fn toggle_ignore_whitespace(&mut self) {
+        let diff = &mut self.settings.diff;
+        diff.ignore_whitespace = !diff.ignore_whitespace;
+        let state = if diff.ignore_whitespace {
+            "ignored"
+        } else {
+            "shown"
+        };
+        self.load_file_diff().await;
+        self.set_status(&format!("Whitespace changes {state}"));
+    }
+
+    /// Switch the diff pane between the unified diff and the full file
+    pub fn toggle_raw_file_view(&mut self) {
+        self.raw_file_view = !self.raw_file_view;
+    }
+
+    /// Text shown in the diff pane for the current mode
+    pub fn diff_view_text(&self) -> &str {
+        if !self.raw_file_view {
+            return &self.current_diff;
+        }
+        match &self.current_file_content {
+            Some(content) => content,
+            None if self.current_file_changes.is_empty() => "",
+            None => "File deleted or unreadable - press v to show the diff",
         }
     }
 
@@ -552,26 +2337,83 @@ impl App {
         }
     }
 
+    /// Select the next file with changes (wrapping) and show its first hunk
+    pub async fn next_changed_file(&mut self) {
+        self.step_changed_file(true).await;
+    }
+
+    /// Select the previous file with changes (wrapping) and show its first hunk
+    pub async fn prev_changed_file(&mut self) {
+        self.step_changed_file(false).await;
+    }
+
+    async fn step_changed_file(&mut self, forward: bool) {
+        let len = self.current_file_changes.len();
+        let next = (1..=len)
+            .map(|step| {
+                if forward {
+                    (self.selected_file_idx + step) % len
+                } else {
+                    (self.selected_file_idx + len * step - step) % len
+                }
+            })
+            .find(|&i| self.current_file_changes[i].has_changes());
+        let Some(idx) = next else {
+            self.set_status("No changed files");
+            return;
+        };
+
+        self.show_file_diff(idx).await;
+    }
+
+    /// Select a file and show its diff from the first hunk
+    async fn show_file_diff(&mut self, idx: usize) {
+        self.selected_file_idx = idx;
+        self.raw_file_view = false;
+        self.focus = Focus::Detail;
+        self.diff_mode = true;
+        self.load_file_diff().await;
+        if let Some(&hunk) = self.hunk_rows().first() {
+            self.chat_scroll = hunk as u16;
+        }
+    }
+
+    /// Whether the diff view gives up a column to the minimap for `lines` lines
+    pub fn diff_shows_minimap(&self, lines: usize) -> bool {
+        !self.raw_file_view && lines > self.diff_pane.height as usize && self.diff_pane.width > 20
+    }
+
+    /// Wrapped row each `@@` hunk header starts at, at the diff pane's width
+    fn hunk_rows(&self) -> Vec<usize> {
+        let text = self.diff_view_text();
+        let mut width = self.diff_pane.width as usize;
+        if self.diff_shows_minimap(text.lines().count()) {
+            width -= 1;
+        }
+        let mut row = 0;
+        let mut hunks = Vec::new();
+        for line in text.lines().map(|l| l.trim_end_matches('\r')) {
+            if line.starts_with("@@") {
+                hunks.push(row);
+            }
+            row += wrap_columns(line, width).len();
+        }
+        hunks
+    }
+
     /// Jump to next diff hunk (@@)
     pub fn jump_to_next_hunk(&mut self) {
-        let hunk_positions: Vec<usize> = self
-            .current_diff
-            .lines()
-            .enumerate()
-            .filter(|(_, line)| line.starts_with("@@"))
-            .map(|(i, _)| i)
-            .collect();
+        let hunk_positions = self.hunk_rows();
 
         if hunk_positions.is_empty() {
             return;
         }
 
         // Find next hunk after current scroll position (no wrap)
-        let current_line = self.chat_scroll_max.saturating_sub(self.chat_scroll) as usize;
+        let current_line = self.scroll_offset() as usize;
         for &pos in &hunk_positions {
             if pos > current_line {
-                let new_scroll = self.chat_scroll_max.saturating_sub(pos as u16);
-                self.chat_scroll = new_scroll;
+                self.chat_scroll = (pos as u16).min(self.chat_scroll_max);
                 return;
             }
         }
@@ -580,24 +2422,17 @@ impl App {
 
     /// Jump to previous diff hunk (@@)
     pub fn jump_to_prev_hunk(&mut self) {
-        let hunk_positions: Vec<usize> = self
-            .current_diff
-            .lines()
-            .enumerate()
-            .filter(|(_, line)| line.starts_with("@@"))
-            .map(|(i, _)| i)
-            .collect();
+        let hunk_positions = self.hunk_rows();
 
         if hunk_positions.is_empty() {
             return;
         }
 
         // Find prev hunk before current scroll position (no wrap)
-        let current_line = self.chat_scroll_max.saturating_sub(self.chat_scroll) as usize;
+        let current_line = self.scroll_offset() as usize;
         for &pos in hunk_positions.iter().rev() {
             if pos < current_line {
-                let new_scroll = self.chat_scroll_max.saturating_sub(pos as u16);
-                self.chat_scroll = new_scroll;
+                self.chat_scroll = (pos as u16).min(self.chat_scroll_max);
                 return;
             }
         }
@@ -608,7 +2443,14 @@ impl App {
         match PresetManager::load() {
             Ok(pm) => {
                 self.presets = pm.all().to_vec();
+                if !pm.duplicates().is_empty() {
+                    self.set_status(&format!(
+                        "Presets overridden: {}",
+                        pm.duplicates().join(", ")
+                    ));
+                }
                 self.preset_manager = Some(pm);
+                self.clamp_selections();
             }
             Err(e) => {
                 self.set_error(&format!("Failed to load presets: {e}"));
@@ -617,6 +2459,108 @@ impl App {
         Ok(())
     }
 
+    /// Re-read presets.toml without restarting
+    pub fn reload_presets(&mut self) {
+        let Some(manager) = self.preset_manager.as_mut() else {
+            let _ = self.load_presets();
+            return;
+        };
+        match manager.reload() {
+            Ok(()) => {
+                self.presets = manager.all().to_vec();
+                let overridden = match manager.duplicates() {
+                    [] => String::new(),
+                    names => format!(" (overridden: {})", names.join(", ")),
+                };
+                self.set_status(&format!(
+                    "Reloaded {} presets{overridden}",
+                    self.presets.len()
+                ));
+                self.clamp_selections();
+            }
+            Err(e) => self.set_error(&format!("Failed to reload presets: {e:#}")),
+        }
+    }
+
+    /// Edit presets.toml in the embedded editor
+    pub fn open_presets_config(&mut self, cols: u16, rows: u16) {
+        match self.preset_manager.as_ref() {
+            Some(manager) => {
+                let path = manager.get_config_path().clone();
+                self.open_config_file(&path, cols, rows);
+            }
+            None => self.set_error("Presets are not loaded"),
+        }
+    }
+
+    /// Edit config.toml in the embedded editor (created on save)
+    pub fn open_settings_config(&mut self, cols: u16, rows: u16) {
+        self.open_config_file(&Settings::config_path(), cols, rows);
+    }
+
+    fn open_config_file(&mut self, path: &Path, cols: u16, rows: u16) {
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match self.open_editor_at(path, cols, rows) {
+            Ok(()) => self.set_status(&format!("Editing {} (Ctrl+q to exit)", path.display())),
+            Err(e) => self.set_error(&format!("Failed: {e}")),
+        }
+    }
+
+    pub fn load_settings(&mut self) -> Result<()> {
+        self.settings_mtime = self.settings_modified();
+        match Settings::load_from(&self.settings_path) {
+            Ok(settings) => {
+                let unknown = settings.layout.unknown_panels().join(", ");
+                if !unknown.is_empty() {
+                    self.set_error(&format!("Ignoring unknown left_panels: {unknown}"));
+                }
+                self.settings = settings;
+            }
+            Err(e) => {
+                self.set_error(&format!("Failed to load config: {e}"));
+            }
+        }
+        Ok(())
+    }
+
+    fn settings_modified(&self) -> Option<SystemTime> {
+        std::fs::metadata(&self.settings_path)
+            .and_then(|m| m.modified())
+            .ok()
+    }
+
+    /// Re-read config.toml after it changes on disk and apply its theme; a
+    /// config that fails to parse keeps the current theme
+    pub fn reload_theme_if_changed(&mut self) {
+        let modified = self.settings_modified();
+        if modified == self.settings_mtime {
+            return;
+        }
+        self.settings_mtime = modified;
+        match Settings::load_from(&self.settings_path) {
+            Ok(settings) => {
+                self.settings.theme = settings.theme;
+                self.set_status("Reloaded theme from config.toml");
+            }
+            Err(e) => self.set_error(&format!("Kept previous theme: {e:#}")),
+        }
+    }
+
+    pub fn load_sidecar(&mut self) -> Result<()> {
+        match SidecarStore::load() {
+            Ok(sidecar) => {
+                sidecar.apply(&mut self.sessions);
+                self.sidecar = Some(sidecar);
+            }
+            Err(e) => {
+                self.set_error(&format!("Failed to load session sidecar: {e}"));
+            }
+        }
+        Ok(())
+    }
+
     pub fn load_process_registry(&mut self) -> Result<()> {
         match ProcessRegistry::load() {
             Ok(reg) => {
@@ -643,49 +2587,117 @@ impl App {
 
     /// Spawn all instances for the selected preset
     pub fn spawn_preset(&mut self) -> Result<()> {
+        if !self.settings.dry_run && !self.ensure_claude() {
+            return Ok(());
+        }
+
         let Some(preset) = self.selected_preset().cloned() else {
             self.set_error("No preset selected");
             return Ok(());
         };
 
+        if self.settings.dry_run {
+            return self.dry_run_preset(&preset);
+        }
+
         if self.process_registry.is_none() {
             self.set_error("Process registry not loaded");
             return Ok(());
         }
 
-        // Spawn instances
+        // Spawn every instance; one failure doesn't stop the rest
+        let mut failures = Vec::new();
         for i in 0..preset.instances {
-            match crate::process::headless::HeadlessTerminal::spawn(
-                &preset.cwd,
-                preset.add_dirs.clone(),
-                preset.extra_args.clone(),
-            ) {
-                Ok(terminal) => {
-                    let pid = terminal.pid();
-                    let session_id = terminal.session_id().to_string();
-
-                    // Register in process registry
-                    if let Some(ref mut registry) = self.process_registry {
-                        if let Err(e) = registry.register_process(
-                            pid,
-                            session_id,
-                            Some(preset.name.clone()),
-                            i,
-                            preset.cwd.clone(),
-                            preset.add_dirs.clone(),
-                        ) {
-                            self.set_error(&format!("Failed to register process: {e}"));
-                        }
-                    }
-                }
-                Err(e) => {
-                    self.set_error(&format!("Failed to spawn instance {}: {e}", i + 1));
-                    return Ok(());
-                }
+            if let Err(e) = self.spawn_preset_instance(&preset, i) {
+                failures.push(format!("#{}: {e:#}", i + 1));
             }
         }
 
-        self.set_status(&format!("Spawned {} instances for {}", preset.instances, preset.name));
+        let launched = preset.instances as usize - failures.len();
+        if failures.is_empty() {
+            self.set_status(&format!(
+                "Spawned {} instances for {}",
+                preset.instances, preset.name
+            ));
+        } else {
+            self.set_error(&format!(
+                "Launched {launched}/{}, {} failed: {}",
+                preset.instances,
+                failures.len(),
+                failures.join("; ")
+            ));
+        }
+        Ok(())
+    }
+
+    /// Record each instance's command without creating worktrees or processes
+    fn dry_run_preset(&mut self, preset: &Preset) -> Result<()> {
+        for i in 0..preset.instances {
+            let cwd = if preset.worktree {
+                worktree::worktree_path(&preset.cwd, &preset.name, i)
+                    .to_string_lossy()
+                    .to_string()
+            } else {
+                preset.cwd.clone()
+            };
+            let command = crate::process::headless::HeadlessTerminal::command_line(
+                &self.claude_program,
+                &cwd,
+                &preset.add_dirs,
+                &preset.extra_args,
+            )?;
+            self.dry_run_log.push(command);
+        }
+        let commands = &self.dry_run_log[self.dry_run_log.len() - preset.instances as usize..];
+        self.set_status(&format!("Dry run: {}", commands.join(" ; ")));
+        Ok(())
+    }
+
+    /// Start and register one preset instance
+    fn spawn_preset_instance(&mut self, preset: &Preset, i: u32) -> Result<()> {
+        // Worktree presets run each instance in its own checkout
+        let cwd = if preset.worktree {
+            worktree::create_worktree(&preset.cwd, &preset.name, i)
+                .context("failed to create worktree")?
+        } else {
+            preset.cwd.clone()
+        };
+
+        let terminal = match crate::process::headless::HeadlessTerminal::spawn_program(
+            &self.claude_program,
+            &cwd,
+            preset.add_dirs.clone(),
+            preset.extra_args.clone(),
+        ) {
+            Ok(terminal) => terminal,
+            Err(e) => {
+                if preset.worktree {
+                    let _ = worktree::remove_worktree(&preset.cwd, &cwd);
+                }
+                return Err(e.into());
+            }
+        };
+        let pid = terminal.pid();
+        let session_id = terminal.session_id().to_string();
+
+        // Register in process registry
+        if let Some(ref mut registry) = self.process_registry {
+            registry
+                .register_process(
+                    pid,
+                    session_id,
+                    Some(preset.name.clone()),
+                    i,
+                    cwd,
+                    preset.add_dirs.clone(),
+                )
+                .context("failed to register process")?;
+            if preset.worktree {
+                registry
+                    .set_worktree_repo(pid, preset.cwd.clone())
+                    .context("failed to register process")?;
+            }
+        }
         Ok(())
     }
 
@@ -702,29 +2714,69 @@ impl App {
             }
         }
 
-        // Unregister from registry
+        // Unregister from registry, remembering any worktree to clean up
+        let mut worktree_checkout = None;
         if let Some(ref mut registry) = self.process_registry {
+            worktree_checkout = registry
+                .find_by_pid(pid)
+                .and_then(|p| Some((p.worktree_repo.clone()?, p.cwd.clone())));
             let _ = registry.unregister_process(pid);
         }
 
+        if let Some((repo, path)) = worktree_checkout {
+            if let Err(e) = worktree::remove_worktree(&repo, &path) {
+                self.set_error(&format!("Killed process {pid}, kept worktree {path}: {e}"));
+                return Ok(());
+            }
+        }
+
         self.set_status(&format!("Killed process {pid}"));
         Ok(())
     }
 
-    /// Kill all managed processes
-    pub fn kill_all_processes(&mut self) -> Result<()> {
+    /// Ask to kill the managed process running the selected session
+    pub fn request_kill_session(&mut self) {
+        let Some(session) = self.selected_session() else {
+            return;
+        };
+        let pid = self
+            .process_registry
+            .as_ref()
+            .and_then(|r| r.find_by_session(&session.id))
+            .map(|p| p.pid);
+        match pid {
+            Some(pid) => {
+                self.pending_kill = Some(pid);
+                self.set_status(&format!("Kill process {pid} for this session? (y/n)"));
+            }
+            None => self.set_status("No managed process for this session"),
+        }
+    }
+
+    /// Kill all managed processes; returns how many were stopped
+    pub fn kill_all_processes(&mut self) -> Result<usize> {
         let Some(ref mut registry) = self.process_registry else {
-            return Ok(());
+            return Ok(0);
         };
 
         let pids: Vec<u32> = registry.get_all_processes().iter().map(|p| p.pid).collect();
 
-        for pid in pids {
+        for &pid in &pids {
             let _ = self.kill_process(pid);
         }
 
-        self.set_status("Killed all managed processes");
-        Ok(())
+        // A process leaves the registry only once it was signalled
+        let remaining = self
+            .process_registry
+            .as_ref()
+            .map_or(0, |r| r.get_all_processes().len());
+        let killed = pids.len().saturating_sub(remaining);
+        if killed == pids.len() {
+            self.set_status(&format!("Killed {killed} processes"));
+        } else {
+            self.set_error(&format!("Killed {killed} of {} processes", pids.len()));
+        }
+        Ok(killed)
     }
 
     /// Startup recovery: cleanup dead processes and discover orphans
@@ -742,6 +2794,7 @@ impl App {
                 }
             }
         }
+        self.refresh_process_counts();
 
         // Discover orphan sessions
         if let Some(ref registry) = self.process_registry {
@@ -762,6 +2815,47 @@ impl App {
         }
     }
 
+    /// Drop registry entries whose process has exited; returns how many
+    pub fn cleanup_dead_processes(&mut self) -> usize {
+        let Some(ref mut registry) = self.process_registry else {
+            self.set_error("Process registry not loaded");
+            return 0;
+        };
+        match registry.cleanup_dead_processes() {
+            Ok(dead) => {
+                let count = dead.len();
+                if count == 0 {
+                    self.set_status("No dead processes");
+                } else {
+                    self.set_status(&format!("Cleaned up {count} dead processes"));
+                }
+                count
+            }
+            Err(e) => {
+                self.set_error(&format!("Failed to cleanup: {e}"));
+                0
+            }
+        }
+    }
+
+    /// Re-check which managed processes are still alive
+    pub fn refresh_process_counts(&mut self) {
+        self.running_processes = self
+            .process_registry
+            .as_ref()
+            .map(|r| r.alive_count())
+            .unwrap_or(0);
+    }
+
+    /// "N running / M total" for the managed processes
+    pub fn process_summary(&self) -> String {
+        let total = self.managed_process_count();
+        format!(
+            "{} running / {total} total",
+            self.running_processes.min(total)
+        )
+    }
+
     /// Get count of managed processes
     pub fn managed_process_count(&self) -> usize {
         self.process_registry
@@ -0,0 +1,57 @@
+//! Clipboard access behind a trait so copy actions can be tested
+
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+
+pub trait Clipboard {
+    /// Replace the clipboard contents with `text`
+    fn set(&self, text: &str) -> Result<()>;
+}
+
+/// The OS clipboard (pbcopy on macOS)
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClipboard;
+
+impl Clipboard for SystemClipboard {
+    fn set(&self, text: &str) -> Result<()> {
+        let mut child = Command::new("pbcopy")
+            .stdin(Stdio::piped())
+            .spawn()
+            .context("Failed to run pbcopy")?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if !status.success() {
+            bail!("pbcopy exited with {status}");
+        }
+        Ok(())
+    }
+}
+
+/// In-memory clipboard; clones share the same history
+#[derive(Debug, Default, Clone)]
+pub struct MemoryClipboard {
+    copied: Arc<Mutex<Vec<String>>>,
+}
+
+impl MemoryClipboard {
+    /// Everything copied so far, oldest first
+    pub fn history(&self) -> Vec<String> {
+        self.copied.lock().unwrap().clone()
+    }
+
+    /// The most recent copy
+    pub fn last(&self) -> Option<String> {
+        self.copied.lock().unwrap().last().cloned()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn set(&self, text: &str) -> Result<()> {
+        self.copied.lock().unwrap().push(text.to_string());
+        Ok(())
+    }
+}
@@ -1,5 +1,9 @@
 //! Configuration management for lazychat
 
 pub mod presets;
+pub mod project;
+pub mod settings;
 
 pub use presets::{Preset, PresetManager};
+pub use project::ProjectMeta;
+pub use settings::Settings;
@@ -5,7 +5,7 @@ use fuzzy_matcher::skim::SkimMatcherV2;
 use fuzzy_matcher::FuzzyMatcher;
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// A project preset defining Claude instance configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,6 +25,9 @@ pub struct Preset {
     /// Extra CLI arguments for Claude
     #[serde(default)]
     pub extra_args: Vec<String>,
+    /// Give each instance its own git worktree (`../<name>-wt<index>`)
+    #[serde(default)]
+    pub worktree: bool,
 }
 
 fn default_instances() -> u32 {
@@ -41,15 +44,21 @@ struct PresetConfig {
 /// Manager for loading and querying presets
 pub struct PresetManager {
     presets: Vec<Preset>,
+    /// Names defined more than once; the later definition won
+    duplicates: Vec<String>,
     config_path: PathBuf,
     matcher: SkimMatcherV2,
 }
 
 impl PresetManager {
-    /// Load presets from ~/.config/lazychat/presets.toml
+    /// Load presets from ~/.config/lazychat/presets.toml and presets.d/
     pub fn load() -> Result<Self> {
-        let config_path = Self::config_path();
+        Self::load_from(Self::config_path())
+    }
 
+    /// Load presets from an explicit file (created with defaults if missing),
+    /// plus `*.toml` in the `presets.d` directory next to it
+    pub fn load_from(config_path: PathBuf) -> Result<Self> {
         // Ensure config directory exists
         if let Some(parent) = config_path.parent() {
             fs::create_dir_all(parent)?;
@@ -60,25 +69,11 @@ impl PresetManager {
             Self::create_default_config(&config_path)?;
         }
 
-        // Load and parse config
-        let content = fs::read_to_string(&config_path).context("Failed to read presets.toml")?;
-
-        let config: PresetConfig =
-            toml::from_str(&content).context("Failed to parse presets.toml")?;
-
-        // Expand ~ in paths
-        let presets: Vec<Preset> = config
-            .preset
-            .into_iter()
-            .map(|mut p| {
-                p.cwd = expand_tilde(&p.cwd);
-                p.add_dirs = p.add_dirs.into_iter().map(|d| expand_tilde(&d)).collect();
-                p
-            })
-            .collect();
+        let (presets, duplicates) = read_presets(&config_path)?;
 
         Ok(Self {
             presets,
+            duplicates,
             config_path,
             matcher: SkimMatcherV2::default(),
         })
@@ -105,6 +100,7 @@ impl PresetManager {
 # add_dirs = ["../shared-lib"]
 # instances = 2
 # extra_args = ["--dangerously-skip-permissions"]
+# worktree = true  # each instance gets its own git worktree checkout
 
 [[preset]]
 name = "lazychat"
@@ -167,42 +163,73 @@ extra_args = ["--dangerously-skip-permissions"]
             .collect();
 
         // Sort by score descending
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
         results
     }
 
     /// Reload configuration from disk
     pub fn reload(&mut self) -> Result<()> {
-        let content =
-            fs::read_to_string(&self.config_path).context("Failed to read presets.toml")?;
-
-        let config: PresetConfig =
-            toml::from_str(&content).context("Failed to parse presets.toml")?;
-
-        self.presets = config
-            .preset
-            .into_iter()
-            .map(|mut p| {
-                p.cwd = expand_tilde(&p.cwd);
-                p.add_dirs = p.add_dirs.into_iter().map(|d| expand_tilde(&d)).collect();
-                p
-            })
-            .collect();
-
+        (self.presets, self.duplicates) = read_presets(&self.config_path)?;
         Ok(())
     }
 
+    /// Preset names defined more than once (later files override earlier ones)
+    pub fn duplicates(&self) -> &[String] {
+        &self.duplicates
+    }
+
     /// Get the config file path (for display/editing)
     pub fn get_config_path(&self) -> &PathBuf {
         &self.config_path
     }
 }
 
+/// Presets from `config_path` then `presets.d/*.toml` in file-name order; a
+/// repeated name replaces the earlier preset in place and is reported
+fn read_presets(config_path: &Path) -> Result<(Vec<Preset>, Vec<String>)> {
+    let mut files = vec![config_path.to_path_buf()];
+    if let Some(parent) = config_path.parent() {
+        if let Ok(entries) = fs::read_dir(parent.join("presets.d")) {
+            let mut includes: Vec<PathBuf> = entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "toml"))
+                .collect();
+            includes.sort();
+            files.extend(includes);
+        }
+    }
+
+    let mut presets: Vec<Preset> = Vec::new();
+    let mut duplicates = Vec::new();
+    for file in files {
+        let content = fs::read_to_string(&file)
+            .with_context(|| format!("Failed to read {}", file.display()))?;
+        let config: PresetConfig = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", file.display()))?;
+
+        for mut preset in config.preset {
+            // Expand ~ in paths
+            preset.cwd = expand_tilde(&preset.cwd);
+            preset.add_dirs = preset.add_dirs.iter().map(|d| expand_tilde(d)).collect();
+            match presets.iter_mut().find(|p| p.name == preset.name) {
+                Some(existing) => {
+                    if !duplicates.contains(&preset.name) {
+                        duplicates.push(preset.name.clone());
+                    }
+                    *existing = preset;
+                }
+                None => presets.push(preset),
+            }
+        }
+    }
+    Ok((presets, duplicates))
+}
+
 /// Expand ~ to home directory in paths
-fn expand_tilde(path: &str) -> String {
-    if path.starts_with("~/") {
+pub(crate) fn expand_tilde(path: &str) -> String {
+    if let Some(rest) = path.strip_prefix("~/") {
         if let Some(home) = dirs::home_dir() {
-            return home.join(&path[2..]).to_string_lossy().to_string();
+            return home.join(rest).to_string_lossy().to_string();
         }
     } else if path == "~" {
         if let Some(home) = dirs::home_dir() {
@@ -0,0 +1,44 @@
+//! Per-project metadata read from `.lazychat.toml` in a project's root
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// File name looked up in each session's working directory
+pub const PROJECT_FILE: &str = ".lazychat.toml";
+
+/// How a repository describes itself to lazychat
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectMeta {
+    /// Name shown instead of the directory name
+    pub name: Option<String>,
+    /// Color of the project's sessions: a name ("cyan") or "#rrggbb"
+    pub color: Option<String>,
+    /// Extra directories passed as --add-dir when opening its sessions
+    pub add_dirs: Vec<String>,
+}
+
+impl ProjectMeta {
+    /// Read `dir/.lazychat.toml` (missing file = None); relative add_dirs
+    /// resolve against `dir`
+    pub fn load(dir: &Path) -> Result<Option<Self>> {
+        let path = dir.join(PROJECT_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut meta: Self = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        meta.add_dirs = meta
+            .add_dirs
+            .iter()
+            .map(|d| dir.join(super::presets::expand_tilde(d)))
+            .map(|d| d.to_string_lossy().to_string())
+            .collect();
+        Ok(Some(meta))
+    }
+}
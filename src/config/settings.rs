@@ -0,0 +1,458 @@
+//! General settings from ~/.config/lazychat/config.toml
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Top-level settings; every field has a default so the file is optional
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Shell used for `-c` spawns (claude resume, editor, pager)
+    pub shell: String,
+    /// Select the most recently active session after each refresh
+    pub auto_follow: bool,
+    /// Read-only mode: refuse every action that spawns or kills a process
+    pub safe_mode: bool,
+    /// Show the commands that spawns would run instead of starting anything
+    pub dry_run: bool,
+    /// Program run as `<program> <session id> <project dir>` whenever the
+    /// selection changes; empty disables it
+    pub on_select_command: String,
+    /// How a session description is picked from its first messages
+    pub descriptions: DescriptionRules,
+    /// Rendering options
+    pub display: DisplaySettings,
+    /// Activity ages that decide a session's status
+    pub status: StatusThresholds,
+    /// Options passed to `git diff` for the diff view
+    pub diff: DiffOptions,
+    /// Panel arrangement
+    pub layout: LayoutSettings,
+    /// How `n` starts a new Claude session
+    pub new_session: NewSessionSettings,
+    /// Colors for the diff view
+    pub theme: ThemeSettings,
+    /// Glyphs marking each session status
+    pub status_glyphs: StatusGlyphs,
+}
+
+/// Session status glyphs (`[status_glyphs]` in config.toml); unset entries
+/// use the Unicode set, or the ASCII one when `ascii` is on
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusGlyphs {
+    /// ASCII defaults (`* > o . !`) for fonts without `⟳ ▶ ● ○ ◆`
+    pub ascii: bool,
+    pub working: Option<String>,
+    pub active: Option<String>,
+    pub idle: Option<String>,
+    pub inactive: Option<String>,
+    pub waiting: Option<String>,
+}
+
+impl StatusGlyphs {
+    /// Glyph for `status`; unknown statuses share the inactive one
+    pub fn glyph(&self, status: &str) -> &str {
+        let (configured, unicode, ascii) = match status {
+            "working" => (&self.working, "⟳", "*"),
+            "active" => (&self.active, "▶", ">"),
+            "idle" => (&self.idle, "●", "o"),
+            "waiting" => (&self.waiting, "◆", "!"),
+            _ => (&self.inactive, "○", "."),
+        };
+        match configured {
+            Some(glyph) => glyph,
+            None if self.ascii => ascii,
+            None => unicode,
+        }
+    }
+
+    /// Whether working sessions get the Unicode spinner instead of their glyph
+    pub fn animate_working(&self) -> bool {
+        !self.ascii && self.working.is_none()
+    }
+}
+
+/// Diff colors (`[theme]` in config.toml): a color name or `#rrggbb`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSettings {
+    /// Added lines
+    pub diff_add: String,
+    /// Removed lines
+    pub diff_remove: String,
+    /// `@@` hunk headers
+    pub diff_hunk: String,
+    /// `diff`/`index` file headers
+    pub diff_header: String,
+}
+
+impl Default for ThemeSettings {
+    fn default() -> Self {
+        Self {
+            diff_add: "green".to_string(),
+            diff_remove: "red".to_string(),
+            diff_hunk: "cyan".to_string(),
+            diff_header: "yellow".to_string(),
+        }
+    }
+}
+
+/// New-session options (`[new_session]` in config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NewSessionSettings {
+    /// Arguments passed to `claude`
+    pub args: Vec<String>,
+    /// Ask for the working directory instead of using lazychat's
+    pub prompt_cwd: bool,
+}
+
+impl Default for NewSessionSettings {
+    fn default() -> Self {
+        Self {
+            args: vec!["--dangerously-skip-permissions".to_string()],
+            prompt_cwd: false,
+        }
+    }
+}
+
+/// Panel arrangement (`[layout]` in config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutSettings {
+    /// Left-column panels top to bottom: `name`, `name:ROWS` (fixed height) or
+    /// `name:Nx` (share of the remaining space, default 1x)
+    pub left_panels: Vec<String>,
+    /// Always show one column: the sidebar, or the detail view when focused
+    pub single_column: bool,
+    /// Switch to one column below this terminal width (0 = never)
+    pub single_column_below: u16,
+}
+
+impl Default for LayoutSettings {
+    fn default() -> Self {
+        Self {
+            left_panels: vec![
+                "sessions".to_string(),
+                "files".to_string(),
+                "todos".to_string(),
+            ],
+            single_column: false,
+            single_column_below: 90,
+        }
+    }
+}
+
+/// A panel that can be placed in the left column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Sessions,
+    Files,
+    Todos,
+}
+
+/// How tall a left-column panel is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanelHeight {
+    /// Exactly this many rows
+    Fixed(u16),
+    /// Weighted share of the rows left after fixed panels
+    Ratio(u16),
+}
+
+impl LayoutSettings {
+    /// Parsed panels in order; unknown or repeated entries are skipped, and an
+    /// empty result falls back to the default arrangement
+    pub fn panels(&self) -> Vec<(Panel, PanelHeight)> {
+        let mut panels: Vec<(Panel, PanelHeight)> = Vec::new();
+        for entry in &self.left_panels {
+            if let Some((panel, height)) = parse_panel(entry) {
+                if !panels.iter().any(|(p, _)| *p == panel) {
+                    panels.push((panel, height));
+                }
+            }
+        }
+        if panels.is_empty() {
+            return Self::default().panels();
+        }
+        panels
+    }
+
+    /// Entries of `left_panels` that could not be parsed
+    pub fn unknown_panels(&self) -> Vec<&str> {
+        self.left_panels
+            .iter()
+            .filter(|entry| parse_panel(entry).is_none())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+fn parse_panel(entry: &str) -> Option<(Panel, PanelHeight)> {
+    let (name, size) = match entry.split_once(':') {
+        Some((name, size)) => (name, Some(size.trim())),
+        None => (entry, None),
+    };
+    let panel = match name.trim().to_lowercase().as_str() {
+        "sessions" => Panel::Sessions,
+        "files" => Panel::Files,
+        "todos" => Panel::Todos,
+        _ => return None,
+    };
+    let height = match size {
+        None => PanelHeight::Ratio(1),
+        Some(size) => match size.strip_suffix('x') {
+            Some(weight) => PanelHeight::Ratio(weight.parse().ok().filter(|&w| w > 0)?),
+            None => PanelHeight::Fixed(size.parse().ok()?),
+        },
+    };
+    Some((panel, height))
+}
+
+/// `git diff` options (`[diff]` in config.toml)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DiffOptions {
+    /// Lines of context around each change (`-U<n>`)
+    pub context_lines: u32,
+    /// Ignore whitespace changes (`-w`); toggled live with `w`
+    pub ignore_whitespace: bool,
+    /// Ignore changes whose lines are all blank
+    pub ignore_blank_lines: bool,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            context_lines: 3,
+            ignore_whitespace: false,
+            ignore_blank_lines: false,
+        }
+    }
+}
+
+impl DiffOptions {
+    /// Arguments for `git` that diff a single file with these options
+    pub fn git_args(&self, path: &str) -> Vec<String> {
+        let mut args = self.diff_args();
+        args.push("--".to_string());
+        args.push(path.to_string());
+        args
+    }
+
+    /// Arguments for diffing a staged rename against HEAD, so git reports the
+    /// similarity and any content changes across both paths
+    pub fn rename_git_args(&self, old: &str, new: &str) -> Vec<String> {
+        let mut args = self.diff_args();
+        args.extend(["-M", "HEAD", "--", old, new].map(String::from));
+        args
+    }
+
+    fn diff_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "diff".to_string(),
+            "--color=never".to_string(),
+            format!("-U{}", self.context_lines),
+        ];
+        if self.ignore_whitespace {
+            args.push("-w".to_string());
+        }
+        if self.ignore_blank_lines {
+            args.push("--ignore-blank-lines".to_string());
+        }
+        args
+    }
+}
+
+/// Age limits (in seconds) for session statuses (`[status]` in config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusThresholds {
+    /// Transcript written within this many seconds = working
+    pub working_secs: u64,
+    /// ... within this many = active
+    pub active_secs: u64,
+    /// ... within this many = idle, older = inactive
+    pub idle_secs: u64,
+    /// Hook state files reporting working/active that are older than this are
+    /// considered stale (the hook likely died) and downgraded by age
+    pub stale_state_secs: u64,
+    /// Without a hook state file, show sessions whose transcript ends with
+    /// Claude's reply as waiting (until they turn inactive)
+    pub infer_waiting: bool,
+}
+
+impl Default for StatusThresholds {
+    fn default() -> Self {
+        Self {
+            working_secs: 10,
+            active_secs: 120,
+            idle_secs: 30 * 60,
+            stale_state_secs: 10 * 60,
+            infer_waiting: false,
+        }
+    }
+}
+
+impl StatusThresholds {
+    /// Status from the age of the last transcript write
+    pub fn status_for_age(&self, age_secs: u64) -> &'static str {
+        if age_secs < self.working_secs {
+            "working"
+        } else if age_secs < self.active_secs {
+            "active"
+        } else if age_secs < self.idle_secs {
+            "idle"
+        } else {
+            "inactive"
+        }
+    }
+
+    /// Status from the transcript alone: its age, and whether it ends with
+    /// Claude waiting on the user (only used with `infer_waiting`)
+    pub fn status_for_transcript(&self, age_secs: u64, awaits_input: bool) -> &'static str {
+        match self.status_for_age(age_secs) {
+            "inactive" => "inactive",
+            _ if self.infer_waiting && awaits_input => "waiting",
+            status => status,
+        }
+    }
+
+    /// Status from a hook state file written `age_secs` ago; stale
+    /// working/active states fall back to idle/inactive
+    pub fn status_for_state(&self, state: &str, age_secs: u64) -> String {
+        match state {
+            "working" | "active" if age_secs >= self.stale_state_secs => {
+                if age_secs < self.idle_secs {
+                    "idle".to_string()
+                } else {
+                    "inactive".to_string()
+                }
+            }
+            _ => state.to_string(),
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            shell: "bash".to_string(),
+            auto_follow: false,
+            safe_mode: false,
+            dry_run: false,
+            on_select_command: String::new(),
+            descriptions: DescriptionRules::default(),
+            display: DisplaySettings::default(),
+            status: StatusThresholds::default(),
+            diff: DiffOptions::default(),
+            layout: LayoutSettings::default(),
+            new_session: NewSessionSettings::default(),
+            theme: ThemeSettings::default(),
+            status_glyphs: StatusGlyphs::default(),
+        }
+    }
+}
+
+/// Rendering options (`[display]` in config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    /// Syntax-highlight diffs and file views; turn off on slow terminals
+    pub syntax_highlighting: bool,
+    /// Switch to a minimal status screen after this many seconds without
+    /// input (0 = never); any key wakes it
+    pub dim_after_secs: u64,
+    /// Show consecutive same-role messages under a single header
+    pub merge_messages: bool,
+    /// Right-aligned clock and refresh heartbeat in the footer
+    pub show_clock: bool,
+    /// Files panel shows paths relative to the session's project dir
+    pub relative_paths: bool,
+    /// Wrap chat text at this many columns even in wider panes (0 = pane width)
+    pub max_line_width: usize,
+    /// Files panel lists the most recently edited file first and selects it
+    pub files_by_recency: bool,
+    /// Mask home-directory user names and session ids for screenshots
+    pub redact: bool,
+    /// While redacting, also show message text as its length only
+    pub redact_content: bool,
+    /// Note thinking time and tokens on assistant headers, when recorded
+    pub show_thinking: bool,
+}
+
+impl Default for DisplaySettings {
+    fn default() -> Self {
+        Self {
+            syntax_highlighting: true,
+            dim_after_secs: 0,
+            merge_messages: false,
+            show_clock: false,
+            relative_paths: false,
+            max_line_width: 0,
+            files_by_recency: false,
+            redact: false,
+            redact_content: false,
+            show_thinking: false,
+        }
+    }
+}
+
+/// Rules for choosing a user message as a session description
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DescriptionRules {
+    /// Messages shorter than this (in characters) are skipped
+    pub min_length: usize,
+    /// Messages starting with any of these are skipped (commands, tags)
+    pub skip_prefixes: Vec<String>,
+}
+
+impl Default for DescriptionRules {
+    fn default() -> Self {
+        Self {
+            min_length: 5,
+            skip_prefixes: vec!["/".to_string(), "<".to_string()],
+        }
+    }
+}
+
+impl DescriptionRules {
+    /// Whether a user message is substantive enough to describe a session
+    pub fn accepts(&self, text: &str) -> bool {
+        let text = text.trim();
+        text.chars().count() >= self.min_length
+            && !self
+                .skip_prefixes
+                .iter()
+                .any(|p| !p.is_empty() && text.starts_with(p.as_str()))
+    }
+}
+
+impl Settings {
+    /// Load settings from ~/.config/lazychat/config.toml (missing file = defaults)
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::config_path())
+    }
+
+    /// Load settings from an explicit path (missing file = defaults)
+    pub fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(path).context("Failed to read config.toml")?;
+        toml::from_str(&content).context("Failed to parse config.toml")
+    }
+
+    /// Get the settings file path
+    pub fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("~/.config"))
+            .join("lazychat")
+            .join("config.toml")
+    }
+}
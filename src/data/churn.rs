@@ -0,0 +1,53 @@
+//! Uncommitted churn per active session, for the overview across sessions
+
+use super::git::FileStat;
+use super::Session;
+use crate::redact::Redactor;
+use std::collections::HashMap;
+
+/// One overview row: a session and its project's uncommitted changes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChurnRow {
+    pub session_id: String,
+    /// custom name > description > project name, as in the session list
+    pub name: String,
+    pub project: String,
+    pub files: usize,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+impl ChurnRow {
+    /// Lines touched either way
+    pub fn total(&self) -> u32 {
+        self.additions + self.deletions
+    }
+}
+
+/// Rows for every session that isn't inactive, using `stats` keyed by project
+/// dir; busiest first, sessions without stats last
+pub fn churn_rows(
+    sessions: &[Session],
+    stats: &HashMap<String, HashMap<String, FileStat>>,
+    redact: Redactor,
+) -> Vec<ChurnRow> {
+    let mut rows: Vec<ChurnRow> = sessions
+        .iter()
+        .filter(|s| s.status != "inactive")
+        .map(|session| {
+            let project = session.project_dir();
+            let files = stats.get(&project);
+            let name = redact.session_name(session);
+            ChurnRow {
+                session_id: session.id.clone(),
+                name,
+                files: files.map_or(0, |f| f.len()),
+                additions: files.map_or(0, |f| f.values().map(|s| s.additions).sum()),
+                deletions: files.map_or(0, |f| f.values().map(|s| s.deletions).sum()),
+                project,
+            }
+        })
+        .collect();
+    rows.sort_by(|a, b| b.total().cmp(&a.total()).then(b.files.cmp(&a.files)));
+    rows
+}
@@ -1,14 +1,162 @@
-use super::{Agent, ChatMessage, Session, TodoItem, ToolCall};
+use super::{session_key, Agent, ChatMessage, Session, ThinkingStats, TodoItem, ToolCall};
+use crate::config::settings::{DescriptionRules, Settings, StatusThresholds};
+use crate::config::{project::PROJECT_FILE, ProjectMeta};
+use crate::text::trim_line_cr;
 use anyhow::Result;
 use chrono::{DateTime, Utc};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use tokio::fs;
 
+/// How many transcript lines to scan for a description when history has none
+const DESCRIPTION_SCAN_LINES: usize = 200;
+
+/// Leading transcript lines searched for the first timestamp
+const START_SCAN_LINES: usize = 20;
+
+/// Trailing transcript bytes searched for the last turn
+const END_SCAN_BYTES: u64 = 64 * 1024;
+
+/// What the first lines of a transcript say about the session
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TranscriptHead {
+    /// Timestamp of the first message
+    pub started_at: Option<DateTime<Utc>>,
+    /// Directory Claude was started in
+    pub cwd: Option<String>,
+}
+
+/// What earlier loads read from each transcript, with the size it was read
+/// at; kept on `App` so a refresh only rereads transcripts that grew
+#[derive(Debug, Default)]
+pub struct LoadCache {
+    heads: HashMap<PathBuf, (u64, TranscriptHead)>,
+    /// Whether the transcript ends on Claude's reply
+    ends: HashMap<PathBuf, (u64, bool)>,
+    /// Descriptions found in transcripts, under `description_rules`
+    descriptions: HashMap<PathBuf, (u64, Option<String>)>,
+    description_rules: Option<DescriptionRules>,
+    /// `.lazychat.toml` per project directory, with the file's mtime
+    project_metas: HashMap<String, (Option<SystemTime>, ProjectMetaLoad)>,
+    /// Sub-agent trees per session key, with the files and sizes they came from
+    agent_trees: HashMap<String, (Vec<AgentSource>, Vec<Agent>)>,
+}
+
+/// A file an agent tree is read from, with its size
+type AgentSource = (PathBuf, u64);
+
+/// A project's `.lazychat.toml`: absent, parsed, or why it could not be read
+type ProjectMetaLoad = Result<Option<ProjectMeta>, String>;
+
+impl LoadCache {
+    /// Head of the transcript at `path` of `len` bytes; a known start time is
+    /// final, anything else is read again once the file grows
+    async fn head(&mut self, path: &Path, len: u64) -> TranscriptHead {
+        if let Some((seen, head)) = self.heads.get(path) {
+            if head.started_at.is_some() || *seen == len {
+                return head.clone();
+            }
+        }
+        let head = ClaudeData::transcript_head(path).await;
+        self.heads.insert(path.to_path_buf(), (len, head.clone()));
+        head
+    }
+
+    /// `ClaudeData::awaits_input`, read again only when the size changed
+    async fn awaits_input(&mut self, path: &Path, len: u64) -> bool {
+        if let Some(&(seen, waiting)) = self.ends.get(path) {
+            if seen == len {
+                return waiting;
+            }
+        }
+        let waiting = ClaudeData::awaits_input(path, len).await;
+        self.ends.insert(path.to_path_buf(), (len, waiting));
+        waiting
+    }
+
+    /// `ClaudeData::transcript_description`; a found one is final, a miss is
+    /// looked for again once the file grows or the rules change
+    async fn description(&mut self, path: &Path, rules: &DescriptionRules) -> Option<String> {
+        if self.description_rules.as_ref() != Some(rules) {
+            self.descriptions.clear();
+            self.description_rules = Some(rules.clone());
+        }
+        let len = fs::metadata(path).await.map(|m| m.len()).unwrap_or(0);
+        if let Some((seen, description)) = self.descriptions.get(path) {
+            if description.is_some() || *seen == len {
+                return description.clone();
+            }
+        }
+        let description = ClaudeData::transcript_description(path, rules).await;
+        self.descriptions
+            .insert(path.to_path_buf(), (len, description.clone()));
+        description
+    }
+
+    /// `ProjectMeta::load` for `dir`, read again only when the file changed
+    fn project_meta(&mut self, dir: &str) -> ProjectMetaLoad {
+        let mtime = std::fs::metadata(Path::new(dir).join(PROJECT_FILE))
+            .and_then(|m| m.modified())
+            .ok();
+        if let Some((seen, meta)) = self.project_metas.get(dir) {
+            if *seen == mtime {
+                return meta.clone();
+            }
+        }
+        let meta = ProjectMeta::load(Path::new(dir)).map_err(|e| format!("{e:#}"));
+        self.project_metas
+            .insert(dir.to_string(), (mtime, meta.clone()));
+        meta
+    }
+
+    /// `ClaudeData::load_session_agents`, rebuilt only when one of its files
+    /// changed size or a sub-agent file appeared
+    pub async fn session_agents(&mut self, session: &Session) -> Result<Vec<Agent>> {
+        let sources = ClaudeData::agent_sources(session).await?;
+        if let Some((seen, agents)) = self.agent_trees.get(&session.key()) {
+            if *seen == sources {
+                return Ok(agents.clone());
+            }
+        }
+        let agents = ClaudeData::read_agent_tree(&session.id, &sources).await?;
+        self.agent_trees
+            .insert(session.key(), (sources, agents.clone()));
+        Ok(agents)
+    }
+
+    /// Forget transcripts that no longer exist
+    fn retain(&mut self, sessions: &[Session]) {
+        let live: HashSet<&Path> = sessions
+            .iter()
+            .filter_map(|s| s.file_path.as_deref())
+            .collect();
+        self.heads.retain(|path, _| live.contains(path.as_path()));
+        self.ends.retain(|path, _| live.contains(path.as_path()));
+        self.descriptions
+            .retain(|path, _| live.contains(path.as_path()));
+        let dirs: HashSet<String> = sessions.iter().map(|s| s.project_dir()).collect();
+        self.project_metas.retain(|dir, _| dirs.contains(dir));
+        let keys: HashSet<String> = sessions.iter().map(|s| s.key()).collect();
+        self.agent_trees.retain(|key, _| keys.contains(key));
+    }
+}
+
+/// Read position in a transcript that is still being written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TailCursor {
+    /// Bytes already parsed
+    pub offset: u64,
+    /// Complete lines already parsed
+    pub line: usize,
+}
+
 pub struct ClaudeData {
     pub sessions: Vec<Session>,
     pub agents: Vec<Agent>,
+    /// `.lazychat.toml` files that could not be read or parsed
+    pub project_errors: Vec<String>,
 }
 
 impl ClaudeData {
@@ -16,21 +164,41 @@ impl ClaudeData {
         dirs::home_dir().unwrap_or_default().join(".claude")
     }
 
-    pub async fn load() -> Result<Self> {
-        let claude_dir = Self::claude_dir();
+    pub async fn load(settings: &Settings) -> Result<Self> {
+        Self::load_from(&Self::claude_dir(), settings).await
+    }
+
+    /// Load sessions and agents from an explicit Claude directory
+    pub async fn load_from(claude_dir: &Path, settings: &Settings) -> Result<Self> {
+        Self::load_cached(claude_dir, settings, &mut LoadCache::default()).await
+    }
 
-        let mut sessions = Self::load_sessions(&claude_dir).await?;
-        let agents = Self::load_agents(&claude_dir).await?;
+    /// `load_from`, reusing what `cache` kept from the previous load
+    pub async fn load_cached(
+        claude_dir: &Path,
+        settings: &Settings,
+        cache: &mut LoadCache,
+    ) -> Result<Self> {
+        let mut sessions = Self::load_sessions(claude_dir, &settings.status, cache).await?;
+        cache.retain(&sessions);
+        let agents = Self::load_agents(claude_dir).await?;
 
         // Load history to get first user messages as descriptions
-        let history = Self::load_history(&claude_dir).await.unwrap_or_default();
+        let rules = &settings.descriptions;
+        let history = Self::load_history(claude_dir, rules)
+            .await
+            .unwrap_or_default();
 
         // Load tasks from ~/.claude/tasks/{sessionId}/*.json
-        let tasks_by_session = Self::load_tasks_by_session(&claude_dir)
+        // (task and todo files carry no project, so they stay keyed by id)
+        let tasks_by_session = Self::load_tasks_by_session(claude_dir)
             .await
             .unwrap_or_default();
 
-        // Populate todos and descriptions into each session
+        // Each project's file once per load
+        let mut project_metas: HashMap<String, ProjectMetaLoad> = HashMap::new();
+
+        // Populate todos, project metadata and descriptions into each session
         for session in &mut sessions {
             // Add todos from agents (old system: ~/.claude/todos/)
             let mut session_todos: Vec<TodoItem> = agents
@@ -46,19 +214,43 @@ impl ClaudeData {
 
             session.todos = session_todos;
 
-            // Add description from history (first user message)
-            if let Some(desc) = history.get(&session.id) {
+            // Project metadata from `.lazychat.toml` in the session's directory
+            let meta = project_metas
+                .entry(session.project_dir())
+                .or_insert_with_key(|dir| cache.project_meta(dir));
+            if let Ok(Some(meta)) = meta {
+                if let Some(name) = &meta.name {
+                    session.project_name = name.clone();
+                }
+                session.project_meta = Some(meta.clone());
+            }
+
+            // Add description from history (first user message), falling back to
+            // entries recorded without a project, then to the transcript itself
+            if let Some(desc) = history
+                .get(&session.key())
+                .or_else(|| history.get(&session_key("", &session.id)))
+            {
                 session.description = Some(desc.clone());
+            } else if let Some(path) = &session.file_path {
+                session.description = cache.description(path, rules).await;
             }
         }
 
-        Ok(Self { sessions, agents })
+        let mut project_errors: Vec<String> = project_metas
+            .into_values()
+            .filter_map(Result::err)
+            .collect();
+        project_errors.sort();
+        Ok(Self {
+            sessions,
+            agents,
+            project_errors,
+        })
     }
 
     /// Load tasks from ~/.claude/tasks/{sessionId}/*.json
-    async fn load_tasks_by_session(
-        claude_dir: &std::path::Path,
-    ) -> Result<HashMap<String, Vec<TodoItem>>> {
+    async fn load_tasks_by_session(claude_dir: &Path) -> Result<HashMap<String, Vec<TodoItem>>> {
         let tasks_dir = claude_dir.join("tasks");
         let mut tasks_map: HashMap<String, Vec<TodoItem>> = HashMap::new();
 
@@ -127,8 +319,11 @@ impl ClaudeData {
         Ok(tasks_map)
     }
 
-    /// Load history.jsonl to extract first user messages per session
-    async fn load_history(claude_dir: &std::path::Path) -> Result<HashMap<String, String>> {
+    /// Load history.jsonl to extract first user messages per session, keyed by `session_key`
+    async fn load_history(
+        claude_dir: &Path,
+        rules: &DescriptionRules,
+    ) -> Result<HashMap<String, String>> {
         let history_file = claude_dir.join("history.jsonl");
         let mut descriptions: HashMap<String, String> = HashMap::new();
 
@@ -140,27 +335,23 @@ impl ClaudeData {
 
         for line in content.lines() {
             if let Ok(json) = serde_json::from_str::<Value>(line) {
-                let session_id = json
-                    .get("sessionId")
+                let session_id = json.get("sessionId").and_then(|v| v.as_str()).unwrap_or("");
+                let project = json
+                    .get("project")
                     .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
+                    .map(project_from_path)
+                    .unwrap_or_default();
+                let key = session_key(&project, session_id);
 
                 // Skip if we already have a description for this session
-                if descriptions.contains_key(&session_id) {
+                if descriptions.contains_key(&key) {
                     continue;
                 }
 
                 if let Some(display) = json.get("display").and_then(|v| v.as_str()) {
-                    // Skip commands (start with /)
-                    if display.starts_with('/') || display.starts_with('<') {
-                        continue;
-                    }
-                    // Skip very short messages
-                    if display.len() < 5 {
-                        continue;
+                    if rules.accepts(display) {
+                        descriptions.insert(key, display.to_string());
                     }
-                    descriptions.insert(session_id, display.to_string());
                 }
             }
         }
@@ -168,6 +359,109 @@ impl ClaudeData {
         Ok(descriptions)
     }
 
+    /// First substantive user message in a transcript (only the first lines are scanned)
+    pub async fn transcript_description(path: &Path, rules: &DescriptionRules) -> Option<String> {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let file = fs::File::open(path).await.ok()?;
+        let mut lines = BufReader::new(file).lines();
+        let mut scanned = 0;
+
+        while let Ok(Some(line)) = lines.next_line().await {
+            scanned += 1;
+            if scanned > DESCRIPTION_SCAN_LINES {
+                break;
+            }
+
+            let Ok(json) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if json.get("type").and_then(|v| v.as_str()) != Some("user")
+                || json.get("isSidechain").and_then(|v| v.as_bool()) == Some(true)
+            {
+                continue;
+            }
+
+            let text = json
+                .get("message")
+                .and_then(|m| m.get("content"))
+                .and_then(content_text)
+                .unwrap_or_default();
+            if rules.accepts(&text) {
+                return Some(text.trim().to_string());
+            }
+        }
+
+        None
+    }
+
+    /// First timestamp and cwd found in a transcript's leading lines
+    pub async fn transcript_head(path: &Path) -> TranscriptHead {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let mut head = TranscriptHead::default();
+        let Ok(file) = fs::File::open(path).await else {
+            return head;
+        };
+        let mut lines = BufReader::new(file).lines();
+        let mut scanned = 0;
+        while let Ok(Some(line)) = lines.next_line().await {
+            scanned += 1;
+            if scanned > START_SCAN_LINES {
+                break;
+            }
+            let Ok(json) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if head.started_at.is_none() {
+                head.started_at = json
+                    .get("timestamp")
+                    .and_then(|t| t.as_str())
+                    .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                    .map(|dt| dt.with_timezone(&Utc));
+            }
+            if head.cwd.is_none() {
+                head.cwd = json.get("cwd").and_then(|v| v.as_str()).map(str::to_string);
+            }
+            if head.started_at.is_some() && head.cwd.is_some() {
+                break;
+            }
+        }
+        head
+    }
+
+    /// Whether the last turn in a transcript of `len` bytes is Claude's
+    /// finished reply (no tool call pending), i.e. it is waiting for the user
+    pub async fn awaits_input(path: &Path, len: u64) -> bool {
+        use std::io::SeekFrom;
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let Ok(mut file) = fs::File::open(path).await else {
+            return false;
+        };
+        let start = len.saturating_sub(END_SCAN_BYTES);
+        let mut tail = Vec::new();
+        if file.seek(SeekFrom::Start(start)).await.is_err()
+            || file.read_to_end(&mut tail).await.is_err()
+        {
+            return false;
+        }
+        let tail = String::from_utf8_lossy(&tail);
+        // A scan starting mid-file begins inside a line
+        let lines = tail.lines().skip(usize::from(start > 0));
+
+        let last_turn = lines
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter(|json| matches!(json["type"].as_str(), Some("user" | "assistant")))
+            .last();
+        last_turn.is_some_and(|json| {
+            json["type"] == "assistant"
+                && !json["message"]["content"]
+                    .as_array()
+                    .is_some_and(|blocks| blocks.iter().any(|b| b["type"] == "tool_use"))
+        })
+    }
+
     /// Load chat messages from a session's transcript file
     pub async fn load_session_messages(session: &Session) -> Result<Vec<ChatMessage>> {
         let file_path = match &session.file_path {
@@ -180,162 +474,223 @@ impl ClaudeData {
         }
 
         let content = fs::read_to_string(&file_path).await?;
+        Ok(content
+            .lines()
+            .enumerate()
+            .filter_map(|(idx, line)| parse_message_line(line, idx + 1))
+            .collect())
+    }
+
+    /// Parse what was appended to a transcript since `cursor`. A trailing line
+    /// without a newline is only taken once it is complete JSON.
+    pub async fn tail_messages(
+        path: &Path,
+        mut cursor: TailCursor,
+    ) -> Result<(Vec<ChatMessage>, TailCursor)> {
+        use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+        let mut file = fs::File::open(path).await?;
+        file.seek(std::io::SeekFrom::Start(cursor.offset)).await?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended).await?;
+
         let mut messages = Vec::new();
+        let mut segments = appended.split(|&b| b == b'\n').peekable();
+        while let Some(segment) = segments.next() {
+            let text = String::from_utf8_lossy(segment);
+            let terminated = segments.peek().is_some();
+            if !terminated && serde_json::from_str::<Value>(&text).is_err() {
+                // Still being written
+                break;
+            }
+            messages.extend(parse_message_line(&text, cursor.line + 1));
+            cursor.offset += segment.len() as u64;
+            if terminated {
+                cursor.offset += 1;
+                cursor.line += 1;
+            }
+        }
+
+        Ok((messages, cursor))
+    }
+
+    /// Pretty-printed JSON of one transcript line (1-based), for debugging
+    pub async fn load_raw_message(session: &Session, line: usize) -> Result<Option<String>> {
+        let Some(file_path) = &session.file_path else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(file_path).await?;
+        let Some(raw) = content.lines().nth(line.saturating_sub(1)) else {
+            return Ok(None);
+        };
+        Ok(Some(match serde_json::from_str::<Value>(raw) {
+            Ok(json) => serde_json::to_string_pretty(&json)?,
+            Err(_) => raw.to_string(),
+        }))
+    }
+
+    /// Load the sub-agent tree for a session from its transcript and any
+    /// `{sessionId}/subagents/*.jsonl` files next to it
+    pub async fn load_session_agents(session: &Session) -> Result<Vec<Agent>> {
+        let sources = Self::agent_sources(session).await?;
+        Self::read_agent_tree(&session.id, &sources).await
+    }
+
+    /// Files the agent tree is built from, with their sizes: the transcript
+    /// first, then its sub-agent transcripts
+    async fn agent_sources(session: &Session) -> Result<Vec<AgentSource>> {
+        let file_path = match &session.file_path {
+            Some(p) if p.exists() => p.clone(),
+            _ => return Ok(Vec::new()),
+        };
+        let mut sources = vec![(file_path.clone(), fs::metadata(&file_path).await?.len())];
+
+        let subagents_dir = file_path.with_extension("").join("subagents");
+        if subagents_dir.exists() {
+            let mut entries = fs::read_dir(&subagents_dir).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("jsonl") {
+                    continue;
+                }
+                let len = entry.metadata().await.map(|m| m.len()).unwrap_or(0);
+                sources.push((path, len));
+            }
+            sources[1..].sort();
+        }
+        Ok(sources)
+    }
+
+    async fn read_agent_tree(session_id: &str, sources: &[AgentSource]) -> Result<Vec<Agent>> {
+        let Some(((transcript, _), subagents)) = sources.split_first() else {
+            return Ok(Vec::new());
+        };
+        let mut content = fs::read_to_string(transcript).await?;
+        for (path, _) in subagents {
+            content.push('\n');
+            content.push_str(&fs::read_to_string(path).await.unwrap_or_default());
+        }
+        Ok(Self::parse_agent_tree(session_id, &content))
+    }
+
+    /// Build a nested agent tree from transcript lines.
+    ///
+    /// Sidechain entries carry an `agentId`; an agent's parent is the agent that
+    /// owns the `parentUuid` of its first entry. Agents spawned from the main
+    /// conversation are returned as roots with `parent_id: None`.
+    pub fn parse_agent_tree(session_id: &str, content: &str) -> Vec<Agent> {
+        // uuid -> owning agent id ("" for the main conversation)
+        let mut owners: HashMap<String, String> = HashMap::new();
+        // agent id -> first entry, in spawn order
+        let mut order: Vec<String> = Vec::new();
+        let mut firsts: HashMap<String, AgentSpawn> = HashMap::new();
 
         for line in content.lines() {
-            if line.trim().is_empty() {
+            let Ok(json) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+
+            let is_sidechain = json.get("isSidechain").and_then(|v| v.as_bool()) == Some(true);
+            let agent_id = if is_sidechain {
+                json.get("agentId").and_then(|v| v.as_str()).unwrap_or("")
+            } else {
+                ""
+            };
+
+            if let Some(uuid) = json.get("uuid").and_then(|v| v.as_str()) {
+                owners.insert(uuid.to_string(), agent_id.to_string());
+            }
+
+            if agent_id.is_empty() || firsts.contains_key(agent_id) {
                 continue;
             }
 
-            if let Ok(json) = serde_json::from_str::<Value>(line) {
-                let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("");
-
-                match msg_type {
-                    "user" => {
-                        if let Some(msg) = json.get("message") {
-                            let content = msg
-                                .get("content")
-                                .and_then(|c| {
-                                    if c.is_string() {
-                                        c.as_str().map(|s| s.to_string())
-                                    } else if c.is_array() {
-                                        // Handle array of content blocks
-                                        let parts: Vec<String> = c
-                                            .as_array()
-                                            .unwrap_or(&vec![])
-                                            .iter()
-                                            .filter_map(|block| {
-                                                if block.get("type").and_then(|t| t.as_str())
-                                                    == Some("text")
-                                                {
-                                                    block
-                                                        .get("text")
-                                                        .and_then(|t| t.as_str())
-                                                        .map(|s| s.to_string())
-                                                } else {
-                                                    None
-                                                }
-                                            })
-                                            .collect();
-                                        Some(parts.join("\n"))
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .unwrap_or_default();
-
-                            let timestamp = json
-                                .get("timestamp")
-                                .and_then(|t| t.as_str())
-                                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                                .map(|dt| dt.with_timezone(&Utc));
-
-                            if !content.is_empty() {
-                                messages.push(ChatMessage {
-                                    role: "user".to_string(),
-                                    content,
-                                    timestamp,
-                                    tool_calls: Vec::new(),
-                                });
-                            }
-                        }
-                    }
-                    "assistant" => {
-                        if let Some(msg) = json.get("message") {
-                            let mut content = String::new();
-                            let mut tool_calls = Vec::new();
-
-                            if let Some(content_array) =
-                                msg.get("content").and_then(|c| c.as_array())
-                            {
-                                for block in content_array {
-                                    let block_type =
-                                        block.get("type").and_then(|t| t.as_str()).unwrap_or("");
-
-                                    match block_type {
-                                        "text" => {
-                                            if let Some(text) =
-                                                block.get("text").and_then(|t| t.as_str())
-                                            {
-                                                if !content.is_empty() {
-                                                    content.push('\n');
-                                                }
-                                                content.push_str(text);
-                                            }
-                                        }
-                                        "thinking" => {
-                                            if let Some(thinking) =
-                                                block.get("thinking").and_then(|t| t.as_str())
-                                            {
-                                                if !content.is_empty() {
-                                                    content.push('\n');
-                                                }
-                                                let truncated: String =
-                                                    thinking.chars().take(100).collect();
-                                                content.push_str(&format!(
-                                                    "[Thinking: {truncated}...]"
-                                                ));
-                                            }
-                                        }
-                                        "tool_use" => {
-                                            let tool_name = block
-                                                .get("name")
-                                                .and_then(|n| n.as_str())
-                                                .unwrap_or("unknown")
-                                                .to_string();
-
-                                            // Extract file_path from Edit/Write tool inputs
-                                            let file_path =
-                                                if tool_name == "Edit" || tool_name == "Write" {
-                                                    block
-                                                        .get("input")
-                                                        .and_then(|i| i.get("file_path"))
-                                                        .and_then(|p| p.as_str())
-                                                        .map(|s| s.to_string())
-                                                } else {
-                                                    None
-                                                };
-
-                                            tool_calls.push(ToolCall {
-                                                tool_name,
-                                                status: "completed".to_string(),
-                                                file_path,
-                                            });
-                                        }
-                                        _ => {}
-                                    }
-                                }
-                            }
+            let parent_uuid = json
+                .get("parentUuid")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string());
+            let started_at = json
+                .get("timestamp")
+                .and_then(|t| t.as_str())
+                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                .map(|dt| dt.with_timezone(&Utc));
+            let description = json
+                .get("message")
+                .and_then(|m| m.get("content"))
+                .and_then(content_text)
+                .and_then(|text| text.lines().next().map(|l| l.to_string()))
+                .unwrap_or_else(|| {
+                    format!("Agent {}", agent_id.chars().take(8).collect::<String>())
+                });
 
-                            let timestamp = json
-                                .get("timestamp")
-                                .and_then(|t| t.as_str())
-                                .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
-                                .map(|dt| dt.with_timezone(&Utc));
-
-                            if !content.is_empty() || !tool_calls.is_empty() {
-                                messages.push(ChatMessage {
-                                    role: "assistant".to_string(),
-                                    content: if content.is_empty() && !tool_calls.is_empty() {
-                                        format!("[{} tool calls]", tool_calls.len())
-                                    } else {
-                                        content
-                                    },
-                                    timestamp,
-                                    tool_calls,
-                                });
-                            }
-                        }
-                    }
-                    _ => {}
+            order.push(agent_id.to_string());
+            firsts.insert(
+                agent_id.to_string(),
+                AgentSpawn {
+                    parent_uuid,
+                    started_at,
+                    description,
+                },
+            );
+        }
+
+        let parents: HashMap<&str, Option<&str>> = order
+            .iter()
+            .map(|id| {
+                let parent = firsts[id]
+                    .parent_uuid
+                    .as_ref()
+                    .and_then(|uuid| owners.get(uuid))
+                    .map(|owner| owner.as_str())
+                    .filter(|owner| !owner.is_empty() && owner != id);
+                (id.as_str(), parent)
+            })
+            .collect();
+
+        fn build(
+            id: &str,
+            session_id: &str,
+            order: &[String],
+            parents: &HashMap<&str, Option<&str>>,
+            firsts: &HashMap<String, AgentSpawn>,
+        ) -> Agent {
+            let spawn = &firsts[id];
+            let parent_id = parents[id];
+            Agent {
+                id: id.to_string(),
+                session_id: session_id.to_string(),
+                parent_id: parent_id.map(|p| p.to_string()),
+                agent_type: if parent_id.is_some() {
+                    "nested"
+                } else {
+                    "subagent"
                 }
+                .to_string(),
+                status: "idle".to_string(),
+                started_at: spawn.started_at,
+                description: spawn.description.clone(),
+                children: order
+                    .iter()
+                    .filter(|child| parents[child.as_str()] == Some(id))
+                    .map(|child| build(child, session_id, order, parents, firsts))
+                    .collect(),
+                todos: Vec::new(),
             }
         }
 
-        Ok(messages)
+        // Every node has a single parent, so cycles are unreachable from roots
+        order
+            .iter()
+            .filter(|id| parents[id.as_str()].is_none())
+            .map(|id| build(id, session_id, &order, &parents, &firsts))
+            .collect()
     }
 
-    async fn load_sessions(claude_dir: &PathBuf) -> Result<Vec<Session>> {
+    async fn load_sessions(
+        claude_dir: &Path,
+        thresholds: &StatusThresholds,
+        cache: &mut LoadCache,
+    ) -> Result<Vec<Session>> {
         let projects_dir = claude_dir.join("projects");
         let mut sessions = Vec::new();
 
@@ -375,6 +730,9 @@ impl ClaudeData {
                 let metadata = fs::metadata(&file_path).await?;
                 let modified = metadata.modified().ok().map(DateTime::<Utc>::from);
                 let file_size = metadata.len();
+                // True start and cwd from the first messages; mtime when there are none
+                let head = cache.head(&file_path, file_size).await;
+                let started_at = head.started_at.or(modified);
 
                 // Estimate message count from file size (avg ~500 bytes per line)
                 let message_count = (file_size / 500).max(1);
@@ -385,26 +743,28 @@ impl ClaudeData {
                     .join("session-state")
                     .join(format!("{}.state", &session_id));
                 let status = if state_file.exists() {
-                    // Read state from hook-written file
-                    std::fs::read_to_string(&state_file)
-                        .map(|s| s.trim().to_string())
-                        .unwrap_or_else(|_| "idle".to_string())
+                    // Read state from hook-written file; its mtime tells if the hook is still alive
+                    match std::fs::read_to_string(&state_file) {
+                        Ok(state) => {
+                            let state_age = std::fs::metadata(&state_file)
+                                .and_then(|m| m.modified())
+                                .ok()
+                                .and_then(|t| t.elapsed().ok())
+                                .map(|d| d.as_secs())
+                                .unwrap_or(0);
+                            thresholds.status_for_state(state.trim(), state_age)
+                        }
+                        Err(_) => "idle".to_string(),
+                    }
                 } else if let Some(mod_time) = &modified {
-                    // Fall back to time-based detection
-                    // working = < 10 sec (actively processing)
-                    // active = < 2 min (recent activity)
-                    // idle = 2-30 min (waiting)
-                    // inactive = > 30 min (old)
+                    // Fall back to time-based detection (working / active / idle / inactive)
                     let age = chrono::Utc::now().signed_duration_since(*mod_time);
-                    if age.num_seconds() < 10 {
-                        "working".to_string()
-                    } else if age.num_seconds() < 120 {
-                        "active".to_string()
-                    } else if age.num_minutes() < 30 {
-                        "idle".to_string()
-                    } else {
-                        "inactive".to_string()
-                    }
+                    let age = age.num_seconds().max(0) as u64;
+                    let awaits_input =
+                        thresholds.infer_waiting && cache.awaits_input(&file_path, file_size).await;
+                    thresholds
+                        .status_for_transcript(age, awaits_input)
+                        .to_string()
                 } else {
                     "inactive".to_string()
                 };
@@ -414,13 +774,15 @@ impl ClaudeData {
                     project: project_name.clone(),
                     project_name: project_name
                         .split('/')
-                        .last()
+                        .next_back()
                         .unwrap_or(&project_name)
                         .to_string(),
                     description: None, // Will be populated from history.jsonl
                     custom_name: None,
-                    started_at: modified,
+                    started_at,
                     last_activity: modified,
+                    cwd: head.cwd,
+                    project_meta: None,
                     message_count,
                     status,
                     todos: Vec::new(), // Will be populated after loading all sessions
@@ -430,12 +792,12 @@ impl ClaudeData {
         }
 
         // Sort by last activity (most recent first)
-        sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
 
         Ok(sessions)
     }
 
-    async fn load_agents(claude_dir: &PathBuf) -> Result<Vec<Agent>> {
+    async fn load_agents(claude_dir: &Path) -> Result<Vec<Agent>> {
         let todos_dir = claude_dir.join("todos");
         let mut agents = Vec::new();
 
@@ -537,3 +899,175 @@ impl ClaudeData {
         Ok(agents)
     }
 }
+
+/// Map a project's absolute path to the form used for `Session::project`.
+///
+/// Claude names project dirs by replacing every non-alphanumeric character of the
+/// path with `-`, which `load_sessions` then turns into `/`.
+pub fn project_from_path(path: &str) -> String {
+    path.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '/' })
+        .collect()
+}
+
+/// First sidechain entry of a sub-agent
+struct AgentSpawn {
+    parent_uuid: Option<String>,
+    started_at: Option<DateTime<Utc>>,
+    description: String,
+}
+
+/// Parse one transcript line into a chat message (`line_no` is 1-based);
+/// None for blank lines, other record types and empty messages
+fn parse_message_line(line: &str, line_no: usize) -> Option<ChatMessage> {
+    if line.trim().is_empty() {
+        return None;
+    }
+
+    if let Ok(json) = serde_json::from_str::<Value>(line) {
+        let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("");
+
+        match msg_type {
+            "user" => {
+                if let Some(msg) = json.get("message") {
+                    let content = msg
+                        .get("content")
+                        .and_then(content_text)
+                        .unwrap_or_default();
+
+                    let timestamp = json
+                        .get("timestamp")
+                        .and_then(|t| t.as_str())
+                        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+
+                    if !content.is_empty() {
+                        return Some(ChatMessage {
+                            role: "user".to_string(),
+                            content,
+                            timestamp,
+                            tool_calls: Vec::new(),
+                            line: Some(line_no),
+                            thinking: None,
+                        });
+                    }
+                }
+            }
+            "assistant" => {
+                if let Some(msg) = json.get("message") {
+                    let mut content = String::new();
+                    let mut tool_calls = Vec::new();
+
+                    if let Some(content_array) = msg.get("content").and_then(|c| c.as_array()) {
+                        for block in content_array {
+                            let block_type =
+                                block.get("type").and_then(|t| t.as_str()).unwrap_or("");
+
+                            match block_type {
+                                "text" => {
+                                    if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
+                                        if !content.is_empty() {
+                                            content.push('\n');
+                                        }
+                                        content.push_str(text);
+                                    }
+                                }
+                                "thinking" => {
+                                    if let Some(thinking) =
+                                        block.get("thinking").and_then(|t| t.as_str())
+                                    {
+                                        if !content.is_empty() {
+                                            content.push('\n');
+                                        }
+                                        let truncated: String =
+                                            thinking.chars().take(100).collect();
+                                        content.push_str(&format!("[Thinking: {truncated}...]"));
+                                    }
+                                }
+                                "tool_use" => {
+                                    let tool_name = block
+                                        .get("name")
+                                        .and_then(|n| n.as_str())
+                                        .unwrap_or("unknown")
+                                        .to_string();
+
+                                    // Extract file_path from Edit/Write tool inputs
+                                    let file_path = if tool_name == "Edit" || tool_name == "Write" {
+                                        block
+                                            .get("input")
+                                            .and_then(|i| i.get("file_path"))
+                                            .and_then(|p| p.as_str())
+                                            .map(|s| s.to_string())
+                                    } else {
+                                        None
+                                    };
+
+                                    tool_calls.push(ToolCall {
+                                        tool_name,
+                                        status: "completed".to_string(),
+                                        file_path,
+                                    });
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+
+                    let timestamp = json
+                        .get("timestamp")
+                        .and_then(|t| t.as_str())
+                        .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+                        .map(|dt| dt.with_timezone(&Utc));
+
+                    if !content.is_empty() || !tool_calls.is_empty() {
+                        return Some(ChatMessage {
+                            role: "assistant".to_string(),
+                            // Text blocks are joined raw; user content is
+                            // already normalized by `content_text`
+                            content: trim_line_cr(&content),
+                            timestamp,
+                            tool_calls,
+                            line: Some(line_no),
+                            thinking: thinking_stats(&json),
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Thinking time (`thinkingDurationMs` on the entry) and tokens
+/// (`message.usage.thinking_tokens`), when either is recorded
+fn thinking_stats(entry: &Value) -> Option<ThinkingStats> {
+    let stats = ThinkingStats {
+        duration_secs: entry
+            .get("thinkingDurationMs")
+            .and_then(|ms| ms.as_f64())
+            .map(|ms| ms / 1000.0),
+        tokens: entry
+            .pointer("/message/usage/thinking_tokens")
+            .and_then(|t| t.as_u64()),
+    };
+    (stats != ThinkingStats::default()).then_some(stats)
+}
+
+/// Extract the text of a message `content`, which is either a plain string or
+/// an array of content blocks
+fn content_text(content: &Value) -> Option<String> {
+    if let Some(s) = content.as_str() {
+        return Some(trim_line_cr(s));
+    }
+
+    let parts: Vec<String> = content
+        .as_array()?
+        .iter()
+        .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("text"))
+        .filter_map(|block| block.get("text").and_then(|t| t.as_str()))
+        .map(trim_line_cr)
+        .collect();
+    Some(parts.join("\n"))
+}
@@ -0,0 +1,235 @@
+//! Git status/diff stats for files touched in a session
+
+use super::{FileChange, FileStatus};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+/// What git reports for one file
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileStat {
+    pub status: FileStatus,
+    pub additions: u32,
+    pub deletions: u32,
+    /// Old path (relative to the repo root) of a rename
+    pub renamed_from: Option<String>,
+}
+
+impl FileStat {
+    fn new(status: FileStatus) -> Self {
+        Self {
+            status,
+            additions: 0,
+            deletions: 0,
+            renamed_from: None,
+        }
+    }
+}
+
+/// Per-file stats parsed from one `git diff --numstat -z` and one
+/// `git status --porcelain=v1 -z`, keyed by path relative to the repo root.
+/// NUL-separated output keeps paths with spaces or non-ASCII characters verbatim
+pub fn parse_git_stats(numstat: &str, status: &str) -> HashMap<String, FileStat> {
+    let mut stats = HashMap::new();
+
+    // Status first so numstat (unstaged edits with line counts) wins, as before
+    let mut entries = status.split('\0');
+    while let Some(entry) = entries.next() {
+        if entry.len() < 4 {
+            continue;
+        }
+        let (code, path) = entry.split_at(3);
+        // Renames and copies are followed by the old path as its own entry
+        let old = if code.contains(['R', 'C']) {
+            entries.next()
+        } else {
+            None
+        };
+        let file_status = match &code[..2] {
+            "??" => FileStatus::Untracked,
+            "A " | " A" => FileStatus::Added,
+            "D " | " D" => FileStatus::Deleted,
+            // Staged rename, possibly edited since ("RM")
+            c if c.starts_with('R') => FileStatus::Renamed,
+            _ => continue,
+        };
+        let mut stat = FileStat::new(file_status);
+        stat.renamed_from = old.map(|old| old.to_string());
+        stats.insert(path.to_string(), stat);
+    }
+
+    let mut records = numstat.split('\0');
+    while let Some(record) = records.next() {
+        let mut parts = record.splitn(3, '\t');
+        let (Some(added), Some(deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        // A rename leaves the path empty and follows with old and new paths
+        let path = if path.is_empty() {
+            match (records.next(), records.next()) {
+                (Some(_old), Some(new)) => new,
+                _ => continue,
+            }
+        } else {
+            path
+        };
+        // Renames keep their status and old path; everything else is a modification
+        let stat = stats
+            .entry(path.to_string())
+            .and_modify(|s| {
+                if s.status != FileStatus::Renamed {
+                    s.status = FileStatus::Modified;
+                }
+            })
+            .or_insert_with(|| FileStat::new(FileStatus::Modified));
+        // Binary files report "-" for both counts
+        stat.additions = added.parse().unwrap_or(0);
+        stat.deletions = deleted.parse().unwrap_or(0);
+    }
+
+    stats
+}
+
+/// Build `FileChange`s for absolute `file_paths` from stats of the repo at `repo_root`
+pub fn file_changes_from_stats(
+    repo_root: &Path,
+    file_paths: &[String],
+    stats: &HashMap<String, FileStat>,
+) -> Vec<FileChange> {
+    file_paths
+        .iter()
+        .map(|path| {
+            let filename = Path::new(path)
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or(path)
+                .to_string();
+
+            let relative = relative_to(repo_root, path).unwrap_or_else(|| path.clone());
+            let stat = stats
+                .get(&relative)
+                .cloned()
+                .unwrap_or_else(|| FileStat::new(FileStatus::Modified));
+
+            FileChange {
+                path: path.clone(),
+                filename,
+                status: stat.status,
+                additions: stat.additions,
+                deletions: stat.deletions,
+                renamed_from: stat
+                    .renamed_from
+                    .map(|old| repo_root.join(old).to_string_lossy().to_string()),
+            }
+        })
+        .collect()
+}
+
+/// `path` relative to `root`, also trying the canonical path (symlinked temp/home dirs)
+fn relative_to(root: &Path, path: &str) -> Option<String> {
+    let path = Path::new(path);
+    let relative = match path.strip_prefix(root) {
+        Ok(rel) => rel.to_path_buf(),
+        Err(_) => std::fs::canonicalize(path)
+            .ok()?
+            .strip_prefix(root)
+            .ok()?
+            .to_path_buf(),
+    };
+    relative.to_str().map(|s| s.to_string())
+}
+
+/// Directory to run git in for `path`, so it resolves against its own enclosing
+/// repo rather than the cwd's (nested repos, submodules)
+pub fn repo_dir(path: &str) -> Option<&Path> {
+    Path::new(path).parent().filter(|d| d.is_dir())
+}
+
+/// Get git stats for all files, running one numstat and one status call in
+/// each repo the files live in (nested repos and submodules get their own)
+pub async fn file_changes(project_dir: &Path, file_paths: &[String]) -> Vec<FileChange> {
+    if file_paths.is_empty() {
+        return Vec::new();
+    }
+
+    // Project dirs decoded from Claude's folder names can be lossy; fall back to
+    // the directory of the first touched file
+    let fallback_dir = if project_dir.is_dir() {
+        project_dir.to_path_buf()
+    } else {
+        Path::new(&file_paths[0])
+            .parent()
+            .map(|p| p.to_path_buf())
+            .unwrap_or_default()
+    };
+
+    // Group files by enclosing repo, resolving each directory only once
+    let mut toplevels: HashMap<PathBuf, Option<PathBuf>> = HashMap::new();
+    let mut groups: Vec<(Option<PathBuf>, Vec<String>)> = Vec::new();
+    for path in file_paths {
+        let dir = repo_dir(path)
+            .map(|d| d.to_path_buf())
+            .unwrap_or_else(|| fallback_dir.clone());
+        let root = match toplevels.get(&dir) {
+            Some(root) => root.clone(),
+            None => {
+                let root = git_output(&dir, &["rev-parse", "--show-toplevel"])
+                    .await
+                    .map(|out| PathBuf::from(out.trim()));
+                toplevels.insert(dir, root.clone());
+                root
+            }
+        };
+        match groups.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, files)) => files.push(path.clone()),
+            None => groups.push((root, vec![path.clone()])),
+        }
+    }
+
+    let mut by_path: HashMap<String, FileChange> = HashMap::new();
+    for (root, files) in groups {
+        let changes = match root {
+            Some(root) => {
+                let stats = repo_stats(&root).await.unwrap_or_default();
+                file_changes_from_stats(&root, &files, &stats)
+            }
+            None => file_changes_from_stats(&fallback_dir, &files, &HashMap::new()),
+        };
+        by_path.extend(changes.into_iter().map(|c| (c.path.clone(), c)));
+    }
+
+    // Keep the caller's order
+    file_paths
+        .iter()
+        .filter_map(|path| by_path.remove(path))
+        .collect()
+}
+
+/// Stats for every uncommitted file in the repo containing `dir`, from one
+/// numstat and one status call; `None` outside a repo
+pub async fn repo_stats(dir: &Path) -> Option<HashMap<String, FileStat>> {
+    let numstat = git_output(dir, &["diff", "--numstat", "-z"]).await?;
+    let status = git_output(
+        dir,
+        &["status", "--porcelain=v1", "-z", "--untracked-files=all"],
+    )
+    .await
+    .unwrap_or_default();
+    Some(parse_git_stats(&numstat, &status))
+}
+
+/// Run git in `dir`, returning stdout on success
+async fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .await
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
@@ -1,5 +1,10 @@
+pub mod churn;
 pub mod claude;
+pub mod git;
+pub mod sidecar;
+pub mod stats;
 
+use crate::config::ProjectMeta;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -13,6 +18,12 @@ pub struct Session {
     pub custom_name: Option<String>, // User-defined override name
     pub started_at: Option<DateTime<Utc>>,
     pub last_activity: Option<DateTime<Utc>>,
+    /// Directory Claude ran in, from the transcript (exact, unlike `project`)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Settings from the project's `.lazychat.toml`
+    #[serde(skip)]
+    pub project_meta: Option<ProjectMeta>,
     pub message_count: u64,
     pub status: String,
     pub todos: Vec<TodoItem>, // Session-specific todos
@@ -73,6 +84,31 @@ pub struct ChatMessage {
     pub content: String, // The message text
     pub timestamp: Option<DateTime<Utc>>,
     pub tool_calls: Vec<ToolCall>,
+    /// 1-based line of the transcript this message was parsed from
+    pub line: Option<usize>,
+    /// Time and tokens spent thinking, when the transcript records them
+    pub thinking: Option<ThinkingStats>,
+}
+
+/// Extended thinking recorded for an assistant turn
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ThinkingStats {
+    pub duration_secs: Option<f64>,
+    pub tokens: Option<u64>,
+}
+
+impl ThinkingStats {
+    /// `(thought 4.2s, 320 tok)`, leaving out whichever part is unknown
+    pub fn annotation(&self) -> Option<String> {
+        let parts: Vec<String> = [
+            self.duration_secs.map(|secs| format!("{secs:.1}s")),
+            self.tokens.map(|tokens| format!("{tokens} tok")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        (!parts.is_empty()).then(|| format!("(thought {})", parts.join(", ")))
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -89,6 +125,8 @@ pub struct FileChange {
     pub status: FileStatus,
     pub additions: u32,
     pub deletions: u32,
+    /// Previous absolute path when git reports a rename
+    pub renamed_from: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -100,45 +138,195 @@ pub enum FileStatus {
     Untracked,
 }
 
+/// Composite `(project, id)` key; the same session id may exist under several projects
+pub fn session_key(project: &str, id: &str) -> String {
+    format!("{project}:{id}")
+}
+
+impl Session {
+    /// Key for per-session lookups (history, sidecar, saved state)
+    pub fn key(&self) -> String {
+        session_key(&self.project, &self.id)
+    }
+
+    /// Time between the first message and the last activity
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        Some(self.last_activity? - self.started_at?)
+    }
+
+    /// Whether the last activity falls on the same local calendar day as `now`
+    pub fn touched_today(&self, now: DateTime<chrono::Local>) -> bool {
+        self.last_activity
+            .is_some_and(|t| t.with_timezone(&chrono::Local).date_naive() == now.date_naive())
+    }
+
+    /// Whether the transcript file was deleted after the session was listed
+    pub fn transcript_missing(&self) -> bool {
+        self.file_path.as_ref().is_some_and(|p| !p.exists())
+    }
+
+    /// Working directory of the session: the transcript's cwd, else the
+    /// project with dash-encoded names decoded
+    pub fn project_dir(&self) -> String {
+        if let Some(cwd) = &self.cwd {
+            cwd.clone()
+        } else if self.project.starts_with('/') {
+            self.project.clone()
+        } else {
+            format!("/{}", self.project.replace('-', "/"))
+        }
+    }
+
+    /// Completed vs total todos, or None when the session has no todos
+    pub fn todo_progress(&self) -> Option<(usize, usize)> {
+        if self.todos.is_empty() {
+            return None;
+        }
+        let completed = self
+            .todos
+            .iter()
+            .filter(|t| t.status == "completed")
+            .count();
+        Some((completed, self.todos.len()))
+    }
+}
+
+impl FileChange {
+    /// Whether git reports anything for this file (touched files can be unchanged)
+    pub fn has_changes(&self) -> bool {
+        self.additions + self.deletions > 0 || self.status != FileStatus::Modified
+    }
+
+    /// `old → new` for renames, otherwise the path
+    pub fn display_path(&self) -> String {
+        match &self.renamed_from {
+            Some(old) => format!("{old} → {}", self.path),
+            None => self.path.clone(),
+        }
+    }
+}
+
+impl ToolCall {
+    /// Tool name plus the file it touched, if known
+    pub fn summary(&self) -> String {
+        match &self.file_path {
+            Some(path) => format!("{} {path}", self.tool_name),
+            None => self.tool_name.clone(),
+        }
+    }
+}
+
 impl ChatMessage {
+    /// Whether the message has prose; tool-only turns have empty content
+    pub fn has_text(&self) -> bool {
+        !self.content.is_empty()
+    }
+
     pub fn display_content(&self, max_width: usize) -> Vec<String> {
         let mut lines = Vec::new();
 
-        // Format content with word wrapping (char-safe)
+        // Format content with word wrapping (display-width aware)
         for line in self.content.lines() {
-            if line.chars().count() <= max_width {
-                lines.push(line.to_string());
-            } else {
-                // Word wrap
-                let words: Vec<&str> = line.split_whitespace().collect();
-                let mut current_line = String::new();
-                let mut current_len = 0usize;
-                for word in words {
-                    let word_len = word.chars().count();
-                    if current_line.is_empty() {
-                        current_line = word.to_string();
-                        current_len = word_len;
-                    } else if current_len + 1 + word_len <= max_width {
-                        current_line.push(' ');
-                        current_line.push_str(word);
-                        current_len += 1 + word_len;
-                    } else {
-                        lines.push(current_line);
-                        current_line = word.to_string();
-                        current_len = word_len;
-                    }
-                }
-                if !current_line.is_empty() {
-                    lines.push(current_line);
-                }
-            }
+            lines.extend(crate::text::wrap_words(line, max_width));
         }
 
-        // Add tool calls if present
+        // One line per tool call, so tool-only turns still show what they did
         for tool in &self.tool_calls {
-            lines.push(format!("  └─ {} [{}]", tool.tool_name, tool.status));
+            lines.push(format!("  └─ {} [{}]", tool.summary(), tool.status));
         }
 
         lines
     }
 }
+
+/// Files touched by the chat's tool calls: sorted by path, or most recently
+/// edited first
+pub fn edited_files(messages: &[ChatMessage], by_recency: bool) -> Vec<String> {
+    let paths = messages
+        .iter()
+        .flat_map(|m| &m.tool_calls)
+        .filter_map(|tc| tc.file_path.clone());
+    if !by_recency {
+        let mut files: Vec<String> = paths.collect();
+        files.sort();
+        files.dedup();
+        return files;
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for path in paths.rev() {
+        if !files.contains(&path) {
+            files.push(path);
+        }
+    }
+    files
+}
+
+/// Render a transcript as Markdown for reading outside the TUI
+pub fn transcript_markdown(messages: &[ChatMessage]) -> String {
+    render_transcript(messages, false)
+}
+
+/// Render messages as Markdown, or as plain text for pasting into tickets
+pub fn render_transcript<'a>(
+    messages: impl IntoIterator<Item = &'a ChatMessage>,
+    plain: bool,
+) -> String {
+    let mut out = String::new();
+    for msg in messages {
+        let role = if msg.role == "user" {
+            "User"
+        } else {
+            "Assistant"
+        };
+        let header = match msg.timestamp {
+            Some(ts) => format!("{role} ({})", ts.format("%Y-%m-%d %H:%M")),
+            None => role.to_string(),
+        };
+        if plain {
+            out.push_str(&format!("{header}:\n"));
+        } else {
+            out.push_str(&format!("## {header}\n\n"));
+        }
+        if !msg.content.is_empty() {
+            out.push_str(&msg.content);
+            out.push_str(if plain { "\n" } else { "\n\n" });
+        }
+        for tool in &msg.tool_calls {
+            let name = if plain {
+                format!("[{}]", tool.tool_name)
+            } else {
+                format!("`{}`", tool.tool_name)
+            };
+            match &tool.file_path {
+                Some(path) => out.push_str(&format!("- {name} {path}\n")),
+                None => out.push_str(&format!("- {name}\n")),
+            }
+        }
+        if plain || !msg.tool_calls.is_empty() {
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Per-role message totals for a loaded transcript
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MessageCounts {
+    pub user: usize,
+    pub assistant: usize,
+    pub tool_calls: usize,
+}
+
+pub fn message_counts(messages: &[ChatMessage]) -> MessageCounts {
+    let mut counts = MessageCounts::default();
+    for msg in messages {
+        if msg.role == "user" {
+            counts.user += 1;
+        } else {
+            counts.assistant += 1;
+        }
+        counts.tool_calls += msg.tool_calls.len();
+    }
+    counts
+}
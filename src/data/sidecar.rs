@@ -0,0 +1,190 @@
+//! Sidecar store - lazychat's own per-session metadata (custom names, notes, tags, ...)
+
+use super::Session;
+use anyhow::{Context, Result};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Metadata lazychat keeps for a session, keyed by `Session::key`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Freeform multi-line note
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Free-form labels used for filtering
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Single-key mark for quick jumps (unique across sessions)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mark: Option<char>,
+}
+
+impl SessionMeta {
+    fn is_empty(&self) -> bool {
+        self.name.is_none() && self.note.is_none() && self.tags.is_empty() && self.mark.is_none()
+    }
+}
+
+/// Split comma-separated tag input into trimmed, de-duplicated tags
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t == tag) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SidecarData {
+    #[serde(default)]
+    sessions: HashMap<String, SessionMeta>,
+}
+
+/// Tag filter predicate: every whitespace/comma separated term of `query` must
+/// fuzzy-match at least one tag. An empty query matches everything.
+pub fn tags_match(tags: &[String], query: &str) -> bool {
+    terms_score(tags, query).is_some()
+}
+
+/// Fuzzy score of `fields` against `query` (sum of each term's best field
+/// score), None when some term matches no field
+pub fn terms_score<S: AsRef<str>>(fields: &[S], query: &str) -> Option<i64> {
+    let matcher = SkimMatcherV2::default();
+    query
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|term| !term.is_empty())
+        .map(|term| {
+            fields
+                .iter()
+                .filter_map(|field| matcher.fuzzy_match(field.as_ref(), term))
+                .max()
+        })
+        .sum()
+}
+
+/// Persistent per-session metadata that survives refreshes and restarts
+pub struct SidecarStore {
+    data: SidecarData,
+    path: PathBuf,
+}
+
+impl SidecarStore {
+    /// Load sidecar from ~/.local/share/lazychat/sessions.json
+    pub fn load() -> Result<Self> {
+        Self::load_from(Self::sidecar_path())
+    }
+
+    /// Load sidecar from an explicit path (missing file = empty store); a
+    /// corrupted file is an error and is left untouched
+    pub fn load_from(path: PathBuf) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let data = if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            serde_json::from_str(&content)
+                .with_context(|| format!("Corrupted session sidecar {}", path.display()))?
+        } else {
+            SidecarData::default()
+        };
+
+        Ok(Self { data, path })
+    }
+
+    /// Get the sidecar file path
+    fn sidecar_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("/tmp"))
+            .join("lazychat")
+            .join("sessions.json")
+    }
+
+    /// Save sidecar to disk
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(&self.data)?;
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    /// Metadata for a session key, if any
+    pub fn get(&self, key: &str) -> Option<&SessionMeta> {
+        self.data.sessions.get(key)
+    }
+
+    /// Set or clear a session's custom name and persist
+    pub fn set_name(&mut self, key: &str, name: Option<String>) -> Result<()> {
+        self.update(key, |meta| meta.name = name)
+    }
+
+    /// A session's note, if any
+    pub fn note(&self, key: &str) -> Option<&str> {
+        self.get(key).and_then(|m| m.note.as_deref())
+    }
+
+    /// Set or clear a session's note (blank = clear) and persist
+    pub fn set_note(&mut self, key: &str, note: Option<String>) -> Result<()> {
+        let note = note.filter(|n| !n.trim().is_empty());
+        self.update(key, |meta| meta.note = note)
+    }
+
+    /// A session's tags (empty when untagged)
+    pub fn tags(&self, key: &str) -> &[String] {
+        self.get(key).map(|m| m.tags.as_slice()).unwrap_or_default()
+    }
+
+    /// Replace a session's tags and persist
+    pub fn set_tags(&mut self, key: &str, tags: Vec<String>) -> Result<()> {
+        self.update(key, |meta| meta.tags = tags)
+    }
+
+    /// A session's mark, if any
+    pub fn mark(&self, key: &str) -> Option<char> {
+        self.get(key).and_then(|m| m.mark)
+    }
+
+    /// Key of the session holding `mark`
+    pub fn find_mark(&self, mark: char) -> Option<&str> {
+        self.data
+            .sessions
+            .iter()
+            .find(|(_, meta)| meta.mark == Some(mark))
+            .map(|(key, _)| key.as_str())
+    }
+
+    /// Assign `mark` to a session, taking it from any other session, and persist
+    pub fn set_mark(&mut self, key: &str, mark: char) -> Result<()> {
+        self.data.sessions.retain(|k, meta| {
+            if k != key && meta.mark == Some(mark) {
+                meta.mark = None;
+            }
+            !meta.is_empty()
+        });
+        self.update(key, |meta| meta.mark = Some(mark))
+    }
+
+    /// Modify a session's metadata, dropping entries that end up empty, and persist
+    fn update(&mut self, key: &str, f: impl FnOnce(&mut SessionMeta)) -> Result<()> {
+        let meta = self.data.sessions.entry(key.to_string()).or_default();
+        f(meta);
+        if meta.is_empty() {
+            self.data.sessions.remove(key);
+        }
+        self.save()
+    }
+
+    /// Copy stored metadata onto freshly loaded sessions
+    pub fn apply(&self, sessions: &mut [Session]) {
+        for session in sessions {
+            session.custom_name = self.get(&session.key()).and_then(|m| m.name.clone());
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Per-day activity totals aggregated from session transcripts, and export
+
+use super::DailyStats;
+use anyhow::Result;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Aggregate transcripts (JSONL contents) into per-day totals, oldest first.
+/// A session counts once for every UTC day it has messages on.
+pub fn aggregate_daily_stats<'a>(
+    transcripts: impl IntoIterator<Item = &'a str>,
+) -> Vec<DailyStats> {
+    let mut days: BTreeMap<String, DailyStats> = BTreeMap::new();
+
+    for transcript in transcripts {
+        let mut session_days = BTreeSet::new();
+
+        for line in transcript.lines() {
+            let Ok(json) = serde_json::from_str::<Value>(line) else {
+                continue;
+            };
+            let msg_type = json.get("type").and_then(|v| v.as_str()).unwrap_or("");
+            if msg_type != "user" && msg_type != "assistant" {
+                continue;
+            }
+            // RFC 3339 timestamps start with the UTC date
+            let Some(date) = json
+                .get("timestamp")
+                .and_then(|t| t.as_str())
+                .and_then(|t| t.get(..10))
+            else {
+                continue;
+            };
+
+            let day = days.entry(date.to_string()).or_insert_with(|| DailyStats {
+                date: date.to_string(),
+                message_count: 0,
+                session_count: 0,
+                tool_call_count: 0,
+            });
+            day.message_count += 1;
+            day.tool_call_count += json
+                .pointer("/message/content")
+                .and_then(|c| c.as_array())
+                .map(|blocks| {
+                    blocks
+                        .iter()
+                        .filter(|b| b.get("type").and_then(|t| t.as_str()) == Some("tool_use"))
+                        .count() as u64
+                })
+                .unwrap_or(0);
+            session_days.insert(date.to_string());
+        }
+
+        for date in session_days {
+            if let Some(day) = days.get_mut(&date) {
+                day.session_count += 1;
+            }
+        }
+    }
+
+    days.into_values().collect()
+}
+
+/// Aggregate every transcript under `claude_dir/projects`
+pub async fn load_daily_stats(claude_dir: &Path) -> Result<Vec<DailyStats>> {
+    let projects_dir = claude_dir.join("projects");
+    let mut transcripts = Vec::new();
+
+    if projects_dir.exists() {
+        let mut projects = tokio::fs::read_dir(&projects_dir).await?;
+        while let Some(project) = projects.next_entry().await? {
+            if !project.path().is_dir() {
+                continue;
+            }
+            let mut files = tokio::fs::read_dir(project.path()).await?;
+            while let Some(file) = files.next_entry().await? {
+                let path = file.path();
+                if path.extension().and_then(|e| e.to_str()) == Some("jsonl") {
+                    transcripts.push(tokio::fs::read_to_string(&path).await.unwrap_or_default());
+                }
+            }
+        }
+    }
+
+    Ok(aggregate_daily_stats(
+        transcripts.iter().map(String::as_str),
+    ))
+}
+
+/// `date,messages,sessions,tool_calls` with a header row
+pub fn stats_to_csv(stats: &[DailyStats]) -> String {
+    let mut out = String::from("date,messages,sessions,tool_calls\n");
+    for day in stats {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            day.date, day.message_count, day.session_count, day.tool_call_count
+        ));
+    }
+    out
+}
+
+/// JSON array of `{date, messages, sessions, tool_calls}` objects
+pub fn stats_to_json(stats: &[DailyStats]) -> Result<String> {
+    let rows: Vec<Value> = stats
+        .iter()
+        .map(|day| {
+            serde_json::json!({
+                "date": day.date,
+                "messages": day.message_count,
+                "sessions": day.session_count,
+                "tool_calls": day.tool_call_count,
+            })
+        })
+        .collect();
+    Ok(serde_json::to_string_pretty(&rows)?)
+}
+
+/// Write stats to `path`: JSON for `.json`, CSV otherwise
+pub fn export_daily_stats(stats: &[DailyStats], path: &Path) -> Result<()> {
+    let content = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+        stats_to_json(stats)?
+    } else {
+        stats_to_csv(stats)
+    };
+    std::fs::write(path, content)?;
+    Ok(())
+}
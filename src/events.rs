@@ -1,9 +1,58 @@
-use crate::app::{App, Focus};
+use crate::app::{App, Focus, MarkAction};
 use crate::ui;
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
 use ratatui::prelude::*;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+/// Lines moved per j/k press in the detail view
+const SCROLL_STEP: i32 = 3;
+/// Lines moved per page (Ctrl+u/Ctrl+d)
+const SCROLL_PAGE: i32 = 30;
+/// Redraws wait until resize events have been quiet this long
+const RESIZE_DEBOUNCE: Duration = Duration::from_millis(50);
+/// Messages load once the selection has stayed put this long
+pub const SELECT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Decides when a changed session selection should load its messages, so
+/// holding j/k through the list doesn't parse every transcript on the way
+#[derive(Debug, Default)]
+pub struct SelectionDebounce {
+    loaded: Option<String>,
+    pending: Option<(Option<String>, Instant)>,
+}
+
+impl SelectionDebounce {
+    /// Whether to load messages for `current` (a session key) now
+    pub fn should_load(&mut self, current: Option<String>, now: Instant) -> bool {
+        if current == self.loaded {
+            self.pending = None;
+            return false;
+        }
+        match &self.pending {
+            Some((key, since)) if *key == current => {
+                if now.saturating_duration_since(*since) < SELECT_DEBOUNCE {
+                    return false;
+                }
+                self.pending = None;
+                self.loaded = current;
+                true
+            }
+            _ => {
+                self.pending = Some((current, now));
+                false
+            }
+        }
+    }
+}
+
+/// Size to spawn the embedded terminal at: the detail pane of the current screen
+fn terminal_pane(app: &App) -> (u16, u16) {
+    let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+    ui::terminal_pane_size(app, Rect::new(0, 0, cols, rows))
+}
 
 /// Convert a key event to bytes for the terminal
 fn key_to_bytes(key: KeyEvent) -> Vec<u8> {
@@ -34,32 +83,70 @@ fn key_to_bytes(key: KeyEvent) -> Vec<u8> {
     }
 }
 
+/// Keys whose action starts or kills a process (claude, editor, presets)
+fn spawns_or_kills(app: &App, key: &KeyEvent) -> bool {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return false;
+    }
+    match key.code {
+        KeyCode::Char('o' | 'n' | 'E' | 'D' | ',') => true,
+        KeyCode::Char('d' | 'A') => app.focus == Focus::Sessions,
+        KeyCode::Char('O') => matches!(app.focus, Focus::Sessions | Focus::Files),
+        KeyCode::Char('e') => matches!(app.focus, Focus::Files | Focus::Presets) || app.diff_mode,
+        KeyCode::Char('V') => app.focus == Focus::Files || app.diff_mode,
+        KeyCode::Char('p') => matches!(app.focus, Focus::Sessions | Focus::Detail),
+        KeyCode::Enter => app.focus == Focus::Presets,
+        _ => false,
+    }
+}
+
 pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> Result<()> {
-    let mut last_selected_session: Option<usize> = None;
+    let mut selection = SelectionDebounce::default();
     let mut last_refresh = std::time::Instant::now();
+    let mut last_resize: Option<std::time::Instant> = None;
 
     loop {
-        terminal.draw(|f| ui::draw(f, app))?;
+        // Skip redraws (and re-wrapping) while a window drag is still resizing
+        if last_resize.is_none_or(|at| at.elapsed() >= RESIZE_DEBOUNCE) {
+            last_resize = None;
+            terminal.draw(|f| ui::draw(f, app))?;
+            app.tick();
+        }
 
-        // Auto-refresh session data every second
-        if last_refresh.elapsed() >= Duration::from_secs(1) {
+        // Auto-refresh session data every second (paused while typing so the
+        // list doesn't reorder under an open input)
+        if last_refresh.elapsed() >= Duration::from_secs(1) && !app.input_active() {
             let _ = app.load_data().await;
             last_refresh = std::time::Instant::now();
+            app.record_refresh(last_refresh);
+            app.refresh_process_counts();
+            if app.show_churn {
+                app.refresh_churn(last_refresh).await;
+            }
+            app.follow_newest(last_refresh);
+            app.reload_theme_if_changed();
+            let _ = app.tail_selected_session().await;
         }
 
-        // Check if session selection changed, load messages
-        let current_selection = app.session_list_state.selected();
-        if current_selection != last_selected_session {
-            last_selected_session = current_selection;
+        app.update_idle(std::time::Instant::now());
+        app.settle_session_filter(Instant::now());
+
+        // Load messages once a changed selection settles
+        let current_selection = app.selected_session().map(|s| s.key());
+        if selection.should_load(current_selection, Instant::now()) {
             let _ = app.load_session_messages().await;
+            app.run_select_hook();
         }
 
         // Poll for events with timeout
         if event::poll(Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
-                if handle_key(app, key).await? {
-                    return Ok(());
-                }
+            match event::read()? {
+                // The first key after idling only wakes the screen
+                Event::Key(_) if app.register_input(std::time::Instant::now()) => {}
+                Event::Key(key) if handle_key(app, key).await? => return Ok(()),
+                Event::Mouse(mouse) => handle_mouse(app, mouse),
+                Event::Resize(..) => last_resize = Some(std::time::Instant::now()),
+                _ => {}
             }
         }
 
@@ -69,7 +156,23 @@ pub async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> R
     }
 }
 
-async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
+/// Mouse support: click or wheel over the diff minimap
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    if app.terminal_mode || app.compare.is_some() {
+        return;
+    }
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) | MouseEventKind::Drag(MouseButton::Left) => {
+            app.click_minimap(mouse.column, mouse.row);
+        }
+        MouseEventKind::ScrollDown if app.diff_mode => app.scroll_lines(SCROLL_STEP),
+        MouseEventKind::ScrollUp if app.diff_mode => app.scroll_lines(-SCROLL_STEP),
+        _ => {}
+    }
+}
+
+/// Handle a single key press; returns `true` when the app should quit
+pub async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
     // Terminal mode - forward keys to embedded terminal
     if app.terminal_mode {
         let exit_keys = matches!(
@@ -85,7 +188,11 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
 
         let data = key_to_bytes(key);
         if !data.is_empty() {
-            let _ = app.send_to_terminal(&data);
+            // A failed write means the PTY is gone; stop forwarding keys into it
+            if let Err(e) = app.send_to_terminal(&data) {
+                app.close_embedded_terminal();
+                app.set_error(&format!("Terminal closed: {e}"));
+            }
         }
         return Ok(false);
     }
@@ -96,6 +203,88 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => {
                 app.show_help = false;
             }
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.help_scroll = app.help_scroll.saturating_add(1)
+            }
+            KeyCode::Char('k') | KeyCode::Up => app.help_scroll = app.help_scroll.saturating_sub(1),
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.help_scroll = app.help_scroll.saturating_add(SCROLL_PAGE as u16)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.help_scroll = app.help_scroll.saturating_sub(SCROLL_PAGE as u16)
+            }
+            KeyCode::Char('g') => app.help_scroll = 0,
+            KeyCode::Char('G') => app.help_scroll = u16::MAX,
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Panel cheatsheet: type to search, `?` on an empty query opens full help
+    if app.show_cheatsheet {
+        match key.code {
+            KeyCode::Esc if !app.cheatsheet_query.is_empty() => app.cheatsheet_query.clear(),
+            KeyCode::Esc | KeyCode::Enter => app.close_cheatsheet(),
+            KeyCode::Char('?') if app.cheatsheet_query.is_empty() => {
+                app.close_cheatsheet();
+                app.toggle_help();
+            }
+            KeyCode::Backspace => app.cheatsheet_backspace(),
+            KeyCode::Char(c) => app.cheatsheet_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Raw JSON popup: scroll or close
+    if app.raw_json.is_some() {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('J') => app.close_raw_json(),
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.raw_json_scroll = app.raw_json_scroll.saturating_add(1)
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.raw_json_scroll = app.raw_json_scroll.saturating_sub(1)
+            }
+            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.raw_json_scroll = app.raw_json_scroll.saturating_add(SCROLL_PAGE as u16)
+            }
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.raw_json_scroll = app.raw_json_scroll.saturating_sub(SCROLL_PAGE as u16)
+            }
+            KeyCode::Char('g') => app.raw_json_scroll = 0,
+            KeyCode::Char('G') => app.raw_json_scroll = u16::MAX,
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Compare view: scroll the active pane, Tab/h/l switch panes
+    if let Some(active) = app.compare.as_ref().map(|c| c.active) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('x') => app.close_compare(),
+            KeyCode::Tab | KeyCode::BackTab | KeyCode::Char('h') | KeyCode::Char('l') => {
+                app.toggle_compare_pane()
+            }
+            KeyCode::Char('j') | KeyCode::Down => {
+                app.with_compare_pane(active, |app| app.scroll_lines(SCROLL_STEP));
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                app.with_compare_pane(active, |app| app.scroll_lines(-SCROLL_STEP));
+            }
+            KeyCode::Char('d') if ctrl => {
+                app.with_compare_pane(active, |app| app.scroll_lines(SCROLL_PAGE));
+            }
+            KeyCode::Char('u') if ctrl => {
+                app.with_compare_pane(active, |app| app.scroll_lines(-SCROLL_PAGE));
+            }
+            KeyCode::Char('g') => {
+                app.with_compare_pane(active, App::scroll_to_top);
+            }
+            KeyCode::Char('G') => {
+                app.with_compare_pane(active, App::scroll_to_bottom);
+            }
             _ => {}
         }
         return Ok(false);
@@ -113,6 +302,137 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
         return Ok(false);
     }
 
+    // Kill confirmation: only `y` goes ahead
+    if let Some(pid) = app.pending_kill.take() {
+        if key.code == KeyCode::Char('y') {
+            app.kill_process(pid)?;
+        } else {
+            app.set_status("Kill cancelled");
+        }
+        return Ok(false);
+    }
+
+    // Open-all confirmation: only `y` goes ahead
+    if std::mem::take(&mut app.pending_open_all) {
+        if key.code == KeyCode::Char('y') {
+            let (cols, rows) = terminal_pane(app);
+            if let Err(e) = app.open_all_files(cols, rows) {
+                app.set_error(&format!("Failed: {e}"));
+            }
+        } else {
+            app.set_status("Open cancelled");
+        }
+        return Ok(false);
+    }
+
+    // Mark key after `m` / `'`
+    if let Some(action) = app.pending_mark.take() {
+        if let KeyCode::Char(c) = key.code {
+            match action {
+                MarkAction::Set => app.set_mark(c),
+                MarkAction::Jump => app.jump_to_mark(c),
+            }
+        }
+        return Ok(false);
+    }
+
+    // Open file by path prompt
+    if app.opening_path {
+        match key.code {
+            KeyCode::Esc => app.cancel_open_path(),
+            KeyCode::Enter => {
+                let (cols, rows) = terminal_pane(app);
+                app.confirm_open_path(cols, rows);
+            }
+            KeyCode::Backspace => app.open_path_backspace(),
+            KeyCode::Char(c) => app.open_path_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Tag editor (comma-separated)
+    if app.editing_tags {
+        match key.code {
+            KeyCode::Esc => app.cancel_tags(),
+            KeyCode::Enter => app.confirm_tags(),
+            KeyCode::Backspace => app.tags_backspace(),
+            KeyCode::Char(c) => app.tags_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Session filter input mode
+    if app.session_filter_active {
+        match key.code {
+            KeyCode::Esc => app.cancel_session_filter(),
+            KeyCode::Backspace => app.session_filter_backspace(),
+            // Close input, keep the filter and open the top match
+            KeyCode::Enter => app.confirm_session_filter(),
+            KeyCode::Char(c) => app.session_filter_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Note editor (multi-line: Enter = newline, Ctrl+s = save)
+    if app.editing_note {
+        match key.code {
+            KeyCode::Esc => app.cancel_note(),
+            KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.confirm_note()
+            }
+            KeyCode::Enter => app.note_input('\n'),
+            KeyCode::Backspace => app.note_backspace(),
+            KeyCode::Char(c) => app.note_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Working directory prompt for a new session
+    if app.new_session_prompt {
+        match key.code {
+            KeyCode::Esc => app.cancel_new_session_prompt(),
+            KeyCode::Enter => {
+                let (cols, rows) = terminal_pane(app);
+                app.confirm_new_session_prompt(cols, rows)
+            }
+            KeyCode::Backspace => app.new_session_dir_backspace(),
+            KeyCode::Char(c) => app.new_session_dir_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Extra --add-dir paths for resuming a session
+    if app.add_dir_prompt {
+        match key.code {
+            KeyCode::Esc => app.cancel_add_dir_prompt(),
+            KeyCode::Enter => {
+                let (cols, rows) = terminal_pane(app);
+                app.confirm_add_dir_prompt(cols, rows);
+            }
+            KeyCode::Backspace => app.add_dir_backspace(),
+            KeyCode::Char(c) => app.add_dir_input_char(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    // Fuzzy go-to-file prompt
+    if app.file_jump_active {
+        match key.code {
+            KeyCode::Esc => app.cancel_file_jump(),
+            KeyCode::Enter => app.confirm_file_jump().await,
+            KeyCode::Backspace => app.file_jump_backspace(),
+            KeyCode::Char(c) => app.file_jump_input(c),
+            _ => {}
+        }
+        return Ok(false);
+    }
+
     // File filter input mode
     if app.file_filter_active {
         match key.code {
@@ -133,22 +453,21 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
 
     // Normal mode
     match key.code {
+        // Safe mode refuses spawn/kill keys but leaves browsing alone
+        _ if app.settings.safe_mode && spawns_or_kills(app, &key) => {
+            app.set_status("Disabled in safe mode");
+        }
+
         // Ctrl+Q = fully exit detail view back to sidebar (must be before regular 'q')
         KeyCode::Char('q') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            if app.focus == Focus::Detail || app.fullscreen {
-                app.fullscreen = false;
-                if app.diff_mode {
-                    app.diff_mode = false;
-                    app.focus = Focus::Files;
-                } else {
-                    app.focus = Focus::Sessions;
-                }
-            }
+            app.exit_detail_view();
         }
 
-        // Quit AND kill all processes (Shift+Q)
+        // Quit AND kill all processes (Shift+Q); safe mode only quits
         KeyCode::Char('Q') => {
-            let _ = app.kill_all_processes();
+            if !app.settings.safe_mode {
+                let _ = app.kill_all_processes();
+            }
             app.should_quit = true;
             return Ok(true);
         }
@@ -208,82 +527,60 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
         },
 
         // j/k = navigate within current panel (j=down, k=up)
-        KeyCode::Char('j') | KeyCode::Down => {
-            match app.focus {
-                Focus::Presets => {
-                    if app.selected_preset_idx + 1 < app.presets.len() {
-                        app.selected_preset_idx += 1;
-                    }
-                }
-                Focus::Sessions => app.list_next(),
-                Focus::Todos => app.todos_scroll_down(),
-                Focus::Files => {
-                    app.files_select_next();
-                    app.load_file_diff().await;
+        KeyCode::Char('j') | KeyCode::Down => match app.focus {
+            Focus::Presets => {
+                if app.selected_preset_idx + 1 < app.presets.len() {
+                    app.selected_preset_idx += 1;
                 }
-                Focus::Detail if app.diff_mode => app.scroll_up(), // diff: scroll_up = view moves down
-                Focus::Detail => app.scroll_down(), // chat: scroll_down = view moves down
             }
-        }
-        KeyCode::Char('k') | KeyCode::Up => {
-            match app.focus {
-                Focus::Presets => {
-                    if app.selected_preset_idx > 0 {
-                        app.selected_preset_idx -= 1;
-                    }
-                }
-                Focus::Sessions => app.list_prev(),
-                Focus::Todos => app.todos_scroll_up(),
-                Focus::Files => {
-                    app.files_select_prev();
-                    app.load_file_diff().await;
+            Focus::Sessions => app.list_next(),
+            Focus::Todos => app.todos_scroll_down(),
+            Focus::Files => {
+                app.files_select_next();
+                app.load_file_diff().await;
+            }
+            Focus::Detail => app.scroll_lines(SCROLL_STEP),
+        },
+        KeyCode::Char('k') | KeyCode::Up => match app.focus {
+            Focus::Presets => {
+                if app.selected_preset_idx > 0 {
+                    app.selected_preset_idx -= 1;
                 }
-                Focus::Detail if app.diff_mode => app.scroll_down(), // diff: scroll_down = view moves up
-                Focus::Detail => app.scroll_up(), // chat: scroll_up = view moves up
             }
+            Focus::Sessions => app.list_prev(),
+            Focus::Todos => app.todos_scroll_up(),
+            Focus::Files => {
+                app.files_select_prev();
+                app.load_file_diff().await;
+            }
+            Focus::Detail => app.scroll_lines(-SCROLL_STEP),
+        },
+
+        // Wrap long todos instead of truncating them
+        KeyCode::Char('w') if app.focus == Focus::Todos => app.toggle_todos_wrap(),
+        KeyCode::Char('c') if app.focus == Focus::Todos => app.toggle_hide_done_todos(),
+
+        // Toggle ignoring whitespace in diffs
+        KeyCode::Char('w') if app.focus == Focus::Files || app.diff_mode => {
+            app.toggle_ignore_whitespace().await
         }
 
-        // Page up/down (Ctrl+U = up, Ctrl+D = down)
-        KeyCode::PageUp | KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            if app.diff_mode || app.focus == Focus::Files {
-                // Diff view: scroll_down moves view up (towards top)
-                for _ in 0..10 {
-                    app.scroll_down();
-                }
-            } else {
-                // Chat view: scroll_up moves view up (towards earlier messages)
-                for _ in 0..10 {
-                    app.scroll_up();
-                }
-            }
+        // Step through every changed file, landing on its first hunk
+        KeyCode::Char(']') if app.focus == Focus::Files || app.diff_mode => {
+            app.next_changed_file().await
         }
-        KeyCode::PageDown => {
-            if app.diff_mode || app.focus == Focus::Files {
-                // Diff view: scroll_up moves view down (towards bottom)
-                for _ in 0..10 {
-                    app.scroll_up();
-                }
-            } else {
-                // Chat view: scroll_down moves view down (towards newer messages)
-                for _ in 0..10 {
-                    app.scroll_down();
-                }
-            }
+        KeyCode::Char('[') if app.focus == Focus::Files || app.diff_mode => {
+            app.prev_changed_file().await
         }
 
-        // Ctrl+D = page down OR kill all processes
+        // Page up/down (Ctrl+U = up, Ctrl+D = down)
+        KeyCode::PageUp => app.scroll_lines(-SCROLL_PAGE),
+        KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.scroll_lines(-SCROLL_PAGE)
+        }
+        KeyCode::PageDown => app.scroll_lines(SCROLL_PAGE),
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            if app.diff_mode || app.focus == Focus::Files {
-                // Diff view: scroll_up moves view down (towards bottom)
-                for _ in 0..10 {
-                    app.scroll_up();
-                }
-            } else {
-                // Chat view: scroll_down moves view down (towards newer messages)
-                for _ in 0..10 {
-                    app.scroll_down();
-                }
-            }
+            app.scroll_lines(SCROLL_PAGE)
         }
 
         // Enter = fullscreen detail view (from any left panel)
@@ -324,6 +621,10 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
                     Focus::Presets => {
                         app.focus = Focus::Sessions;
                     }
+                    // Clear an applied session filter
+                    Focus::Sessions if !app.session_filter.is_empty() => {
+                        app.cancel_session_filter()
+                    }
                     Focus::Sessions => {}
                 }
             }
@@ -335,7 +636,7 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             Focus::Sessions => app.session_list_state.select(Some(0)),
             Focus::Todos => app.todos_scroll = 0,
             Focus::Files => app.files_scroll = 0,
-            Focus::Detail => app.scroll_top(),
+            Focus::Detail => app.scroll_to_top(),
         },
         KeyCode::Char('G') => match app.focus {
             Focus::Presets => {
@@ -345,14 +646,20 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
                 }
             }
             Focus::Sessions => {
-                let len = app.sessions.len();
+                let len = app.visible_sessions().len();
                 if len > 0 {
                     app.session_list_state.select(Some(len - 1));
                 }
             }
             Focus::Todos => app.todos_scroll = app.todos_scroll_max,
             Focus::Files => app.files_scroll = app.files_scroll_max,
-            Focus::Detail => app.scroll_bottom(),
+            Focus::Detail if app.diff_mode => app.scroll_to_bottom(),
+            // Chat: also pulls in messages that arrived since the last load
+            Focus::Detail => {
+                if let Err(e) = app.jump_to_newest().await {
+                    app.set_error(&format!("Failed to reload: {e}"));
+                }
+            }
         },
 
         // Open session in embedded terminal (only from Sessions panel)
@@ -360,7 +667,8 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             if app.focus == Focus::Files || app.diff_mode {
                 // Disabled in diff view for now
             } else if app.selected_session().is_some() {
-                match app.open_embedded_terminal(80, 24) {
+                let (cols, rows) = terminal_pane(app);
+                match app.open_embedded_terminal(cols, rows, &[]) {
                     Ok(_) => app.set_status("Opening Claude... (Ctrl+q to exit)"),
                     Err(e) => app.set_error(&format!("Failed: {}", e)),
                 }
@@ -369,6 +677,25 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             }
         }
 
+        // Resume with extra --add-dir paths for this launch only
+        KeyCode::Char('A') if app.focus == Focus::Sessions => app.start_add_dir_prompt(),
+
+        // Exit lazychat and resume the session in this terminal
+        KeyCode::Char('O') if app.focus == Focus::Sessions => {
+            app.resume_in_shell();
+            if app.should_quit {
+                return Ok(true);
+            }
+        }
+
+        // Read transcript in $PAGER
+        KeyCode::Char('p') if app.focus == Focus::Sessions || app.focus == Focus::Detail => {
+            let (cols, rows) = terminal_pane(app);
+            if let Err(e) = app.open_pager(cols, rows) {
+                app.set_error(&format!("Failed: {e}"));
+            }
+        }
+
         // New session OR spawn preset
         KeyCode::Char('n') => {
             if app.focus == Focus::Presets {
@@ -376,9 +703,11 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
                 if let Err(e) = app.spawn_preset() {
                     app.set_error(&format!("Failed to spawn preset: {e}"));
                 }
+            } else if app.settings.new_session.prompt_cwd {
+                app.start_new_session_prompt();
             } else {
-                // Existing new session logic
-                match app.open_new_embedded_terminal(80, 24) {
+                let (cols, rows) = terminal_pane(app);
+                match app.open_new_embedded_terminal(cols, rows, None) {
                     Ok(_) => app.set_status("Starting new Claude... (Ctrl+q to exit)"),
                     Err(e) => app.set_error(&format!("Failed: {}", e)),
                 }
@@ -387,48 +716,144 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
 
         // Ctrl+F = exit fullscreen (Enter to enter)
         KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-            if app.fullscreen {
-                app.fullscreen = false;
-            }
+            app.fullscreen = false;
+        }
+
+        // Keys for the focused panel (`?` again for the full help)
+        KeyCode::Char('?') => app.open_cheatsheet(),
+
+        // Show all / text only / tools only in the chat
+        KeyCode::Char('T') if !app.diff_mode => app.cycle_chat_filter(),
+
+        // Source JSON of the message at the top of the chat
+        KeyCode::Char('J') if app.focus == Focus::Detail && !app.diff_mode => {
+            app.open_raw_json().await
+        }
+
+        // Hop between your own prompts in the chat
+        KeyCode::Char(c @ ('{' | '}'))
+            if matches!(app.focus, Focus::Sessions | Focus::Detail) && !app.diff_mode =>
+        {
+            app.jump_user_message(c == '}')
+        }
+
+        // One header for consecutive messages from the same role
+        KeyCode::Char('M') => app.toggle_merge_messages(),
+
+        // Copy the conversation (respecting the chat filter) as plain text
+        KeyCode::Char('C')
+            if app.focus == Focus::Sessions || (app.focus == Focus::Detail && !app.diff_mode) =>
+        {
+            app.yank_conversation()
         }
 
-        // Help
-        KeyCode::Char('?') => app.toggle_help(),
+        // Footer status summary
+        KeyCode::Char('S') => app.toggle_status_summary(),
+
+        // Follow the most recently active session on refresh
+        KeyCode::Char('F') => app.toggle_auto_follow(),
+
+        // Mask paths and ids for screenshots
+        KeyCode::Char('H') => app.toggle_redact(),
+
+        // Re-read git stats and the open diff after edits outside lazychat
+        KeyCode::Char('r')
+            if key.modifiers.contains(KeyModifiers::CONTROL) && app.focus != Focus::Presets =>
+        {
+            app.refresh_file_changes().await
+        }
 
         // Rename session
-        KeyCode::Char('r') => {
-            if app.focus == Focus::Sessions {
-                app.start_rename();
-            }
+        KeyCode::Char('r') if app.focus == Focus::Sessions => app.start_rename(),
+
+        // Mark session / jump to mark (vim-style)
+        KeyCode::Char('m') if app.focus == Focus::Sessions && app.selected_session().is_some() => {
+            app.pending_mark = Some(MarkAction::Set)
         }
+        KeyCode::Char('\'') => app.pending_mark = Some(MarkAction::Jump),
 
-        // File filter
-        KeyCode::Char('f') => {
-            if app.focus == Focus::Files {
-                app.start_file_filter();
-            }
+        // Edit session note
+        KeyCode::Char('N') if app.focus == Focus::Sessions => app.start_note(),
+
+        // Edit session tags / filter sessions by name, project or tag
+        KeyCode::Char('t') if app.focus == Focus::Sessions => app.start_tags(),
+        KeyCode::Char('#') if app.focus == Focus::Sessions => app.start_session_filter(),
+        KeyCode::Char('f') if app.focus == Focus::Sessions => app.toggle_today_filter(),
+
+        // Mark two sessions to compare their chats side by side
+        KeyCode::Char('x') if app.focus == Focus::Sessions => app.mark_for_compare().await,
+
+        // Toggle sub-agent tree in the detail view
+        KeyCode::Char('a') if matches!(app.focus, Focus::Sessions | Focus::Detail) => {
+            app.toggle_agents()
         }
 
-        // Toggle file tree view
-        KeyCode::Char('t') => {
-            if app.focus == Focus::Files {
-                app.toggle_file_tree_mode();
+        // Uncommitted churn across active sessions
+        KeyCode::Char('c') if matches!(app.focus, Focus::Sessions | Focus::Detail) => {
+            app.toggle_churn();
+            if app.show_churn {
+                app.refresh_churn(std::time::Instant::now()).await;
             }
         }
 
+        // File filter
+        KeyCode::Char('f') if app.focus == Focus::Files => app.start_file_filter(),
+
+        // Fuzzy jump to a changed file and open its diff
+        KeyCode::Char('/') if app.focus == Focus::Files => app.start_file_jump(),
+
+        // Show file paths relative to the project (yank still copies absolute)
+        KeyCode::Char('P') if app.focus == Focus::Files => app.toggle_relative_paths(),
+
+        // Most recently edited files first
+        KeyCode::Char('s') if app.focus == Focus::Files => app.toggle_files_by_recency().await,
+
+        // Toggle file tree view
+        KeyCode::Char('t') if app.focus == Focus::Files => app.toggle_file_tree_mode(),
+
+        // Copy a ready-to-run resume command for the selected session
+        KeyCode::Char('y') if app.focus == Focus::Sessions => app.yank_resume_command(),
+
         // Yank (copy) file path to clipboard
-        KeyCode::Char('y') => {
-            if app.focus == Focus::Files {
-                if app.yank_file_path() {
-                    if let Some(path) = app.selected_file_path() {
-                        app.set_status(&format!("Copied: {}", path));
-                    }
-                } else {
-                    app.set_error("Failed to copy to clipboard");
+        KeyCode::Char('y') if app.focus == Focus::Files => {
+            if app.yank_file_path() {
+                if let Some(path) = app.selected_file_path() {
+                    app.set_status(&format!("Copied: {}", path));
                 }
+            } else {
+                app.set_error("Failed to copy to clipboard");
             }
         }
 
+        // Yank all (filtered) file paths, newline-separated
+        KeyCode::Char('Y') if app.focus == Focus::Files => match app.yank_all_file_paths() {
+            Some(0) => app.set_status("No files to copy"),
+            Some(count) => app.set_status(&format!("Copied {count} file paths")),
+            None => app.set_error("Failed to copy to clipboard"),
+        },
+
+        // Toggle diff / full file content - works from Files panel or diff view
+        KeyCode::Char('v')
+            if app.focus == Focus::Files || (app.focus == Focus::Detail && app.diff_mode) =>
+        {
+            app.toggle_raw_file_view()
+        }
+
+        KeyCode::Char('X') if app.focus == Focus::Presets => {
+            app.cleanup_dead_processes();
+        }
+
+        // Config files: reload presets, edit presets.toml / config.toml
+        KeyCode::Char('R') => app.reload_presets(),
+        KeyCode::Char('e') if app.focus == Focus::Presets => {
+            let (cols, rows) = terminal_pane(app);
+            app.open_presets_config(cols, rows);
+        }
+        KeyCode::Char(',') => {
+            let (cols, rows) = terminal_pane(app);
+            app.open_settings_config(cols, rows);
+        }
+
         // Edit file in $EDITOR (default: nvim) - works from Files panel or diff view
         KeyCode::Char('e') => {
             let can_edit = (app.focus == Focus::Files
@@ -436,7 +861,7 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
                 && !app.current_file_changes.is_empty();
             if can_edit {
                 // Get terminal size from crossterm
-                let (cols, rows) = crossterm::terminal::size().unwrap_or((80, 24));
+                let (cols, rows) = terminal_pane(app);
                 match app.open_editor(cols, rows) {
                     Ok(_) => app.set_status("Opening editor... (Ctrl+q to exit)"),
                     Err(e) => app.set_error(&format!("Failed: {e}")),
@@ -444,15 +869,32 @@ async fn handle_key(app: &mut App, key: KeyEvent) -> Result<bool> {
             }
         }
 
-        // Kill process (d)
-        KeyCode::Char('d') => {
-            if app.focus == Focus::Sessions {
-                // TODO: Add confirmation dialog
-                // For now, just show message that kill is not yet implemented for sessions
-                app.set_status("Kill process: select from process list (coming in Phase 5)");
+        // Selected file in the user's git difftool
+        KeyCode::Char('V')
+            if (app.focus == Focus::Files || (app.focus == Focus::Detail && app.diff_mode))
+                && !app.current_file_changes.is_empty() =>
+        {
+            let (cols, rows) = terminal_pane(app);
+            match app.open_difftool(cols, rows) {
+                Ok(_) => app.set_status("Opening difftool... (Ctrl+q to exit)"),
+                Err(e) => app.set_error(&format!("Failed: {e}")),
             }
         }
 
+        // Every changed file in one editor (asks first when there are many)
+        KeyCode::Char('O') if app.focus == Focus::Files && !app.current_file_changes.is_empty() => {
+            let (cols, rows) = terminal_pane(app);
+            if let Err(e) = app.request_open_all_files(cols, rows) {
+                app.set_error(&format!("Failed: {e}"));
+            }
+        }
+
+        // Open any file by path in $EDITOR
+        KeyCode::Char('E') => app.start_open_path(),
+
+        // Kill the selected session's managed process (asks first)
+        KeyCode::Char('d') if app.focus == Focus::Sessions => app.request_kill_session(),
+
         // Kill all processes (D)
         KeyCode::Char('D') => {
             if let Err(e) = app.kill_all_processes() {
@@ -0,0 +1,68 @@
+//! Syntax highlighting for the diff and file views
+//!
+//! Loading syntect's syntax and theme sets is slow, so a `Highlighter` is
+//! built once (lazily, via `App::highlighter`) and only the lines currently
+//! on screen are highlighted.
+
+use ratatui::style::{Color, Style};
+use std::path::Path;
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+
+const THEME: &str = "base16-ocean.dark";
+
+pub struct Highlighter {
+    syntaxes: SyntaxSet,
+    theme: Theme,
+}
+
+impl Default for Highlighter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Highlighter {
+    pub fn new() -> Self {
+        let mut themes = ThemeSet::load_defaults();
+        Self {
+            syntaxes: SyntaxSet::load_defaults_nonewlines(),
+            theme: themes.themes.remove(THEME).unwrap_or_default(),
+        }
+    }
+
+    fn syntax_for(&self, path: &str) -> Option<&SyntaxReference> {
+        let ext = Path::new(path).extension()?.to_str()?;
+        self.syntaxes.find_syntax_by_extension(ext)
+    }
+
+    /// Highlight a window of consecutive lines from `path`; `None` when the
+    /// file type is unknown. Each entry is the `(style, text)` runs of a line.
+    pub fn highlight_window(
+        &self,
+        path: &str,
+        lines: &[&str],
+    ) -> Option<Vec<Vec<(Style, String)>>> {
+        let syntax = self.syntax_for(path)?;
+        let mut state = HighlightLines::new(syntax, &self.theme);
+
+        let mut out = Vec::with_capacity(lines.len());
+        for line in lines {
+            let ranges = state.highlight_line(line, &self.syntaxes).ok()?;
+            out.push(
+                ranges
+                    .into_iter()
+                    .map(|(style, text)| {
+                        let fg = style.foreground;
+                        (
+                            Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b)),
+                            text.to_string(),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+        Some(out)
+    }
+}
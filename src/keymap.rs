@@ -0,0 +1,141 @@
+//! Keybinding table behind the help popup and the per-panel cheatsheet
+
+use crate::app::Focus;
+
+/// One entry of the keymap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Binding {
+    pub keys: &'static str,
+    pub action: &'static str,
+    /// Panels where the binding applies; empty means every panel
+    pub focus: &'static [Focus],
+}
+
+const fn bind(keys: &'static str, action: &'static str, focus: &'static [Focus]) -> Binding {
+    Binding {
+        keys,
+        action,
+        focus,
+    }
+}
+
+const ALL: &[Focus] = &[];
+const PRESETS: &[Focus] = &[Focus::Presets];
+const SESSIONS: &[Focus] = &[Focus::Sessions];
+const SESSIONS_DETAIL: &[Focus] = &[Focus::Sessions, Focus::Detail];
+const FILES: &[Focus] = &[Focus::Files];
+const FILES_DETAIL: &[Focus] = &[Focus::Files, Focus::Detail];
+const NOT_PRESETS: &[Focus] = &[Focus::Sessions, Focus::Files, Focus::Todos, Focus::Detail];
+
+/// Every binding in normal mode, grouped into the help popup's sections
+pub const KEYMAP: &[(&str, &[Binding])] = &[
+    (
+        "Navigation",
+        &[
+            bind("j/k", "Move down/up", ALL),
+            bind("h/l", "Switch panels / jump hunks", ALL),
+            bind("g/G", "Top/bottom", ALL),
+            bind("^u/d", "Page up/down", ALL),
+            bind("Tab", "Toggle focus", ALL),
+            bind("Enter", "Fullscreen", NOT_PRESETS),
+            bind("Esc", "Back", ALL),
+        ],
+    ),
+    (
+        "Presets",
+        &[
+            bind("Enter/n", "Spawn preset instances", PRESETS),
+            bind("e", "Edit presets.toml", PRESETS),
+            bind("X", "Clean up dead processes", PRESETS),
+        ],
+    ),
+    (
+        "Sessions",
+        &[
+            bind("o", "Open in terminal", SESSIONS_DETAIL),
+            bind("O", "Exit and resume here", SESSIONS),
+            bind("A", "Open with extra --add-dir", SESSIONS),
+            bind("y", "Copy resume command", SESSIONS),
+            bind("p", "Read in $PAGER", SESSIONS_DETAIL),
+            bind("d", "Kill session's process", SESSIONS),
+            bind("T", "Chat: all/text/tools", SESSIONS_DETAIL),
+            bind("J", "Raw JSON of message", &[Focus::Detail]),
+            bind("{/}", "Prev/next own prompt", SESSIONS_DETAIL),
+            bind("C", "Copy chat as text", SESSIONS_DETAIL),
+            bind("M", "Merge same-role messages", SESSIONS_DETAIL),
+            bind("n", "New session", NOT_PRESETS),
+            bind("r", "Rename", SESSIONS),
+            bind("N", "Edit note", SESSIONS),
+            bind("t", "Edit tags", SESSIONS),
+            bind("#", "Filter sessions (fuzzy)", SESSIONS),
+            bind("f", "Only sessions touched today", SESSIONS),
+            bind("x", "Compare two sessions side by side", SESSIONS),
+            bind("m", "Set mark", SESSIONS),
+            bind("'", "Jump to mark", ALL),
+            bind("a", "Agents tree", SESSIONS_DETAIL),
+            bind("c", "Churn across sessions", SESSIONS_DETAIL),
+        ],
+    ),
+    (
+        "Files",
+        &[
+            bind("e", "Edit in $EDITOR", FILES_DETAIL),
+            bind("E", "Open file by path", ALL),
+            bind("v", "Diff / full file", FILES_DETAIL),
+            bind("V", "Open in git difftool", FILES_DETAIL),
+            bind("O", "Open all changed files in $EDITOR", FILES),
+            bind("w", "Ignore whitespace in diffs", FILES_DETAIL),
+            bind("]/[", "Next/prev changed file", FILES_DETAIL),
+            bind("^r", "Refresh git stats and diff", NOT_PRESETS),
+            bind("f", "Filter", FILES),
+            bind("/", "Go to file (fuzzy)", FILES),
+            bind("t", "Tree/flat", FILES),
+            bind("P", "Project-relative paths", FILES),
+            bind("s", "Sort by last edit / path", FILES),
+            bind("y", "Yank path", FILES),
+            bind("Y", "Yank all paths", FILES),
+        ],
+    ),
+    (
+        "Todos",
+        &[
+            bind("w", "Wrap todos", &[Focus::Todos]),
+            bind("c", "Hide completed todos", &[Focus::Todos]),
+        ],
+    ),
+    (
+        "General",
+        &[
+            bind("?", "Panel keys (again: full help)", ALL),
+            bind("S", "Status summary", ALL),
+            bind("F", "Auto-follow newest session", ALL),
+            bind("H", "Redact paths and ids (screenshots)", ALL),
+            bind("R", "Reload presets", ALL),
+            bind(",", "Edit config", ALL),
+            bind("D", "Kill all processes", ALL),
+            bind("q", "Quit", ALL),
+        ],
+    ),
+];
+
+impl Binding {
+    /// Whether the binding does anything with `focus`
+    pub fn applies_to(&self, focus: Focus) -> bool {
+        self.focus.is_empty() || self.focus.contains(&focus)
+    }
+
+    /// Case-insensitive match on the keys or the action
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.keys.to_lowercase().contains(&query) || self.action.to_lowercase().contains(&query)
+    }
+}
+
+/// Bindings for `focus` whose keys or action contain `query`
+pub fn cheatsheet(focus: Focus, query: &str) -> Vec<&'static Binding> {
+    KEYMAP
+        .iter()
+        .flat_map(|(_, bindings)| bindings.iter())
+        .filter(|b| b.applies_to(focus) && b.matches(query))
+        .collect()
+}
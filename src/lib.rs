@@ -1,7 +1,17 @@
 //! Lazychat library - TUI for AI coding assistants
 
+pub mod app;
+pub mod clipboard;
 pub mod config;
+pub mod data;
+pub mod events;
+pub mod highlight;
+pub mod keymap;
 pub mod process;
+pub mod redact;
+pub mod terminal;
+pub mod text;
+pub mod ui;
 
 // Re-export commonly used types
 pub use config::{Preset, PresetManager};
@@ -1,21 +1,15 @@
-mod app;
-mod config;
-mod data;
-mod events;
-mod process;
-mod terminal;
-mod ui;
-
 use anyhow::Result;
-use app::App;
 use clap::Parser;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use lazychat::data::{claude::ClaudeData, stats};
+use lazychat::{app::App, events};
 use ratatui::prelude::*;
 use std::io;
+use std::path::PathBuf;
 
 #[derive(Parser, Debug)]
 #[command(name = "lazychat")]
@@ -28,11 +22,34 @@ struct Args {
     /// Refresh interval in seconds
     #[arg(short, long, default_value_t = 2)]
     refresh: u64,
+
+    /// Browse only: disable spawning and killing processes
+    #[arg(long)]
+    safe: bool,
+
+    /// Show the commands spawns would run instead of starting them
+    #[arg(long)]
+    dry_run: bool,
+
+    /// ASCII status glyphs for fonts without the Unicode ones
+    #[arg(long)]
+    ascii: bool,
+
+    /// Write per-day stats to FILE (JSON for .json, CSV otherwise) and exit
+    #[arg(long, value_name = "FILE")]
+    export_stats: Option<PathBuf>,
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let _args = Args::parse();
+    let args = Args::parse();
+
+    if let Some(path) = args.export_stats {
+        let stats = stats::load_daily_stats(&ClaudeData::claude_dir()).await?;
+        stats::export_daily_stats(&stats, &path)?;
+        println!("Wrote {} days to {}", stats.len(), path.display());
+        return Ok(());
+    }
 
     // Setup terminal
     enable_raw_mode()?;
@@ -43,7 +60,20 @@ async fn main() -> Result<()> {
 
     // Create app and run
     let mut app = App::new();
-    app.load_data().await?;
+    app.check_claude_available();
+    let _ = app.load_settings();
+    if args.safe {
+        app.settings.safe_mode = true;
+    }
+    if args.dry_run {
+        app.settings.dry_run = true;
+    }
+    if args.ascii {
+        app.settings.status_glyphs.ascii = true;
+    }
+    let _ = app.load_sidecar();
+    // A failed load is kept on `app.load_error` and shown in the UI
+    let _ = app.load_data().await;
 
     // Load presets and process registry (Phase 1 & 2)
     let _ = app.load_presets();
@@ -66,8 +96,15 @@ async fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    for command in &app.dry_run_log {
+        println!("Dry run: {command}");
+    }
+
     if let Err(err) = result {
         eprintln!("Error: {err:?}");
+    } else if let Some(resume) = app.resume_on_exit.take() {
+        // Only returns if the exec itself failed
+        resume.exec()?;
     }
 
     Ok(())
@@ -0,0 +1,58 @@
+//! Typed failures of spawning and tracking Claude processes
+
+use std::io;
+use std::path::PathBuf;
+use thiserror::Error;
+
+/// Why a process operation failed; converts into `anyhow::Error` with `?`
+#[derive(Debug, Error)]
+pub enum ProcessError {
+    #[error("Path traversal not allowed: {0}")]
+    PathTraversal(String),
+
+    #[error("Invalid preset name (use alphanumeric, dash, underscore): {0}")]
+    InvalidPresetName(String),
+
+    #[error("Working directory does not exist: {0}")]
+    MissingCwd(String),
+
+    #[error("{program} not found (is it installed and on PATH?)")]
+    NotFound { program: String },
+
+    #[error("Permission denied running {program}")]
+    PermissionDenied { program: String },
+
+    #[error("Failed to spawn {program}: {source}")]
+    Spawn {
+        program: String,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Failed to stop process: {0}")]
+    Kill(#[source] io::Error),
+
+    #[error("Failed to access process registry {}: {source}", path.display())]
+    Registry {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("Failed to write process registry: {0}")]
+    Serialize(#[from] serde_json::Error),
+}
+
+impl ProcessError {
+    /// Classify an error from `Command::spawn` of `program`
+    pub(crate) fn spawn(program: &str, source: io::Error) -> Self {
+        let program = program.to_string();
+        match source.kind() {
+            io::ErrorKind::NotFound => Self::NotFound { program },
+            io::ErrorKind::PermissionDenied => Self::PermissionDenied { program },
+            _ => Self::Spawn { program, source },
+        }
+    }
+}
+
+pub type Result<T, E = ProcessError> = std::result::Result<T, E>;
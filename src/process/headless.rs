@@ -1,6 +1,6 @@
 //! Headless terminal management for background Claude processes
 
-use anyhow::{Context, Result};
+use super::error::{ProcessError, Result};
 use std::process::{Child, Command, Stdio};
 use uuid::Uuid;
 
@@ -11,7 +11,7 @@ fn validate_path(path: &str) -> Result<()> {
     // Reject paths with ".." components
     for component in path_buf.components() {
         if matches!(component, std::path::Component::ParentDir) {
-            anyhow::bail!("Path traversal not allowed: {}", path);
+            return Err(ProcessError::PathTraversal(path.to_string()));
         }
     }
 
@@ -19,15 +19,12 @@ fn validate_path(path: &str) -> Result<()> {
 }
 
 /// Validate preset name only contains safe characters
-fn validate_preset_name(name: &str) -> Result<()> {
+pub(crate) fn validate_preset_name(name: &str) -> Result<()> {
     if !name
         .chars()
         .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
     {
-        anyhow::bail!(
-            "Invalid preset name (use alphanumeric, dash, underscore): {}",
-            name
-        );
+        return Err(ProcessError::InvalidPresetName(name.to_string()));
     }
     Ok(())
 }
@@ -41,6 +38,16 @@ pub struct HeadlessTerminal {
 impl HeadlessTerminal {
     /// Spawn a new headless Claude instance
     pub fn spawn(cwd: &str, add_dirs: Vec<String>, extra_args: Vec<String>) -> Result<Self> {
+        Self::spawn_program("claude", cwd, add_dirs, extra_args)
+    }
+
+    /// Spawn `program` with Claude's arguments (tests substitute a stand-in)
+    pub fn spawn_program(
+        program: &str,
+        cwd: &str,
+        add_dirs: Vec<String>,
+        extra_args: Vec<String>,
+    ) -> Result<Self> {
         // Generate a unique session ID for this headless instance
         let session_id = Uuid::new_v4().to_string();
 
@@ -49,25 +56,15 @@ impl HeadlessTerminal {
         for dir in &add_dirs {
             validate_path(dir)?;
         }
-
-        // Build the claude command
-        let mut cmd = Command::new("claude");
-
-        // Set working directory
-        cmd.current_dir(cwd);
-
-        // Add additional directories if specified
-        for dir in &add_dirs {
-            cmd.arg("--add-dir").arg(dir);
-        }
-
-        // Add extra arguments from preset (e.g., --dangerously-skip-permissions)
-        for arg in &extra_args {
-            cmd.arg(arg);
+        // Spawning in a missing directory also fails with NotFound; tell them apart
+        if !std::path::Path::new(cwd).is_dir() {
+            return Err(ProcessError::MissingCwd(cwd.to_string()));
         }
 
-        // Set session ID for resumability
-        cmd.arg("--session-id").arg(&session_id);
+        // Build the claude command in the working directory
+        let mut cmd = Command::new(program);
+        cmd.current_dir(cwd);
+        cmd.args(Self::args(&add_dirs, &extra_args, &session_id));
 
         // Run in headless mode (no TTY)
         cmd.stdin(Stdio::null())
@@ -75,9 +72,7 @@ impl HeadlessTerminal {
             .stderr(Stdio::null());
 
         // Spawn the process
-        let process = cmd
-            .spawn()
-            .context("Failed to spawn headless Claude process")?;
+        let process = cmd.spawn().map_err(|e| ProcessError::spawn(program, e))?;
 
         Ok(Self {
             process,
@@ -85,6 +80,42 @@ impl HeadlessTerminal {
         })
     }
 
+    /// What `spawn_program` would run, as a shell line (for dry runs)
+    pub fn command_line(
+        program: &str,
+        cwd: &str,
+        add_dirs: &[String],
+        extra_args: &[String],
+    ) -> Result<String> {
+        validate_path(cwd)?;
+        for dir in add_dirs {
+            validate_path(dir)?;
+        }
+        let args: Vec<String> = Self::args(add_dirs, extra_args, "<new-session-id>")
+            .iter()
+            .map(|arg| crate::terminal::shell_escape(arg))
+            .collect();
+        Ok(format!(
+            "cd {} && {program} {}",
+            crate::terminal::shell_escape(cwd),
+            args.join(" ")
+        ))
+    }
+
+    /// `--add-dir`s, the preset's extra arguments (e.g. --dangerously-skip-permissions),
+    /// then the session ID for resumability
+    fn args(add_dirs: &[String], extra_args: &[String], session_id: &str) -> Vec<String> {
+        let mut args = Vec::new();
+        for dir in add_dirs {
+            args.push("--add-dir".to_string());
+            args.push(dir.clone());
+        }
+        args.extend(extra_args.iter().cloned());
+        args.push("--session-id".to_string());
+        args.push(session_id.to_string());
+        args
+    }
+
     /// Get the session ID
     pub fn session_id(&self) -> &str {
         &self.session_id
@@ -105,7 +136,7 @@ impl HeadlessTerminal {
 
     /// Terminate the headless instance
     pub fn terminate(mut self) -> Result<()> {
-        self.process.kill()?;
+        self.process.kill().map_err(ProcessError::Kill)?;
         Ok(())
     }
 }
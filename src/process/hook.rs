@@ -0,0 +1,45 @@
+//! User command run whenever the selected session changes (`on_select_command`)
+
+use super::which;
+use crate::data::Session;
+use anyhow::{bail, Result};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// `<program> <session id> <project dir>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectHook {
+    pub program: PathBuf,
+    pub args: Vec<String>,
+}
+
+impl SelectHook {
+    /// Hook for `session`; `program` must be an executable path or on PATH
+    pub fn for_session(program: &str, session: &Session) -> Result<Self> {
+        let path_var = std::env::var_os("PATH").unwrap_or_default();
+        let Some(program) = which::resolve_program(program.trim(), &path_var) else {
+            bail!("on_select_command not executable: {program}");
+        };
+        Ok(Self {
+            program,
+            args: vec![session.id.clone(), session.project_dir()],
+        })
+    }
+
+    /// The command with all stdio detached from the TUI
+    pub fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        cmd
+    }
+
+    /// Start without waiting; a background thread reaps the child
+    pub fn spawn(&self) -> Result<()> {
+        let mut child = self.command().spawn()?;
+        std::thread::spawn(move || child.wait());
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
 //! Process management for background Claude instances
 
 pub mod adoption;
+pub mod error;
 pub mod headless;
+pub mod hook;
 pub mod registry;
+pub mod resume;
+pub mod which;
+pub mod worktree;
 
 pub use adoption::{discover_orphan_sessions, OrphanSession};
+pub use error::ProcessError;
 pub use headless::HeadlessTerminal;
 pub use registry::{ManagedProcess, ProcessRegistry};
+pub use resume::ResumeCommand;
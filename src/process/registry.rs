@@ -1,6 +1,6 @@
 //! Process registry - tracks managed Claude processes
 
-use anyhow::Result;
+use super::error::{ProcessError, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -17,6 +17,9 @@ pub struct ManagedProcess {
     pub add_dirs: Vec<String>,
     pub started_at: DateTime<Utc>,
     pub status: String, // "running", "idle", "dead"
+    /// Source repo when `cwd` is a worktree created for this process
+    #[serde(default)]
+    pub worktree_repo: Option<String>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize)]
@@ -33,15 +36,24 @@ pub struct ProcessRegistry {
 impl ProcessRegistry {
     /// Load registry from ~/.cache/lazychat/processes.json
     pub fn load() -> Result<Self> {
-        let path = Self::registry_path();
+        Self::load_from(Self::registry_path())
+    }
 
+    /// Load registry from an explicit path
+    pub fn load_from(path: PathBuf) -> Result<Self> {
         // Ensure directory exists
         if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
+            fs::create_dir_all(parent).map_err(|source| ProcessError::Registry {
+                path: parent.to_path_buf(),
+                source,
+            })?;
         }
 
         let data = if path.exists() {
-            let content = fs::read_to_string(&path)?;
+            let content = fs::read_to_string(&path).map_err(|source| ProcessError::Registry {
+                path: path.clone(),
+                source,
+            })?;
             match serde_json::from_str(&content) {
                 Ok(d) => d,
                 Err(e) => {
@@ -67,7 +79,10 @@ impl ProcessRegistry {
     /// Save registry to disk
     pub fn save(&self) -> Result<()> {
         let content = serde_json::to_string_pretty(&self.data)?;
-        fs::write(&self.path, content)?;
+        fs::write(&self.path, content).map_err(|source| ProcessError::Registry {
+            path: self.path.clone(),
+            source,
+        })?;
         Ok(())
     }
 
@@ -93,6 +108,7 @@ impl ProcessRegistry {
             add_dirs,
             started_at: Utc::now(),
             status: "running".to_string(),
+            worktree_repo: None,
         });
 
         self.save()
@@ -153,6 +169,31 @@ impl ProcessRegistry {
         Ok(dead)
     }
 
+    /// Number of registered processes whose PID still exists
+    pub fn alive_count(&self) -> usize {
+        use sysinfo::{Pid, System};
+
+        if self.data.processes.is_empty() {
+            return 0;
+        }
+        // Look up only the registered PIDs, not every process on the host
+        let mut sys = System::new();
+        self.data
+            .processes
+            .iter()
+            .filter(|p| sys.refresh_process(Pid::from_u32(p.pid)))
+            .count()
+    }
+
+    /// Record that a process runs in a worktree checked out from `repo`
+    pub fn set_worktree_repo(&mut self, pid: u32, repo: String) -> Result<()> {
+        if let Some(proc) = self.data.processes.iter_mut().find(|p| p.pid == pid) {
+            proc.worktree_repo = Some(repo);
+            self.save()?;
+        }
+        Ok(())
+    }
+
     /// Update status of a process
     pub fn update_status(&mut self, pid: u32, status: &str) -> Result<()> {
         if let Some(proc) = self.data.processes.iter_mut().find(|p| p.pid == pid) {
@@ -0,0 +1,92 @@
+//! Hand the real terminal over to `claude --resume` after lazychat exits
+
+use crate::config::presets::Preset;
+use crate::data::Session;
+use anyhow::Result;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// Command run in place of lazychat to resume a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResumeCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub cwd: PathBuf,
+}
+
+impl ResumeCommand {
+    /// `claude --resume <id>` in the session's project directory
+    pub fn for_session(session: &Session) -> Self {
+        Self {
+            program: "claude".to_string(),
+            args: vec!["--resume".to_string(), session.id.clone()],
+            cwd: PathBuf::from(session.project_dir()),
+        }
+    }
+
+    /// Copyable resume command; a matching preset adds its dirs and extra args
+    pub fn for_clipboard(session: &Session, preset: Option<&Preset>) -> Self {
+        let mut cmd = Self::for_session(session);
+        cmd.args.push("--dangerously-skip-permissions".to_string());
+        if let Some(preset) = preset {
+            for dir in &preset.add_dirs {
+                cmd.args.push("--add-dir".to_string());
+                cmd.args.push(dir.clone());
+            }
+            for arg in &preset.extra_args {
+                if !cmd.args.contains(arg) {
+                    cmd.args.push(arg.clone());
+                }
+            }
+        }
+        cmd
+    }
+
+    /// `cd <cwd> && <program> <args>`, quoted for a POSIX shell
+    pub fn shell_line(&self) -> String {
+        let mut words = vec![shell_word(&self.program)];
+        words.extend(self.args.iter().map(|a| shell_word(a)));
+        format!(
+            "cd {} && {}",
+            shell_word(&self.cwd.to_string_lossy()),
+            words.join(" ")
+        )
+    }
+
+    fn command(&self) -> Command {
+        let mut cmd = Command::new(&self.program);
+        cmd.args(&self.args);
+        // Fall back to the current dir if the project was removed
+        if self.cwd.is_dir() {
+            cmd.current_dir(&self.cwd);
+        }
+        cmd
+    }
+
+    /// Replace the current process; only returns on failure
+    #[cfg(unix)]
+    pub fn exec(&self) -> Result<()> {
+        use std::os::unix::process::CommandExt;
+        let err = self.command().exec();
+        Err(err.into())
+    }
+
+    /// Run to completion (no `exec` outside Unix)
+    #[cfg(not(unix))]
+    pub fn exec(&self) -> Result<()> {
+        self.command().status()?;
+        Ok(())
+    }
+}
+
+/// Quote a word only when the shell would otherwise split or expand it
+fn shell_word(s: &str) -> String {
+    let plain = !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./=,:@+".contains(c));
+    if plain {
+        s.to_string()
+    } else {
+        format!("'{}'", s.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,42 @@
+//! Locate executables on PATH (used to check that `claude` is installed)
+
+use std::ffi::OsStr;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Install hint shown when `claude` cannot be found
+pub const CLAUDE_INSTALL_HINT: &str =
+    "claude not found on PATH - install with `npm install -g @anthropic-ai/claude-code` and restart";
+
+/// Find an executable named `program` in a PATH-style list of directories
+pub fn find_executable(program: &str, path_var: &OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path_var)
+        .map(|dir| dir.join(program))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// `program` itself when it is a path (`~/` expands), else its PATH lookup;
+/// `None` unless the result is executable
+pub fn resolve_program(program: &str, path_var: &OsStr) -> Option<PathBuf> {
+    if !program.contains('/') {
+        return find_executable(program, path_var);
+    }
+    let path = match program.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => PathBuf::from(program),
+    };
+    is_executable(&path).then_some(path)
+}
+
+/// Whether the `claude` CLI is on the current PATH
+pub fn claude_available() -> bool {
+    std::env::var_os("PATH")
+        .and_then(|path| find_executable("claude", &path))
+        .is_some()
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
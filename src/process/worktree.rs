@@ -0,0 +1,60 @@
+//! Git worktree management - isolated checkouts for parallel preset instances
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::headless::validate_preset_name;
+
+/// Path of the worktree for a preset instance: `<repo>/../<name>-wt<index>`
+pub fn worktree_path(repo: &str, preset_name: &str, index: u32) -> PathBuf {
+    let repo = Path::new(repo);
+    repo.parent()
+        .unwrap_or(repo)
+        .join(format!("{preset_name}-wt{index}"))
+}
+
+/// Create a detached worktree of `repo` for one preset instance, returning its path
+pub fn create_worktree(repo: &str, preset_name: &str, index: u32) -> Result<String> {
+    validate_preset_name(preset_name)?;
+
+    let path = worktree_path(repo, preset_name, index);
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["worktree", "add", "--detach"])
+        .arg(&path)
+        .output()
+        .context("Failed to run git worktree add")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git worktree add failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Remove a worktree created by `create_worktree`.
+///
+/// Not forced: a worktree with uncommitted changes is kept and an error returned,
+/// so work done by an instance is never thrown away on kill.
+pub fn remove_worktree(repo: &str, path: &str) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["worktree", "remove", path])
+        .output()
+        .context("Failed to run git worktree remove")?;
+
+    if !output.status.success() {
+        anyhow::bail!(
+            "git worktree remove failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+//! Masking of user names, session ids and chat text for screenshots. Applied
+//! at render time only; stored data is never touched.
+
+use crate::data::Session;
+use std::borrow::Cow;
+
+/// Directories whose next path segment is a user name
+const HOME_ROOTS: [&str; 2] = ["/Users/", "/home/"];
+
+/// Replace the user name in home-directory paths anywhere in `text` with `***`
+pub fn redact_home(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some((start, root)) = HOME_ROOTS
+        .iter()
+        .filter_map(|root| rest.find(root).map(|i| (i, root.len())))
+        .min()
+    {
+        let name_start = start + root;
+        let name_len = rest[name_start..]
+            .find(|c: char| c == '/' || c.is_whitespace() || c == '\'' || c == '"')
+            .unwrap_or(rest.len() - name_start);
+        out.push_str(&rest[..name_start]);
+        if name_len > 0 {
+            out.push_str("***");
+        }
+        rest = &rest[name_start + name_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Keep the first four characters of an id (and its dashes), mask the rest
+pub fn redact_id(id: &str) -> String {
+    id.chars()
+        .enumerate()
+        .map(|(i, c)| if i < 4 || c == '-' { c } else { '*' })
+        .collect()
+}
+
+/// Message text reduced to its length
+pub fn redact_content(text: &str) -> String {
+    format!("[{} chars]", text.chars().count())
+}
+
+/// What to mask in the current frame
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Redactor {
+    /// Mask home-directory user names and session ids
+    pub on: bool,
+    /// Also replace message text with its length
+    pub content: bool,
+}
+
+impl Redactor {
+    pub fn path(self, text: &str) -> Cow<'_, str> {
+        if self.on {
+            Cow::Owned(redact_home(text))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    pub fn id(self, id: &str) -> Cow<'_, str> {
+        if self.on {
+            Cow::Owned(redact_id(id))
+        } else {
+            Cow::Borrowed(id)
+        }
+    }
+
+    /// Whether message text is hidden entirely
+    pub fn hides_content(self) -> bool {
+        self.on && self.content
+    }
+
+    /// Message-like text (descriptions, prompts): its length only when
+    /// content is hidden, otherwise with home paths masked
+    pub fn text(self, text: &str) -> Cow<'_, str> {
+        if self.hides_content() {
+            Cow::Owned(redact_content(text))
+        } else {
+            self.path(text)
+        }
+    }
+
+    /// Name a session goes by in every view: custom name > description >
+    /// project name
+    pub fn session_name(self, session: &Session) -> String {
+        session
+            .custom_name
+            .clone()
+            .or_else(|| {
+                session
+                    .description
+                    .as_deref()
+                    .map(|d| self.text(d).into_owned())
+            })
+            .unwrap_or_else(|| session.project_name.clone())
+    }
+}
@@ -1,20 +1,29 @@
 use anyhow::Result;
 use portable_pty::{native_pty_system, CommandBuilder, PtyPair, PtySize};
 use std::io::{Read, Write};
+use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::thread;
+use tempfile::NamedTempFile;
+
+/// A rendered screen cell: character, foreground, background, bold
+pub type StyledCell = (char, vt100::Color, vt100::Color, bool);
 
 pub struct EmbeddedTerminal {
     pty_pair: PtyPair,
     parser: Arc<Mutex<vt100::Parser>>,
     writer: Box<dyn Write + Send>,
     running: Arc<Mutex<bool>>,
+    /// Shell used for `<shell> -c` spawns
+    shell: String,
+    /// Files the spawned program reads, removed when the terminal is dropped
+    temp_files: Vec<NamedTempFile>,
 }
 
 /// Escape a string for safe use in single-quoted shell arguments.
 /// Single quotes within the string are handled by ending the quote,
 /// adding an escaped single quote, and starting a new quote.
-fn shell_escape(s: &str) -> String {
+pub(crate) fn shell_escape(s: &str) -> String {
     if s.chars()
         .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '/' || c == '_' || c == '-')
     {
@@ -26,6 +35,152 @@ fn shell_escape(s: &str) -> String {
     }
 }
 
+/// Pager from $PAGER, falling back to `less -R`
+pub fn default_pager() -> String {
+    std::env::var("PAGER")
+        .ok()
+        .filter(|p| !p.trim().is_empty())
+        .unwrap_or_else(|| "less -R".to_string())
+}
+
+/// Shell command that shows `file_path` in `pager` (the pager may carry its own args)
+pub fn pager_command(pager: &str, file_path: &str) -> String {
+    format!("{pager} {}", shell_escape(file_path))
+}
+
+/// `git difftool` for one file, run in the project so its difftool config applies
+pub fn difftool_script(project_dir: &str, file_path: &str) -> String {
+    format!(
+        "cd {} && git difftool -y -- {}",
+        shell_escape(project_dir),
+        shell_escape(file_path)
+    )
+}
+
+/// `editor` on every file at once, from the project; vim-style editors get
+/// `-p` so each file opens in its own tab
+pub fn multi_editor_script(editor: &str, project_dir: &str, files: &[String]) -> String {
+    let program = editor.split_whitespace().next().unwrap_or_default();
+    let program = program.rsplit('/').next().unwrap_or_default();
+    let tabs = if matches!(program, "nvim" | "vim" | "gvim" | "mvim") {
+        " -p"
+    } else {
+        ""
+    };
+    let files: Vec<String> = files.iter().map(|f| shell_escape(f)).collect();
+    format!(
+        "cd {} && {editor}{tabs} {}",
+        shell_escape(project_dir),
+        files.join(" ")
+    )
+}
+
+/// `<shell> -c <script>`
+pub fn shell_command(shell: &str, script: &str) -> CommandBuilder {
+    let mut cmd = CommandBuilder::new(shell);
+    cmd.args(["-c", script]);
+    cmd
+}
+
+/// Script that resumes `session_id` in `project_dir`, with extra `--add-dir`s
+pub fn resume_claude_script(project_dir: &str, session_id: &str, add_dirs: &[String]) -> String {
+    let mut script = format!(
+        "cd {} 2>/dev/null || cd ~; claude --resume {session_id} --dangerously-skip-permissions",
+        shell_escape(project_dir)
+    );
+    for dir in add_dirs {
+        script.push_str(&format!(" --add-dir {}", shell_escape(dir)));
+    }
+    script
+}
+
+/// `claude <args>` for a new session, run in `cwd` when given
+pub fn new_claude_command(args: &[String], cwd: Option<&str>) -> CommandBuilder {
+    let mut cmd = CommandBuilder::new("claude");
+    cmd.args(args);
+    if let Some(dir) = cwd {
+        cmd.cwd(dir);
+    }
+    cmd
+}
+
+/// Shell form of `new_claude_command`, as a dry run reports it
+pub fn new_claude_script(args: &[String], cwd: Option<&str>) -> String {
+    let mut script = String::new();
+    if let Some(dir) = cwd {
+        script.push_str(&format!("cd {} && ", shell_escape(dir)));
+    }
+    script.push_str("claude");
+    for arg in args {
+        script.push_str(&format!(" {}", shell_escape(arg)));
+    }
+    script
+}
+
+/// Whether `shell` understands bash-style `<(...)` process substitution
+pub fn supports_process_substitution(shell: &str) -> bool {
+    matches!(
+        Path::new(shell).file_name().and_then(|n| n.to_str()),
+        Some("bash" | "zsh")
+    )
+}
+
+/// Script that opens `file_path` in `editor -d` against its HEAD version.
+/// Shells without process substitution get the HEAD version via a temp file,
+/// returned alongside; it must outlive the editor.
+pub fn editor_diff_script(
+    shell: &str,
+    editor: &str,
+    file_path: &str,
+) -> Result<(String, Option<NamedTempFile>)> {
+    let escaped_path = shell_escape(file_path);
+
+    if supports_process_substitution(shell) {
+        // editor -d file <(git show HEAD:file)
+        let script = format!(
+            "{editor} -d {escaped_path} <(git show HEAD:{escaped_path} 2>/dev/null || echo 'New file')",
+        );
+        return Ok((script, None));
+    }
+
+    let head = write_head_version(file_path)?;
+    let script = format!(
+        "{editor} -d {escaped_path} {}",
+        shell_escape(&head.path().to_string_lossy())
+    );
+    Ok((script, Some(head)))
+}
+
+/// Write the HEAD version of `file_path` (or "New file") to a temp file
+/// named after it, so the editor still detects the file type
+fn write_head_version(file_path: &str) -> Result<NamedTempFile> {
+    let path = Path::new(file_path);
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let dir = path
+        .parent()
+        .filter(|d| d.is_dir())
+        .unwrap_or_else(|| Path::new("."));
+
+    let output = std::process::Command::new("git")
+        .args(["show", &format!("HEAD:./{name}")])
+        .current_dir(dir)
+        .output();
+    let content = match output {
+        Ok(out) if out.status.success() => out.stdout,
+        _ => b"New file\n".to_vec(),
+    };
+
+    let mut head = tempfile::Builder::new()
+        .prefix("lazychat-HEAD-")
+        .suffix(&format!("-{name}"))
+        .tempfile()?;
+    head.write_all(&content)?;
+    Ok(head)
+}
+
 impl EmbeddedTerminal {
     pub fn new(cols: u16, rows: u16) -> Result<Self> {
         let pty_system = native_pty_system();
@@ -45,9 +200,27 @@ impl EmbeddedTerminal {
             parser,
             writer,
             running,
+            shell: "bash".to_string(),
+            temp_files: Vec::new(),
         })
     }
 
+    /// Use `shell` instead of bash for `-c` spawns
+    pub fn with_shell(mut self, shell: &str) -> Self {
+        self.shell = shell.to_string();
+        self
+    }
+
+    /// Send input to `writer` instead of the PTY (tests simulate dead terminals)
+    pub fn with_writer(mut self, writer: Box<dyn Write + Send>) -> Self {
+        self.writer = writer;
+        self
+    }
+
+    pub fn shell(&self) -> &str {
+        &self.shell
+    }
+
     /// Start the reader thread that processes PTY output
     fn start_reader_thread(&self) -> Result<()> {
         let mut reader = self.pty_pair.master.try_clone_reader()?;
@@ -76,16 +249,8 @@ impl EmbeddedTerminal {
         Ok(())
     }
 
-    pub fn spawn_claude(&mut self, project_dir: &str, session_id: &str) -> Result<()> {
-        let escaped_dir = shell_escape(project_dir);
-        let mut cmd = CommandBuilder::new("bash");
-        cmd.args([
-            "-c",
-            &format!(
-                "cd {escaped_dir} 2>/dev/null || cd ~; claude --resume {session_id} --dangerously-skip-permissions",
-            ),
-        ]);
-
+    /// Run `cmd` on the PTY in the background and start reading its output
+    fn spawn(&mut self, cmd: CommandBuilder) -> Result<()> {
         let child = self.pty_pair.slave.spawn_command(cmd)?;
         *self.running.lock().unwrap() = true;
 
@@ -97,18 +262,23 @@ impl EmbeddedTerminal {
         Ok(())
     }
 
-    pub fn spawn_new_claude(&mut self) -> Result<()> {
-        let mut cmd = CommandBuilder::new("claude");
-        cmd.arg("--dangerously-skip-permissions");
-
-        let child = self.pty_pair.slave.spawn_command(cmd)?;
-        *self.running.lock().unwrap() = true;
-
-        self.start_reader_thread()?;
+    /// `spawn` of `<shell> -c <script>`
+    fn spawn_script(&mut self, script: &str) -> Result<()> {
+        self.spawn(shell_command(&self.shell, script))
+    }
 
-        drop(child);
+    pub fn spawn_claude(
+        &mut self,
+        project_dir: &str,
+        session_id: &str,
+        add_dirs: &[String],
+    ) -> Result<()> {
+        self.spawn_script(&resume_claude_script(project_dir, session_id, add_dirs))
+    }
 
-        Ok(())
+    /// Start a fresh `claude` with `args`, in `cwd` when given
+    pub fn spawn_new_claude(&mut self, args: &[String], cwd: Option<&str>) -> Result<()> {
+        self.spawn(new_claude_command(args, cwd))
     }
 
     pub fn spawn_editor(&mut self, file_path: &str) -> Result<()> {
@@ -119,28 +289,37 @@ impl EmbeddedTerminal {
         // Get editor from environment, default to nvim
         let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nvim".to_string());
 
-        // Escape the file path for shell safety
-        let escaped_path = shell_escape(file_path);
-
-        // Use bash with process substitution for diff mode
-        // editor -d file <(git show HEAD:file)
-        let script = format!(
-            "{editor} -d {escaped_path} <(git show HEAD:{escaped_path} 2>/dev/null || echo 'New file')",
-        );
-
-        let mut cmd = CommandBuilder::new("bash");
-        cmd.args(["-c", &script]);
-
-        let child = self.pty_pair.slave.spawn_command(cmd)?;
-        *self.running.lock().unwrap() = true;
+        let (script, head) = editor_diff_script(&self.shell, &editor, file_path)?;
+        self.spawn_script(&script)?;
+        self.temp_files.extend(head);
+        Ok(())
+    }
 
-        self.start_reader_thread()?;
+    /// Open a file in $EDITOR without the diff split
+    pub fn spawn_file_editor(&mut self, file_path: &str) -> Result<()> {
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nvim".to_string());
+        self.spawn_script(&format!("{editor} {}", shell_escape(file_path)))
+    }
 
-        drop(child);
+    /// Open several files in one $EDITOR, run from `project_dir`
+    pub fn spawn_files_editor(&mut self, project_dir: &str, files: &[String]) -> Result<()> {
+        let editor = std::env::var("EDITOR").unwrap_or_else(|_| "nvim".to_string());
+        self.spawn_script(&multi_editor_script(&editor, project_dir, files))
+    }
 
+    /// Show `file` read-only in $PAGER; it is deleted with the terminal
+    pub fn spawn_pager(&mut self, file: NamedTempFile) -> Result<()> {
+        let path = file.path().to_string_lossy().to_string();
+        self.spawn_script(&pager_command(&default_pager(), &path))?;
+        self.temp_files.push(file);
         Ok(())
     }
 
+    /// Show a file's changes in the user's git difftool
+    pub fn spawn_difftool(&mut self, project_dir: &str, file_path: &str) -> Result<()> {
+        self.spawn_script(&difftool_script(project_dir, file_path))
+    }
+
     pub fn write(&mut self, data: &[u8]) -> Result<()> {
         self.writer.write_all(data)?;
         self.writer.flush()?;
@@ -160,9 +339,7 @@ impl EmbeddedTerminal {
         Ok(())
     }
 
-    pub fn get_screen_with_styles(
-        &self,
-    ) -> Option<Vec<Vec<(char, vt100::Color, vt100::Color, bool)>>> {
+    pub fn get_screen_with_styles(&self) -> Option<Vec<Vec<StyledCell>>> {
         self.parser.lock().ok().map(|p| {
             let screen = p.screen();
             (0..screen.size().0)
@@ -0,0 +1,83 @@
+//! Display-width aware line wrapping shared by the chat and diff views
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Drop carriage returns ending lines (CRLF or a stray trailing `\r`), which
+/// would otherwise render as artifacts and skew width measurement
+pub fn trim_line_cr(text: &str) -> String {
+    if !text.contains('\r') {
+        return text.to_string();
+    }
+    text.split('\n')
+        .map(|line| line.trim_end_matches('\r'))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Wrap width for a pane `width` columns wide, capped at `max` (0 = no cap)
+pub fn capped_width(width: usize, max: usize) -> usize {
+    if max == 0 {
+        width
+    } else {
+        width.min(max)
+    }
+}
+
+/// Split `line` into chunks no wider than `width` terminal columns, breaking
+/// between characters. Wide characters (CJK, emoji) count as two columns.
+pub fn wrap_columns(line: &str, width: usize) -> Vec<&str> {
+    if width == 0 || line.width() <= width {
+        return vec![line];
+    }
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut current = 0;
+
+    for (idx, c) in line.char_indices() {
+        let w = c.width().unwrap_or(0);
+        if current + w > width && idx > start {
+            chunks.push(&line[start..idx]);
+            start = idx;
+            current = 0;
+        }
+        current += w;
+    }
+    chunks.push(&line[start..]);
+
+    chunks
+}
+
+/// Word-wrap `line` to `width` columns; words wider than a full line are split
+pub fn wrap_words(line: &str, width: usize) -> Vec<String> {
+    if width == 0 || line.width() <= width {
+        return vec![line.to_string()];
+    }
+
+    let mut lines = Vec::new();
+    let mut current_line = String::new();
+    let mut current_width = 0;
+
+    for word in line.split_whitespace() {
+        for piece in wrap_columns(word, width) {
+            let piece_width = piece.width();
+            if current_line.is_empty() {
+                current_line.push_str(piece);
+                current_width = piece_width;
+            } else if current_width + 1 + piece_width <= width {
+                current_line.push(' ');
+                current_line.push_str(piece);
+                current_width += 1 + piece_width;
+            } else {
+                lines.push(std::mem::take(&mut current_line));
+                current_line.push_str(piece);
+                current_width = piece_width;
+            }
+        }
+    }
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines
+}
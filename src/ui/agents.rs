@@ -0,0 +1,68 @@
+use super::{relative_time, styled_block, truncate, INFO, MUTED};
+use crate::app::App;
+use crate::data::Agent;
+use ratatui::{
+    prelude::*,
+    widgets::{Paragraph, Wrap},
+};
+
+/// Flatten an agent tree into rows of (tree prefix, agent), depth first
+pub fn flatten_agent_tree(agents: &[Agent]) -> Vec<(String, &Agent)> {
+    fn walk<'a>(agents: &'a [Agent], indent: &str, rows: &mut Vec<(String, &'a Agent)>) {
+        for (i, agent) in agents.iter().enumerate() {
+            let last = i + 1 == agents.len();
+            let branch = if last { "└─ " } else { "├─ " };
+            rows.push((format!("{}{}", indent, branch), agent));
+
+            let child_indent = format!("{}{}", indent, if last { "   " } else { "│  " });
+            walk(&agent.children, &child_indent, rows);
+        }
+    }
+
+    let mut rows = Vec::new();
+    walk(agents, "", &mut rows);
+    rows
+}
+
+pub fn draw_agents_view(f: &mut Frame, app: &App, area: Rect, is_focused: bool) {
+    let rows = flatten_agent_tree(&app.session_agents);
+    let title = format!("Agents ({})", rows.len());
+    let block = styled_block(&title, is_focused);
+
+    if rows.is_empty() {
+        let empty = Paragraph::new("No sub-agents in this session")
+            .style(Style::default().fg(MUTED))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    let width = area.width.saturating_sub(2) as usize;
+    let redact = app.redactor();
+    let lines: Vec<Line> = rows
+        .iter()
+        .map(|(prefix, agent)| {
+            let id: String = redact.id(&agent.id).chars().take(8).collect();
+            let time = if agent.started_at.is_some() {
+                format!(" {}", relative_time(&agent.started_at))
+            } else {
+                String::new()
+            };
+            let used = prefix.chars().count() + id.len() + time.chars().count() + 2;
+            let desc_width = width.saturating_sub(used).max(10);
+
+            Line::from(vec![
+                Span::styled(prefix.clone(), Style::default().fg(MUTED)),
+                Span::styled(id, Style::default().fg(INFO)),
+                Span::raw(" "),
+                Span::raw(truncate(&redact.text(&agent.description), desc_width)),
+                Span::styled(time, Style::default().fg(MUTED)),
+            ])
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,56 @@
+use super::{diff_add, diff_remove, styled_block, truncate, INFO, MUTED};
+use crate::app::App;
+use ratatui::{prelude::*, widgets::Paragraph};
+use unicode_width::UnicodeWidthStr;
+
+/// Uncommitted churn per active session, busiest first
+pub fn draw_churn_view(f: &mut Frame, app: &App, area: Rect, is_focused: bool) {
+    let rows = app.churn_rows();
+    let title = format!("Churn ({} active)", rows.len());
+    let block = styled_block(&title, is_focused);
+
+    if rows.is_empty() {
+        let empty = Paragraph::new("No active sessions")
+            .style(Style::default().fg(MUTED))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    }
+
+    // Name column takes what the three counts leave
+    let width = area.width.saturating_sub(2) as usize;
+    let name_width = width.saturating_sub(22).max(10);
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "{:<name_width$} {:>5} {:>7} {:>7}",
+            "Session", "Files", "+", "-"
+        ),
+        Style::default().fg(MUTED),
+    ))];
+
+    let theme = &app.settings.theme;
+    let (add, remove) = (diff_add(theme), diff_remove(theme));
+    for row in rows {
+        let name = truncate(&row.name, name_width);
+        let pad = name_width.saturating_sub(name.width());
+        let style = if row.files == 0 {
+            Style::default().fg(MUTED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{name}{}", " ".repeat(pad)), style),
+            Span::styled(format!(" {:>5}", row.files), Style::default().fg(INFO)),
+            Span::styled(
+                format!(" {:>7}", format!("+{}", row.additions)),
+                Style::default().fg(add),
+            ),
+            Span::styled(
+                format!(" {:>7}", format!("-{}", row.deletions)),
+                Style::default().fg(remove),
+            ),
+        ]));
+    }
+
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
@@ -0,0 +1,33 @@
+use super::sessions::draw_chat;
+use crate::app::App;
+use ratatui::prelude::*;
+
+/// Left and right halves of the compare view
+pub fn compare_layout(area: Rect) -> [Rect; 2] {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+    [chunks[0], chunks[1]]
+}
+
+/// Two sessions' chats side by side, each with its own scroll
+pub fn draw_compare_view(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(compare) = app.compare.as_ref() else {
+        return;
+    };
+    let active = compare.active;
+    let titles = compare.session_keys().map(|key| {
+        let name = app
+            .sessions
+            .iter()
+            .find(|s| s.key() == key)
+            .map(|s| app.redactor().session_name(s))
+            .unwrap_or_else(|| key.to_string());
+        format!("Compare - {name}")
+    });
+
+    for (i, (rect, title)) in compare_layout(area).into_iter().zip(titles).enumerate() {
+        app.with_compare_pane(i, |app| draw_chat(f, app, rect, i == active, title, false));
+    }
+}
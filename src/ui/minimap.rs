@@ -0,0 +1,79 @@
+//! One-column overview of where a diff's additions and deletions are
+
+use ratatui::prelude::*;
+
+/// Added/removed line counts for one minimap row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bucket {
+    pub additions: usize,
+    pub deletions: usize,
+}
+
+/// Spread `lines` (one per displayed diff row) over `height` buckets
+pub fn minimap_buckets(lines: &[&str], height: usize) -> Vec<Bucket> {
+    let mut buckets = vec![Bucket::default(); height];
+    if height == 0 || lines.is_empty() {
+        return buckets;
+    }
+
+    for (i, line) in lines.iter().enumerate() {
+        let bucket = &mut buckets[i * height / lines.len()];
+        if line.starts_with('+') && !line.starts_with("+++") {
+            bucket.additions += 1;
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            bucket.deletions += 1;
+        }
+    }
+    buckets
+}
+
+/// Minimap rows covered by the viewport `[start, start + visible)` of `total` rows
+pub fn viewport_rows(start: usize, visible: usize, total: usize, height: usize) -> (usize, usize) {
+    if total == 0 || height == 0 {
+        return (0, 0);
+    }
+    let first = start * height / total;
+    let last = ((start + visible).min(total) * height).div_ceil(total);
+    (first, last.max(first + 1).min(height))
+}
+
+/// Scroll offset that centers the viewport on minimap row `row`
+pub fn scroll_for_row(row: u16, height: u16, total: u16, visible: u16) -> u16 {
+    if height == 0 {
+        return 0;
+    }
+    let target = (row as u32 * total as u32 / height as u32) as u16;
+    target
+        .saturating_sub(visible / 2)
+        .min(total.saturating_sub(visible))
+}
+
+pub fn draw_minimap(f: &mut Frame, area: Rect, lines: &[&str], start: usize, visible: usize) {
+    let height = area.height as usize;
+    let (first, last) = viewport_rows(start, visible, lines.len(), height);
+
+    let rows: Vec<Line> = minimap_buckets(lines, height)
+        .into_iter()
+        .enumerate()
+        .map(|(i, b)| {
+            let color = match (b.additions > 0, b.deletions > 0) {
+                (true, true) => Color::Yellow,
+                (true, false) => Color::Green,
+                (false, true) => Color::Red,
+                (false, false) => Color::DarkGray,
+            };
+            let glyph = if b.additions + b.deletions > 0 {
+                "▐"
+            } else {
+                "│"
+            };
+            let mut style = Style::default().fg(color);
+            if (first..last).contains(&i) {
+                style = style.bg(Color::Rgb(60, 60, 80));
+            }
+            Line::from(Span::styled(glyph, style))
+        })
+        .collect();
+
+    f.render_widget(ratatui::widgets::Paragraph::new(rows), area);
+}
@@ -1,11 +1,22 @@
-mod sessions;
+mod agents;
+mod churn;
+pub mod compare;
+pub mod minimap;
 pub mod presets;
+mod sessions;
 
 use crate::app::{App, Focus};
+use crate::config::settings::{
+    LayoutSettings, Panel, PanelHeight, StatusGlyphs, StatusThresholds, ThemeSettings,
+};
+use crate::data::{FileChange, Session, TodoItem};
+use crate::keymap::KEYMAP;
+use crate::text::wrap_words;
 use ratatui::{
     prelude::*,
     widgets::{Block, Borders, Clear, Paragraph},
 };
+use std::rc::Rc;
 
 // Lazygit-style colors
 pub const BORDER_COLOR: Color = Color::Blue;
@@ -17,8 +28,76 @@ pub const WARNING: Color = Color::Yellow;
 pub const ERROR: Color = Color::Red;
 pub const INFO: Color = Color::Cyan;
 
+/// Smallest terminal the normal layout renders into
+pub const MIN_WIDTH: u16 = 60;
+pub const MIN_HEIGHT: u16 = 16;
+
+/// True when `area` is below the layout minimums
+pub fn too_small(area: Rect) -> bool {
+    area.width < MIN_WIDTH || area.height < MIN_HEIGHT
+}
+
+/// One column instead of the sidebar/detail split: forced by config or when
+/// the terminal is narrower than `single_column_below`
+pub fn single_column(layout: &LayoutSettings, width: u16) -> bool {
+    layout.single_column || width < layout.single_column_below
+}
+
+/// Left panel (40%) and detail view (60%) of the two-column layout
+fn main_columns(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(area)
+}
+
+/// Inner size of the detail pane, where the embedded terminal is drawn, on a
+/// `screen`-sized terminal
+pub fn terminal_pane_size(app: &App, screen: Rect) -> (u16, u16) {
+    // Install banner and help bar rows
+    let reserved = if app.claude_available { 1 } else { 2 };
+    let content = Rect {
+        height: screen.height.saturating_sub(reserved),
+        ..screen
+    };
+    let pane = if app.fullscreen || single_column(&app.settings.layout, screen.width) {
+        content
+    } else {
+        main_columns(content)[1]
+    };
+    let inner = Block::default().borders(Borders::ALL).inner(pane);
+    (inner.width.max(1), inner.height.max(1))
+}
+
 pub fn draw(f: &mut Frame, app: &mut App) {
     let size = f.area();
+    // Only a diff drawn this frame may claim minimap clicks
+    app.minimap_area = None;
+
+    if too_small(size) {
+        draw_too_small(f, size);
+        return;
+    }
+
+    if app.dimmed {
+        draw_idle_screen(f, app, size);
+        return;
+    }
+
+    // Persistent setup banner while the claude CLI is missing
+    let size = if app.claude_available {
+        size
+    } else {
+        let banner_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(size);
+        let banner = Paragraph::new(crate::process::which::CLAUDE_INSTALL_HINT)
+            .style(Style::default().fg(Color::Black).bg(WARNING).bold())
+            .alignment(Alignment::Center);
+        f.render_widget(banner, banner_chunks[0]);
+        banner_chunks[1]
+    };
 
     // Layout: main content + footer (help)
     let chunks = Layout::default()
@@ -29,19 +108,28 @@ pub fn draw(f: &mut Frame, app: &mut App) {
         ])
         .split(size);
 
-    if app.fullscreen {
+    if app.compare.is_some() {
+        compare::draw_compare_view(f, app, chunks[0]);
+    } else if app.fullscreen {
         // Fullscreen: only show detail view
         let is_detail_focused = app.focus == Focus::Detail;
         sessions::draw_detail_view(f, app, chunks[0], is_detail_focused);
+    } else if single_column(&app.settings.layout, f.area().width) {
+        // Narrow: Tab switches between the sidebar and the detail view
+        if app.focus == Focus::Detail {
+            sessions::draw_detail_view(f, app, chunks[0], true);
+        } else {
+            draw_left_panel(f, app, chunks[0], true);
+        }
     } else {
         // Main layout: left panel (40%) + detail (60%)
-        let main_chunks = Layout::default()
-            .direction(Direction::Horizontal)
-            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-            .split(chunks[0]);
+        let main_chunks = main_columns(chunks[0]);
 
         // Left side: presets + sessions + files + todos
-        let is_left_focused = matches!(app.focus, Focus::Presets | Focus::Sessions | Focus::Files | Focus::Todos);
+        let is_left_focused = matches!(
+            app.focus,
+            Focus::Presets | Focus::Sessions | Focus::Files | Focus::Todos
+        );
         draw_left_panel(f, app, main_chunks[0], is_left_focused);
 
         // Right side: chat or diff
@@ -52,9 +140,22 @@ pub fn draw(f: &mut Frame, app: &mut App) {
     // Draw help bar
     draw_help_bar(f, app, chunks[1]);
 
+    // Draw note editor if active
+    if app.editing_note {
+        sessions::draw_note_editor(f, app, size);
+    }
+
+    if app.raw_json.is_some() {
+        sessions::draw_raw_json_popup(f, app, size);
+    }
+
+    if app.show_cheatsheet {
+        draw_cheatsheet(f, app, size);
+    }
+
     // Draw help popup if active
     if app.show_help {
-        draw_help_popup(f, size);
+        draw_help_popup(f, app, size);
     }
 }
 
@@ -77,48 +178,57 @@ fn draw_left_panel(f: &mut Frame, app: &mut App, area: Rect, focused: bool) {
 }
 
 fn draw_sessions_files_todos(f: &mut Frame, app: &mut App, area: Rect, focused: bool) {
-    // Get todos for the SELECTED session only
-    let mut session_todos: Vec<(String, String, String)> = app
+    // Get todos for the SELECTED session only, in display order
+    let session_todos: Vec<TodoItem> = app
         .selected_session()
-        .map(|s| {
-            s.todos
-                .iter()
-                .map(|t| (t.id.clone(), t.content.clone(), t.status.clone()))
-                .collect()
-        })
+        .map(|s| sort_todos(&s.todos).into_iter().cloned().collect())
         .unwrap_or_default();
 
-    // Sort by state first (in_progress → pending → completed), then by ID within each state
-    session_todos.sort_by(|a, b| {
-        let status_order = |s: &str| match s {
-            "in_progress" => 0,
-            "pending" => 1,
-            "completed" => 2,
-            _ => 1,
-        };
-        let status_cmp = status_order(&a.2).cmp(&status_order(&b.2));
-        if status_cmp == std::cmp::Ordering::Equal {
-            let id_a: i64 = a.0.parse().unwrap_or(i64::MAX);
-            let id_b: i64 = b.0.parse().unwrap_or(i64::MAX);
-            id_a.cmp(&id_b)
-        } else {
-            status_cmp
+    let has_todos = !session_todos.is_empty();
+    let has_files = !app.current_file_changes.is_empty();
+
+    let panels = app.settings.layout.panels();
+    for (panel, rect) in left_panel_layout(&panels, has_files, has_todos, area) {
+        match panel {
+            Panel::Sessions => {
+                let sessions_focused = focused && app.focus == Focus::Sessions;
+                sessions::draw_session_list(f, app, rect, sessions_focused);
+            }
+            Panel::Files => {
+                let files_focused = focused && app.focus == Focus::Files;
+                draw_files_panel(f, app, rect, files_focused);
+            }
+            Panel::Todos => {
+                let todos_focused = focused && app.focus == Focus::Todos;
+                draw_todos_panel(f, app, &session_todos, rect, todos_focused);
+            }
         }
-    });
+    }
+}
 
-    // Convert to (content, status) for display
-    let session_todos_display: Vec<(String, String)> = session_todos
-        .into_iter()
-        .map(|(_, content, status)| (content, status))
+/// Areas for the configured left-column panels; files and todos are left out
+/// while they have nothing to show
+pub fn left_panel_layout(
+    panels: &[(Panel, PanelHeight)],
+    has_files: bool,
+    has_todos: bool,
+    area: Rect,
+) -> Vec<(Panel, Rect)> {
+    let shown: Vec<(Panel, PanelHeight)> = panels
+        .iter()
+        .copied()
+        .filter(|(panel, _)| match panel {
+            Panel::Sessions => true,
+            Panel::Files => has_files,
+            Panel::Todos => has_todos,
+        })
         .collect();
-
-    let has_todos = !session_todos_display.is_empty();
-    let has_files = !app.current_file_changes.is_empty();
-
-    // Calculate layout: equal height for all visible panels
-    let panel_count = 1 + has_todos as usize + has_files as usize;
-    let constraints: Vec<Constraint> = (0..panel_count)
-        .map(|_| Constraint::Ratio(1, panel_count as u32))
+    let constraints: Vec<Constraint> = shown
+        .iter()
+        .map(|(_, height)| match *height {
+            PanelHeight::Fixed(rows) => Constraint::Length(rows),
+            PanelHeight::Ratio(weight) => Constraint::Fill(weight),
+        })
         .collect();
 
     let chunks = Layout::default()
@@ -126,37 +236,17 @@ fn draw_sessions_files_todos(f: &mut Frame, app: &mut App, area: Rect, focused:
         .constraints(constraints)
         .split(area);
 
-    let mut chunk_idx = 0;
-
-    // Sessions list (always shown)
-    let sessions_focused = focused && app.focus == Focus::Sessions;
-    sessions::draw_session_list(f, app, chunks[chunk_idx], sessions_focused);
-    chunk_idx += 1;
-
-    // Files panel (middle)
-    if has_files {
-        let files_focused = focused && app.focus == Focus::Files;
-        draw_files_panel(f, app, chunks[chunk_idx], files_focused);
-        chunk_idx += 1;
-    }
-
-    // Todos panel (bottom)
-    if has_todos {
-        let todos_focused = focused && app.focus == Focus::Todos;
-        draw_todos_panel(
-            f,
-            app,
-            &session_todos_display,
-            chunks[chunk_idx],
-            todos_focused,
-        );
-    }
+    shown
+        .into_iter()
+        .map(|(panel, _)| panel)
+        .zip(chunks.iter().copied())
+        .collect()
 }
 
 fn draw_todos_panel(
     f: &mut Frame,
     app: &mut App,
-    todos: &[(String, String)],
+    todos: &[TodoItem],
     area: Rect,
     is_focused: bool,
 ) {
@@ -167,25 +257,7 @@ fn draw_todos_panel(
     f.render_widget(block, area);
 
     // Build lines for ALL todos (scroll to see overflow)
-    let lines: Vec<Line> = todos
-        .iter()
-        .map(|(content, status)| {
-            let (icon, style) = match status.as_str() {
-                "in_progress" => ("■", Style::default().fg(Color::Rgb(255, 180, 180))),
-                "completed" => ("✓", Style::default().fg(MUTED)),
-                _ => ("□", Style::default().fg(Color::Gray)),
-            };
-
-            Line::from(vec![
-                Span::styled(icon, style),
-                Span::raw(" "),
-                Span::styled(
-                    truncate(content, inner.width.saturating_sub(3) as usize),
-                    style,
-                ),
-            ])
-        })
-        .collect();
+    let lines = todo_panel_lines(todos, inner.width, app.todos_wrap, app.hide_done_todos);
 
     // Calculate scroll
     let total_lines = lines.len() as u16;
@@ -203,25 +275,122 @@ fn draw_todos_panel(
     f.render_widget(paragraph, inner);
 }
 
+/// Todo rows for a panel `width` wide: one truncated line each, or word-wrapped
+/// with continuation lines indented under the text
+pub fn todo_lines(todos: &[TodoItem], width: u16, wrap: bool) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for todo in todos {
+        let (icon, style) = todo_style(&todo.status);
+        if !wrap {
+            lines.push(Line::from(vec![
+                Span::styled(icon, style),
+                Span::raw(" "),
+                Span::styled(
+                    truncate(&todo.content, width.saturating_sub(3) as usize),
+                    style,
+                ),
+            ]));
+            continue;
+        }
+        let text_width = width.saturating_sub(2) as usize;
+        for (i, chunk) in wrap_words(&todo.content, text_width)
+            .into_iter()
+            .enumerate()
+        {
+            let lead = if i == 0 {
+                Span::styled(icon, style)
+            } else {
+                Span::raw(" ")
+            };
+            lines.push(Line::from(vec![
+                lead,
+                Span::raw(" "),
+                Span::styled(chunk, style),
+            ]));
+        }
+    }
+    lines
+}
+
+/// Todos panel rows; with `hide_done`, completed todos fold into one summary row
+pub fn todo_panel_lines(
+    todos: &[TodoItem],
+    width: u16,
+    wrap: bool,
+    hide_done: bool,
+) -> Vec<Line<'static>> {
+    if !hide_done {
+        return todo_lines(todos, width, wrap);
+    }
+    let (done, open): (Vec<TodoItem>, Vec<TodoItem>) =
+        todos.iter().cloned().partition(|t| t.status == "completed");
+    let mut lines = todo_lines(&open, width, wrap);
+    if !done.is_empty() {
+        lines.push(done_summary_line(done.len()));
+    }
+    lines
+}
+
+/// Stand-in row for `done` hidden completed todos
+pub fn done_summary_line(done: usize) -> Line<'static> {
+    Line::from(Span::styled(
+        format!("(+{done} done)"),
+        Style::default().fg(MUTED),
+    ))
+}
+
+/// `path` relative to `base` when it lies inside it, otherwise unchanged
+pub fn relative_path<'a>(path: &'a str, base: &str) -> &'a str {
+    std::path::Path::new(path)
+        .strip_prefix(base)
+        .ok()
+        .and_then(|rel| rel.to_str())
+        .filter(|rel| !rel.is_empty())
+        .unwrap_or(path)
+}
+
+/// Total additions and deletions across the given files
+pub fn churn_totals(files: &[&FileChange]) -> (u32, u32) {
+    files.iter().fold((0, 0), |(add, del), f| {
+        (add + f.additions, del + f.deletions)
+    })
+}
+
 fn draw_files_panel(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
     let filtered = app.filtered_files();
     let total = app.current_file_changes.len();
     let mode_indicator = if app.file_tree_mode { "tree" } else { "flat" };
+    let (additions, deletions) = churn_totals(&filtered);
     let title = if app.file_filter.is_empty() {
-        format!("Files ({}) [{}]", total, mode_indicator)
+        format!(
+            "Files ({}) +{} -{} [{}]",
+            total, additions, deletions, mode_indicator
+        )
     } else {
         format!(
-            "Files ({}/{}) [{}] [{}]",
+            "Files ({}/{}) +{} -{} [{}] [{}]",
             filtered.len(),
             total,
+            additions,
+            deletions,
             app.file_filter,
             mode_indicator
         )
     };
     let block = styled_block(&title, is_focused);
 
-    // If filter is active, show input
-    if app.file_filter_active {
+    // If filter or go-to-file is active, show input
+    let input = if app.file_filter_active {
+        Some((" Filter (Enter to apply, Esc to cancel) ", &app.file_filter))
+    } else if app.file_jump_active {
+        Some((
+            " Go to file (Enter to open, Esc to cancel) ",
+            &app.file_jump_query,
+        ))
+    } else {
+        None
+    };
+    if let Some((input_title, text)) = input {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
@@ -230,16 +399,16 @@ fn draw_files_panel(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool)
         let input_block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(Color::Yellow))
-            .title(" Filter (Enter to apply, Esc to cancel) ")
+            .title(input_title)
             .title_style(Style::default().fg(Color::Yellow).bold());
 
-        let input = Paragraph::new(app.file_filter.as_str())
+        let input = Paragraph::new(text.as_str())
             .block(input_block)
             .style(Style::default().fg(Color::White));
         f.render_widget(input, chunks[0]);
 
         // Position cursor
-        let cursor_x = chunks[0].x + 1 + app.file_filter.chars().count() as u16;
+        let cursor_x = chunks[0].x + 1 + text.chars().count() as u16;
         let cursor_y = chunks[0].y + 1;
         if cursor_x < chunks[0].x + chunks[0].width - 1 {
             f.set_cursor_position(ratatui::layout::Position::new(cursor_x, cursor_y));
@@ -277,6 +446,12 @@ fn draw_files_list_inner(
     use crate::data::FileStatus;
 
     let mut lines: Vec<Line> = Vec::new();
+    let project_dir = app
+        .settings
+        .display
+        .relative_paths
+        .then(|| app.selected_session().map(|s| s.project_dir()))
+        .flatten();
 
     if app.file_tree_mode {
         // Tree view: group files by directory
@@ -287,18 +462,27 @@ fn draw_files_list_inner(
         let mut last_dir: Option<String> = None;
 
         for (idx, file) in &sorted_files {
-            let dir = std::path::Path::new(&file.path)
+            let path = match &project_dir {
+                Some(base) => relative_path(&file.path, base),
+                None => &file.path,
+            };
+            let dir = std::path::Path::new(path)
                 .parent()
                 .and_then(|p| p.to_str())
                 .unwrap_or("")
                 .to_string();
 
+            // Files at the project root sit outside any directory group
+            if dir.is_empty() {
+                last_dir = None;
+            }
+
             // Show directory header if changed
             if last_dir.as_ref() != Some(&dir) && !dir.is_empty() {
                 lines.push(Line::from(vec![
                     Span::styled("  ", Style::default()),
                     Span::styled(
-                        format!("{}/ ", dir),
+                        format!("{}/ ", app.redactor().path(&dir)),
                         Style::default().fg(Color::Blue).bold(),
                     ),
                 ]));
@@ -364,12 +548,16 @@ fn draw_files_list_inner(
             };
 
             let max_name = inner.width.saturating_sub(16) as usize;
+            let name = match &project_dir {
+                Some(base) => relative_path(&file.path, base),
+                None => &file.filename,
+            };
 
             let mut spans = vec![
                 Span::styled(icon, Style::default().fg(status_color).bold()),
                 Span::raw(" "),
                 Span::styled(
-                    truncate(&file.filename, max_name),
+                    truncate(name, max_name),
                     if is_selected {
                         Style::default().fg(Color::White).bold()
                     } else {
@@ -404,7 +592,267 @@ fn draw_files_list_inner(
     f.render_widget(paragraph, inner);
 }
 
+/// Session status indicator: configured glyph and color
+pub fn status_glyph<'a>(glyphs: &'a StatusGlyphs, status: &str) -> (&'a str, Color) {
+    let color = match status {
+        "working" => Color::Cyan,      // Cyan spinner = actively processing (<10s)
+        "active" => Color::Green,      // Green play = recent activity (<2 min)
+        "idle" => Color::Yellow,       // Yellow dot = waiting (2-30 min)
+        "inactive" => Color::DarkGray, // Gray circle = old (>30 min)
+        "waiting" => Color::Magenta,   // Magenta = waiting for user (hook or infer_waiting)
+        _ => Color::DarkGray,
+    };
+    (glyphs.glyph(status), color)
+}
+
+/// Parse a theme color, falling back when the name is not recognised
+fn theme_color(value: &str, fallback: Color) -> Color {
+    value.parse().unwrap_or(fallback)
+}
+
+/// Color of added diff lines
+pub fn diff_add(theme: &ThemeSettings) -> Color {
+    theme_color(&theme.diff_add, Color::Green)
+}
+
+/// Color of removed diff lines
+pub fn diff_remove(theme: &ThemeSettings) -> Color {
+    theme_color(&theme.diff_remove, Color::Red)
+}
+
+/// Color of `@@` hunk headers
+pub fn diff_hunk(theme: &ThemeSettings) -> Color {
+    theme_color(&theme.diff_hunk, Color::Cyan)
+}
+
+/// Color of `diff`/`index` file headers
+pub fn diff_header(theme: &ThemeSettings) -> Color {
+    theme_color(&theme.diff_header, Color::Yellow)
+}
+
+/// Base style of a diff line by its prefix (plain gray for raw file content)
+pub fn diff_line_style(line: &str, raw: bool, theme: &ThemeSettings) -> Style {
+    let color = if raw {
+        Color::Gray
+    } else if line.starts_with('+') && !line.starts_with("+++") {
+        diff_add(theme)
+    } else if line.starts_with('-') && !line.starts_with("---") {
+        diff_remove(theme)
+    } else if line.starts_with("@@") {
+        diff_hunk(theme)
+    } else if line.starts_with("diff") || line.starts_with("index") {
+        diff_header(theme)
+    } else {
+        Color::Gray
+    };
+    Style::default().fg(color)
+}
+
+/// Order of statuses in the footer summary and legend, most urgent first
+const SUMMARY_ORDER: [&str; 5] = ["waiting", "working", "active", "idle", "inactive"];
+
+/// Help-popup legend: each status with what it means under `thresholds`
+pub fn status_legend(thresholds: &StatusThresholds) -> Vec<(&'static str, String)> {
+    SUMMARY_ORDER
+        .iter()
+        .map(|&status| {
+            let meaning = match status {
+                "waiting" => "needs your input".to_string(),
+                "working" => format!("output <{}", short_duration(thresholds.working_secs)),
+                "active" => format!("output <{}", short_duration(thresholds.active_secs)),
+                "idle" => format!("output <{}", short_duration(thresholds.idle_secs)),
+                _ => format!("older than {}", short_duration(thresholds.idle_secs)),
+            };
+            (status, meaning)
+        })
+        .collect()
+}
+
+/// Compact duration: 45s, 2m, 1h
+pub fn short_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{secs}s")
+    } else if secs < 3600 {
+        format!("{}m", secs / 60)
+    } else {
+        format!("{}h", secs / 3600)
+    }
+}
+
+/// Session counts per status in SUMMARY_ORDER, skipping empty ones;
+/// unknown statuses count as inactive (they share its indicator)
+pub fn status_counts(sessions: &[Session]) -> Vec<(&'static str, usize)> {
+    SUMMARY_ORDER
+        .iter()
+        .map(|&status| {
+            let count = sessions
+                .iter()
+                .filter(|s| match s.status.as_str() {
+                    known if SUMMARY_ORDER.contains(&known) => known == status,
+                    _ => status == "inactive",
+                })
+                .count();
+            (status, count)
+        })
+        .filter(|&(_, count)| count > 0)
+        .collect()
+}
+
+/// Compact `◆1 ⟳2 ▶3` summary for the footer
+fn status_summary(sessions: &[Session], glyphs: &StatusGlyphs) -> Line<'static> {
+    let mut spans = Vec::new();
+    for (status, count) in status_counts(sessions) {
+        let (glyph, color) = status_glyph(glyphs, status);
+        spans.push(Span::styled(
+            format!("{glyph}{count} "),
+            Style::default().fg(color),
+        ));
+    }
+    Line::from(spans)
+}
+
+/// Footer hints for the focused panel, leaving out actions that would do nothing
+pub fn help_hints(app: &App) -> Vec<&'static str> {
+    let spawn = !app.settings.safe_mode;
+    let session = app.selected_session().is_some();
+    let files = !app.current_file_changes.is_empty();
+    let todos = app.selected_session_todos_count() > 0;
+    let diff = app.diff_mode;
+
+    let hints = match (app.focus, app.fullscreen) {
+        _ if app.editing_note => vec![
+            ("Enter: newline", true),
+            ("^s: save", true),
+            ("Esc: cancel", true),
+        ],
+        _ if app.compare.is_some() => vec![
+            ("j/k: scroll", true),
+            ("^u/d: page", true),
+            ("g/G: top/bottom", true),
+            ("Tab/h/l: other side", true),
+            ("Esc/x: close", true),
+        ],
+        (_, true) => vec![
+            ("j/k: scroll", true),
+            ("h/l: hunks", diff),
+            ("^u/d: page", true),
+            ("^q: back", true),
+            ("g/G: top/bottom", true),
+            ("v: diff/file", diff),
+            ("e: edit", diff && spawn),
+            ("q: quit", true),
+        ],
+        (Focus::Presets, _) => vec![
+            ("j/k: nav", true),
+            ("l: sessions", true),
+            ("Enter: apply preset", !app.presets.is_empty() && spawn),
+            ("X: clean dead", true),
+            ("?: help", true),
+            ("q: quit", true),
+        ],
+        (Focus::Sessions, _) => vec![
+            ("j/k: nav", true),
+            ("h/l: presets/files", files),
+            ("h/l: presets/todos", !files && todos),
+            ("h: presets", !files && !todos),
+            ("Enter: view", session),
+            ("r: rename", session),
+            ("N: note", session),
+            ("y: copy resume", session),
+            ("t/#: tag/filter", session),
+            ("f: today", true),
+            ("x: compare", session),
+            ("a/c: agents/churn", session),
+            ("o/A: open (+dirs)", session && spawn),
+            ("n: new", spawn),
+            ("?: help", true),
+            ("q: quit", true),
+        ],
+        (Focus::Files, _) => vec![
+            ("j/k: select", true),
+            ("]/[: next/prev change", true),
+            ("^r: refresh", true),
+            ("/: go to", true),
+            ("f: filter", true),
+            ("t: tree/flat", true),
+            ("P: rel paths", true),
+            ("s: recent first", true),
+            ("v: diff/file", true),
+            ("V: difftool", files && spawn),
+            ("O: open all", files && spawn),
+            ("y/Y: yank one/all", true),
+            ("Enter: view", true),
+            ("Esc: back", true),
+            ("q: quit", true),
+        ],
+        (Focus::Todos, _) => vec![
+            ("j/k: scroll", true),
+            ("w: wrap", true),
+            ("c: hide done", true),
+            ("h: files", files),
+            ("h: sessions", !files),
+            ("Enter: view", true),
+            ("Esc: back", true),
+            ("?: help", true),
+            ("q: quit", true),
+        ],
+        (Focus::Detail, _) if diff => vec![
+            ("j/k: scroll", true),
+            ("h/l: hunks", true),
+            ("^u/d: page", true),
+            ("^q: back", true),
+            ("v: diff/file", true),
+            ("e: edit", spawn),
+            ("V: difftool", spawn),
+            ("q: quit", true),
+        ],
+        (Focus::Detail, _) => vec![
+            ("j/k: scroll", true),
+            ("{/}: prompts", true),
+            ("^u/d: page", true),
+            ("^q: back", true),
+            ("g/G: top/bottom", true),
+            ("q: quit", true),
+        ],
+    };
+    hints
+        .into_iter()
+        .filter(|&(_, available)| available)
+        .map(|(hint, _)| hint)
+        .collect()
+}
+
 fn draw_help_bar(f: &mut Frame, app: &App, area: Rect) {
+    // Status summary and clock in the right corner, keybindings/status in the rest
+    let mut corner = Vec::new();
+    if app.show_status_summary && !app.sessions.is_empty() {
+        corner.extend(status_summary(&app.sessions, &app.settings.status_glyphs).spans);
+    }
+    if app.settings.display.show_clock {
+        corner.push(Span::styled(
+            format!("{} ", app.refresh_indicator()),
+            Style::default().fg(SUCCESS),
+        ));
+        corner.push(Span::styled(
+            chrono::Local::now().format("%H:%M:%S ").to_string(),
+            Style::default().fg(MUTED),
+        ));
+    }
+    let area = if corner.is_empty() {
+        area
+    } else {
+        let corner = Line::from(corner);
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(corner.width() as u16),
+            ])
+            .split(area);
+        f.render_widget(Paragraph::new(corner), chunks[1]);
+        chunks[0]
+    };
+
     if let Some(ref msg) = app.status_message {
         let style = if app.status_is_error {
             Style::default().fg(ERROR).bold()
@@ -418,25 +866,7 @@ fn draw_help_bar(f: &mut Frame, app: &App, area: Rect) {
         return;
     }
 
-    let help_text = match (app.focus, app.fullscreen) {
-        (_, true) => {
-            "j/k: scroll │ h/l: hunks │ ^u/d: page │ ^q: back │ g/G: top/bottom │ e: edit │ q: quit"
-        }
-        (Focus::Presets, _) => {
-            "j/k: nav │ l: sessions │ Enter: apply preset │ ?: help │ q: quit"
-        }
-        (Focus::Sessions, _) => {
-            "j/k: nav │ h/l: presets/files │ Enter: view │ r: rename │ o: open │ n: new │ ?: help │ q: quit"
-        }
-        (Focus::Files, _) => {
-            "j/k: select │ f: filter │ t: tree/flat │ Enter: view │ Esc: back │ q: quit"
-        }
-        (Focus::Todos, _) => "j/k: scroll │ h: files │ Enter: view │ Esc: back │ ?: help │ q: quit",
-        (Focus::Detail, _) if app.diff_mode => {
-            "j/k: scroll │ h/l: hunks │ ^u/d: page │ ^q: back │ e: edit │ q: quit"
-        }
-        (Focus::Detail, _) => "j/k: scroll │ ^u/d: page │ ^q: back │ g/G: top/bottom │ q: quit",
-    };
+    let help_text = help_hints(app).join(" │ ");
 
     let help = Paragraph::new(help_text)
         .style(Style::default().fg(Color::DarkGray))
@@ -445,7 +875,7 @@ fn draw_help_bar(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(help, area);
 }
 
-pub fn styled_block(title: &str, is_active: bool) -> Block {
+pub fn styled_block(title: &str, is_active: bool) -> Block<'_> {
     Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(if is_active {
@@ -467,33 +897,103 @@ pub fn styled_block(title: &str, is_active: bool) -> Block {
 
 pub fn relative_time(dt: &Option<chrono::DateTime<chrono::Utc>>) -> String {
     match dt {
-        Some(dt) => {
-            let now = chrono::Utc::now();
-            let duration = now.signed_duration_since(*dt);
-
-            if duration.num_seconds() < 60 {
-                "<1m ago".to_string()
-            } else if duration.num_minutes() < 60 {
-                format!("{}m ago", duration.num_minutes())
-            } else if duration.num_hours() < 24 {
-                format!("{}h ago", duration.num_hours())
-            } else {
-                format!("{}d ago", duration.num_days())
-            }
-        }
+        Some(dt) => format!("{} ago", elapsed_since(*dt, chrono::Utc::now())),
         None => "—".to_string(),
     }
 }
 
-pub fn truncate(s: &str, max_len: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_len {
-        s.to_string()
-    } else if max_len > 3 {
-        let truncated: String = s.chars().take(max_len - 3).collect();
-        format!("{}...", truncated)
+/// Coarse time from `dt` to `now`: `<1m`, `12m`, `3h` or `2d`
+pub fn elapsed_since(
+    dt: chrono::DateTime<chrono::Utc>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> String {
+    let duration = now.signed_duration_since(dt);
+    if duration.num_seconds() < 60 {
+        "<1m".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{}m", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        format!("{}h", duration.num_hours())
     } else {
-        s.chars().take(max_len).collect()
+        format!("{}d", duration.num_days())
+    }
+}
+
+/// `idle for 12m` since the last transcript activity, for the session header
+pub fn idle_for(
+    last_activity: Option<chrono::DateTime<chrono::Utc>>,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<String> {
+    last_activity.map(|at| format!("idle for {}", elapsed_since(at, now)))
+}
+
+/// Where the chat viewport sits: `[bottom]` (newest), `[top]` (oldest) or
+/// `[↑ N lines]` above the newest; None when everything fits
+pub fn scroll_indicator(scroll: u16, max: u16) -> Option<String> {
+    if max == 0 {
+        None
+    } else if scroll >= max {
+        Some("[bottom]".to_string())
+    } else if scroll == 0 {
+        Some("[top]".to_string())
+    } else {
+        let lines = max - scroll;
+        let unit = if lines == 1 { "line" } else { "lines" };
+        Some(format!("[↑ {lines} {unit}]"))
+    }
+}
+
+/// Shorten `s` to at most `max_width` display columns, ending in `…` when cut
+pub fn truncate(s: &str, max_width: usize) -> String {
+    use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+
+    // One column is kept for the ellipsis
+    let mut truncated = String::new();
+    let mut used = 0;
+    for c in s.chars() {
+        let w = c.width().unwrap_or(0);
+        if used + w >= max_width {
+            break;
+        }
+        truncated.push(c);
+        used += w;
+    }
+    truncated.push('…');
+    truncated
+}
+
+/// Sort todos for display: in_progress → pending → completed, then by numeric ID
+pub fn sort_todos(todos: &[TodoItem]) -> Vec<&TodoItem> {
+    let status_order = |s: &str| match s {
+        "in_progress" => 0,
+        "pending" => 1,
+        "completed" => 2,
+        _ => 1,
+    };
+
+    let mut sorted: Vec<&TodoItem> = todos.iter().collect();
+    sorted.sort_by_key(|t| {
+        (
+            status_order(&t.status),
+            t.id.parse::<i64>().unwrap_or(i64::MAX),
+        )
+    });
+    sorted
+}
+
+/// Icon and style for a todo status, shared by the todos panel and preview
+pub fn todo_style(status: &str) -> (&'static str, Style) {
+    match status {
+        "in_progress" => ("▶", Style::default().fg(Color::Cyan)),
+        "completed" => ("✓", Style::default().fg(MUTED)),
+        _ => ("○", Style::default().fg(Color::Gray)),
     }
 }
 
@@ -508,106 +1008,170 @@ pub fn status_style(status: &str) -> Style {
     }
 }
 
-fn draw_help_popup(f: &mut Frame, area: Rect) {
-    let popup_width = 36.min(area.width.saturating_sub(4));
-    let popup_height = 24.min(area.height.saturating_sub(4));
+/// Minimal status screen shown after `dim_after_secs` without input
+fn draw_idle_screen(f: &mut Frame, app: &App, area: Rect) {
+    let mut summary = status_summary(&app.sessions, &app.settings.status_glyphs);
+    summary.spans.insert(
+        0,
+        Span::styled(
+            format!("{} sessions  ", app.sessions.len()),
+            Style::default().fg(MUTED),
+        ),
+    );
+    let lines = vec![
+        summary,
+        Line::from(""),
+        Line::from(Span::styled(
+            "press any key",
+            Style::default().fg(Color::Rgb(60, 60, 60)),
+        )),
+    ];
+    let y = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+    let idle = Paragraph::new(lines).alignment(Alignment::Center);
+    f.render_widget(
+        idle,
+        Rect {
+            y,
+            height: 3.min(area.height),
+            ..area
+        },
+    );
+}
+
+/// Replaces the whole UI when the terminal is below MIN_WIDTH x MIN_HEIGHT
+fn draw_too_small(f: &mut Frame, area: Rect) {
+    let text = format!(
+        "Terminal too small ({}x{})\nneed at least {MIN_WIDTH}x{MIN_HEIGHT}",
+        area.width, area.height
+    );
+    let lines = text.lines().count() as u16;
+    let y = area.y + area.height.saturating_sub(lines) / 2;
+    let message = Paragraph::new(text)
+        .style(Style::default().fg(WARNING))
+        .alignment(Alignment::Center);
+    f.render_widget(
+        message,
+        Rect {
+            y,
+            height: area.height.min(lines),
+            ..area
+        },
+    );
+}
+
+fn draw_help_popup(f: &mut Frame, app: &mut App, area: Rect) {
+    let mut help_content = Vec::new();
+    for (section, bindings) in KEYMAP {
+        help_content.push(Line::from(Span::styled(
+            format!("─ {section}"),
+            Style::default().fg(INFO).bold(),
+        )));
+        for binding in bindings.iter() {
+            help_content.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>5} ", binding.keys),
+                    Style::default().fg(Color::Yellow),
+                ),
+                Span::styled(binding.action, Style::default().fg(Color::Gray)),
+            ]));
+        }
+        help_content.push(Line::from(""));
+    }
+    help_content.push(Line::from(Span::styled(
+        "─ Status",
+        Style::default().fg(INFO).bold(),
+    )));
+    let settings = &app.settings;
+    // Same glyphs and thresholds as the session list
+    for (status, meaning) in status_legend(&settings.status) {
+        let (glyph, color) = status_glyph(&settings.status_glyphs, status);
+        help_content.push(Line::from(vec![
+            Span::styled(format!("    {glyph} "), Style::default().fg(color)),
+            Span::styled(format!("{status:<9}"), Style::default().fg(Color::Gray)),
+            Span::styled(meaning, Style::default().fg(MUTED)),
+        ]));
+    }
+
+    // Sized to the content, clamped to the frame; scrolls when it doesn't fit
+    let content_width = help_content.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let popup_width = (content_width + 2).min(area.width.saturating_sub(4));
+    let popup_height = (help_content.len() as u16 + 2).min(area.height.saturating_sub(2));
     let popup_area = Rect {
-        x: (area.width.saturating_sub(popup_width)) / 2,
-        y: (area.height.saturating_sub(popup_height)) / 2,
+        x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_height)) / 2,
         width: popup_width,
         height: popup_height,
     };
+    let max = (help_content.len() as u16).saturating_sub(popup_height.saturating_sub(2));
+    app.help_scroll = app.help_scroll.min(max);
 
     f.render_widget(Clear, popup_area);
 
-    let help_content = vec![
-        Line::from(Span::styled(
-            "─ Navigation",
-            Style::default().fg(INFO).bold(),
-        )),
-        Line::from(vec![
-            Span::styled("  j/k ", Style::default().fg(Color::Yellow)),
-            Span::styled("Move down/up", Style::default().fg(Color::Gray)),
-        ]),
+    let title = if max > 0 {
+        " Help (j/k scroll) "
+    } else {
+        " Help "
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(BORDER_COLOR))
+        .title(title)
+        .title_style(Style::default().fg(Color::White).bold());
+
+    let help = Paragraph::new(help_content)
+        .block(block)
+        .alignment(Alignment::Left)
+        .scroll((app.help_scroll, 0));
+
+    f.render_widget(help, popup_area);
+}
+
+/// Keys for the focused panel with a search line on top
+fn draw_cheatsheet(f: &mut Frame, app: &App, area: Rect) {
+    let bindings = app.cheatsheet_bindings();
+    let popup_width = 40.min(area.width.saturating_sub(4));
+    let popup_height = (bindings.len() as u16 + 4).min(area.height.saturating_sub(2));
+    let popup_area = Rect {
+        x: area.x + (area.width.saturating_sub(popup_width)) / 2,
+        y: area.y + (area.height.saturating_sub(popup_height)) / 2,
+        width: popup_width,
+        height: popup_height,
+    };
+
+    f.render_widget(Clear, popup_area);
+
+    let mut lines = vec![
         Line::from(vec![
-            Span::styled("  h/l ", Style::default().fg(Color::Yellow)),
+            Span::styled("/ ", Style::default().fg(MUTED)),
             Span::styled(
-                "Switch panels / Jump hunks",
-                Style::default().fg(Color::Gray),
+                format!("{}_", app.cheatsheet_query),
+                Style::default().fg(Color::White),
             ),
         ]),
-        Line::from(vec![
-            Span::styled("  g/G ", Style::default().fg(Color::Yellow)),
-            Span::styled("Top/bottom", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled(" ^u/d ", Style::default().fg(Color::Yellow)),
-            Span::styled("Page up/down", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Tab ", Style::default().fg(Color::Yellow)),
-            Span::styled("Toggle focus", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("Enter ", Style::default().fg(Color::Yellow)),
-            Span::styled("Fullscreen", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("  Esc ", Style::default().fg(Color::Yellow)),
-            Span::styled("Back", Style::default().fg(Color::Gray)),
-        ]),
         Line::from(""),
-        Line::from(Span::styled("─ Sessions", Style::default().fg(INFO).bold())),
-        Line::from(vec![
-            Span::styled("    o ", Style::default().fg(Color::Yellow)),
-            Span::styled("Open in terminal", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    n ", Style::default().fg(Color::Yellow)),
-            Span::styled("New session", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    r ", Style::default().fg(Color::Yellow)),
-            Span::styled("Rename", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(""),
-        Line::from(Span::styled("─ Files", Style::default().fg(INFO).bold())),
-        Line::from(vec![
-            Span::styled("    e ", Style::default().fg(Color::Yellow)),
-            Span::styled("Edit in $EDITOR", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    f ", Style::default().fg(Color::Yellow)),
-            Span::styled("Filter", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    t ", Style::default().fg(Color::Yellow)),
-            Span::styled("Tree/flat", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    y ", Style::default().fg(Color::Yellow)),
-            Span::styled("Yank path", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("    ? ", Style::default().fg(Color::Yellow)),
-            Span::styled("Help", Style::default().fg(Color::Gray)),
-        ]),
-        Line::from(vec![
-            Span::styled("    q ", Style::default().fg(Color::Yellow)),
-            Span::styled("Quit", Style::default().fg(Color::Gray)),
-        ]),
     ];
+    if bindings.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No matching keys",
+            Style::default().fg(MUTED),
+        )));
+    }
+    for binding in bindings {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{:>6} ", binding.keys),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(binding.action, Style::default().fg(Color::Gray)),
+        ]));
+    }
 
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(BORDER_COLOR))
-        .title(" Help ")
+        .title(format!(" {:?} keys ", app.focus))
+        .title_bottom(Line::from(" ?: all keys │ Esc: close ").right_aligned())
         .title_style(Style::default().fg(Color::White).bold());
 
-    let help = Paragraph::new(help_content)
-        .block(block)
-        .alignment(Alignment::Left);
-
-    f.render_widget(help, popup_area);
+    f.render_widget(Paragraph::new(lines).block(block), popup_area);
 }
@@ -14,38 +14,49 @@ pub fn draw_presets_panel(f: &mut Frame, app: &App, area: Rect, focused: bool) {
 
     let block = Block::default()
         .title(" Presets ")
+        .title(
+            Line::from(format!(" {} ", app.process_summary()))
+                .right_aligned()
+                .style(Style::default().fg(MUTED)),
+        )
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
     if app.presets.is_empty() {
-        let empty_msg = Paragraph::new("No presets configured.\nEdit ~/.config/lazychat/presets.toml")
-            .style(Style::default().fg(MUTED))
-            .block(block);
+        let empty_msg =
+            Paragraph::new("No presets configured.\nEdit ~/.config/lazychat/presets.toml")
+                .style(Style::default().fg(MUTED))
+                .block(block);
         f.render_widget(empty_msg, area);
         return;
     }
 
     // Build list items
-    let items: Vec<ListItem> = app.presets.iter().enumerate().map(|(i, preset)| {
-        let instances = preset.instances;
-        let shortcut = preset.shortcut.as_deref().unwrap_or("");
-
-        let line = if shortcut.is_empty() {
-            format!("{} ({})", preset.name, instances)
-        } else {
-            format!("{} [{}] ({})", preset.name, shortcut, instances)
-        };
-
-        let style = if i == app.selected_preset_idx && focused {
-            Style::default().bg(SELECTED_BG).fg(Color::White)
-        } else if i == app.selected_preset_idx {
-            Style::default().fg(Color::White)
-        } else {
-            Style::default().fg(MUTED)
-        };
-
-        ListItem::new(line).style(style)
-    }).collect();
+    let items: Vec<ListItem> = app
+        .presets
+        .iter()
+        .enumerate()
+        .map(|(i, preset)| {
+            let instances = preset.instances;
+            let shortcut = preset.shortcut.as_deref().unwrap_or("");
+
+            let line = if shortcut.is_empty() {
+                format!("{} ({})", preset.name, instances)
+            } else {
+                format!("{} [{}] ({})", preset.name, shortcut, instances)
+            };
+
+            let style = if i == app.selected_preset_idx && focused {
+                Style::default().bg(SELECTED_BG).fg(Color::White)
+            } else if i == app.selected_preset_idx {
+                Style::default().fg(Color::White)
+            } else {
+                Style::default().fg(MUTED)
+            };
+
+            ListItem::new(line).style(style)
+        })
+        .collect();
 
     let list = List::new(items)
         .block(block)
@@ -71,7 +82,7 @@ pub fn draw_preset_detail(f: &mut Frame, app: &App, area: Rect) {
     let mut lines = vec![
         Line::from(vec![
             Span::styled("Directory: ", Style::default().fg(MUTED)),
-            Span::raw(&preset.cwd),
+            Span::raw(app.redactor().path(&preset.cwd)),
         ]),
         Line::from(vec![
             Span::styled("Instances: ", Style::default().fg(MUTED)),
@@ -87,7 +98,10 @@ pub fn draw_preset_detail(f: &mut Frame, app: &App, area: Rect) {
     }
 
     if !preset.add_dirs.is_empty() {
-        lines.push(Line::from(Span::styled("Add dirs:", Style::default().fg(MUTED))));
+        lines.push(Line::from(Span::styled(
+            "Add dirs:",
+            Style::default().fg(MUTED),
+        )));
         for dir in &preset.add_dirs {
             lines.push(Line::from(format!("  {}", dir)));
         }
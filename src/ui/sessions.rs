@@ -1,51 +1,90 @@
-use super::{relative_time, styled_block, truncate, INFO, MUTED, SELECTED_BG, SUCCESS, WARNING};
-use crate::app::App;
+use super::minimap;
+use super::{
+    idle_for, relative_time, scroll_indicator, short_duration, sort_todos, styled_block,
+    todo_style, truncate, ERROR, INFO, MUTED, SELECTED_BG, SUCCESS, WARNING,
+};
+use crate::app::{message_blocks, App, ChatFilter};
+use crate::data::message_counts;
+use crate::redact::redact_content;
+use crate::text::{capped_width, wrap_columns};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     prelude::*,
     widgets::{
-        Block, Borders, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+        Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+        ScrollbarState, Wrap,
     },
 };
+use std::borrow::Cow;
 
 pub fn draw_session_list(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
     let title = format!("Sessions ({})", app.sessions.len());
     let block = styled_block(&title, is_focused);
 
     if app.sessions.is_empty() {
-        let empty = Paragraph::new("No sessions found")
+        // "Nothing there" and "couldn't read it" look different
+        let (text, color) = match &app.load_error {
+            Some(error) => (error.as_str(), ERROR),
+            None => ("No sessions found", MUTED),
+        };
+        let empty = Paragraph::new(text)
             .block(block)
-            .style(Style::default().fg(MUTED))
-            .alignment(Alignment::Center);
+            .style(Style::default().fg(color))
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         f.render_widget(empty, area);
         return;
     }
 
-    // If renaming, show input at top
-    if app.renaming {
+    let filter_title = app.session_filter_active.then(|| {
+        format!(
+            " Filter sessions: {} matching (Enter to open, Esc to clear) ",
+            app.visible_sessions().len()
+        )
+    });
+
+    // Show the active text input (rename, tags, session filter, open path, new session) at the top
+    let input = if app.renaming {
+        Some((
+            " Rename (Enter to save, Esc to cancel) ",
+            app.rename_buffer.as_str(),
+        ))
+    } else if app.editing_tags {
+        Some((
+            " Tags, comma-separated (Enter to save, Esc to cancel) ",
+            app.tags_buffer.as_str(),
+        ))
+    } else if app.session_filter_active {
+        Some((
+            filter_title.as_deref().unwrap_or_default(),
+            app.session_filter_draft.as_str(),
+        ))
+    } else if app.opening_path {
+        Some((
+            " Open file (relative to project, Enter to edit) ",
+            app.open_path_buffer.as_str(),
+        ))
+    } else if app.add_dir_prompt {
+        Some((
+            " Extra --add-dir paths, space-separated (Enter to open) ",
+            app.add_dir_input.as_str(),
+        ))
+    } else if app.new_session_prompt {
+        Some((
+            " New session in (Enter to start, Esc to cancel) ",
+            app.new_session_dir.as_str(),
+        ))
+    } else {
+        None
+    };
+
+    if let Some((title, text)) = input {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(3), Constraint::Min(0)])
             .split(area);
 
-        // Rename input
-        let input_block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
-            .title(" Rename (Enter to save, Esc to cancel) ")
-            .title_style(Style::default().fg(Color::Yellow).bold());
-
-        let input = Paragraph::new(app.rename_buffer.as_str())
-            .block(input_block)
-            .style(Style::default().fg(Color::White));
-        f.render_widget(input, chunks[0]);
-
-        // Position cursor at end of input
-        let cursor_x = chunks[0].x + 1 + app.rename_buffer.chars().count() as u16;
-        let cursor_y = chunks[0].y + 1;
-        if cursor_x < chunks[0].x + chunks[0].width - 1 {
-            f.set_cursor_position(ratatui::layout::Position::new(cursor_x, cursor_y));
-        }
+        draw_input_box(f, chunks[0], title, text);
 
         // Draw session list in remaining space
         draw_session_list_inner(f, app, chunks[1], is_focused);
@@ -55,49 +94,90 @@ pub fn draw_session_list(f: &mut Frame, app: &mut App, area: Rect, is_focused: b
     draw_session_list_inner(f, app, area, is_focused);
 }
 
+/// Single-line text input with the cursor at the end
+fn draw_input_box(f: &mut Frame, area: Rect, title: &str, text: &str) {
+    let input_block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(title)
+        .title_style(Style::default().fg(Color::Yellow).bold());
+
+    let input = Paragraph::new(text)
+        .block(input_block)
+        .style(Style::default().fg(Color::White));
+    f.render_widget(input, area);
+
+    // Position cursor at end of input
+    let cursor_x = area.x + 1 + text.chars().count() as u16;
+    let cursor_y = area.y + 1;
+    if cursor_x < area.x + area.width - 1 {
+        f.set_cursor_position(ratatui::layout::Position::new(cursor_x, cursor_y));
+    }
+}
+
 fn draw_session_list_inner(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
-    let block = styled_block("Sessions", is_focused);
+    let visible = app.visible_sessions();
+    let title = if app.load_error.is_some() {
+        "Sessions (stale: load failed)".to_string()
+    } else if app.session_filter.is_empty() && !app.today_only {
+        "Sessions".to_string()
+    } else {
+        let mut title = format!("Sessions ({}/{})", visible.len(), app.sessions.len());
+        if app.today_only {
+            title.push_str(" today");
+        }
+        if !app.session_filter.is_empty() {
+            title.push_str(&format!(" #{}", app.session_filter));
+        }
+        title
+    };
+    let block = styled_block(&title, is_focused);
     let max_name_width = (area.width as usize).saturating_sub(4).min(25);
 
-    let items: Vec<ListItem> = app
-        .sessions
+    let items: Vec<ListItem> = visible
         .iter()
+        .map(|&idx| &app.sessions[idx])
         .enumerate()
         .map(|(i, session)| {
             let is_selected = app.session_list_state.selected() == Some(i);
 
             // More distinct status indicators
-            let (status_char, status_color) = match session.status.as_str() {
-                "working" => ("⟳", Color::Cyan), // Cyan spinner = actively processing (<10s)
-                "active" => ("▶", Color::Green), // Green play = recent activity (<2 min)
-                "idle" => ("●", Color::Yellow),  // Yellow dot = waiting (2-30 min)
-                "inactive" => ("○", Color::DarkGray), // Gray circle = old (>30 min)
-                "waiting" => ("◆", Color::Magenta), // Magenta = waiting for user (from hook)
-                _ => ("○", Color::DarkGray),
-            };
+            let glyphs = &app.settings.status_glyphs;
+            let (mut status_char, status_color) = super::status_glyph(glyphs, &session.status);
+            if session.status == "working" && glyphs.animate_working() {
+                status_char = app.spinner_frame();
+            }
+
+            let display_name = app.redactor().session_name(session);
 
-            // Use custom_name > description > project_name
-            let display_name = session
-                .custom_name
-                .as_ref()
-                .or(session.description.as_ref())
-                .map(|d| truncate(d, max_name_width))
-                .unwrap_or_else(|| session.project_name.clone());
+            let mark = app
+                .session_mark(session)
+                .map(|m| format!("'{m} "))
+                .unwrap_or_default();
 
-            let content = Line::from(vec![
-                Span::styled(status_char, Style::default().fg(status_color)),
+            let missing = session.transcript_missing();
+            let name_color = if missing {
+                Color::DarkGray
+            } else if is_selected {
+                Color::White
+            } else {
+                project_color(session).unwrap_or(Color::Gray)
+            };
+            let mut name_spans = vec![
+                Span::styled(status_char.to_string(), Style::default().fg(status_color)),
                 Span::raw(" "),
+                Span::styled(mark, Style::default().fg(Color::Yellow)),
                 Span::styled(
                     truncate(&display_name, max_name_width),
-                    Style::default().fg(if is_selected {
-                        Color::White
-                    } else {
-                        Color::Gray
-                    }),
+                    Style::default().fg(name_color),
                 ),
-            ]);
+            ];
+            if missing {
+                name_spans.push(Span::styled(" [missing]", Style::default().fg(MUTED)));
+            }
+            let content = Line::from(name_spans);
 
-            let time_line = Line::from(vec![
+            let mut time_spans = vec![
                 Span::raw("  "),
                 Span::styled(
                     relative_time(&session.last_activity),
@@ -107,7 +187,41 @@ fn draw_session_list_inner(f: &mut Frame, app: &mut App, area: Rect, is_focused:
                     format!(" {} msgs", session.message_count),
                     Style::default().fg(MUTED),
                 ),
-            ]);
+            ];
+
+            let unseen = app.unseen_message_count(session);
+            if unseen > 0 {
+                time_spans.push(Span::styled(
+                    format!(" +{unseen}"),
+                    Style::default().fg(SUCCESS).bold(),
+                ));
+            }
+
+            // Todo progress, colored by how much is done
+            if let Some((completed, total)) = session.todo_progress() {
+                let color = if completed == total {
+                    SUCCESS
+                } else if completed > 0 {
+                    WARNING
+                } else {
+                    MUTED
+                };
+                time_spans.push(Span::styled(
+                    format!(" {completed}/{total} ✓"),
+                    Style::default().fg(color),
+                ));
+            }
+
+            // Tag chips
+            for tag in app.session_tags(session) {
+                time_spans.push(Span::raw(" "));
+                time_spans.push(Span::styled(
+                    format!(" {tag} "),
+                    Style::default().fg(Color::Black).bg(Color::Magenta),
+                ));
+            }
+
+            let time_line = Line::from(time_spans);
 
             ListItem::new(vec![content, time_line]).style(if is_selected {
                 Style::default().bg(SELECTED_BG)
@@ -137,6 +251,10 @@ pub fn draw_detail_view(f: &mut Frame, app: &mut App, area: Rect, is_focused: bo
     } else if app.focus == crate::app::Focus::Todos {
         // Show todos preview when Todos panel is focused
         draw_todos_preview(f, app, area);
+    } else if app.show_churn {
+        super::churn::draw_churn_view(f, app, area, is_focused);
+    } else if app.show_agents {
+        super::agents::draw_agents_view(f, app, area, is_focused);
     } else {
         // Layout: header + chat
         let chunks = Layout::default()
@@ -153,12 +271,17 @@ pub fn draw_detail_view(f: &mut Frame, app: &mut App, area: Rect, is_focused: bo
 }
 
 fn draw_todos_preview(f: &mut Frame, app: &mut App, area: Rect) {
-    let todos: Vec<_> = app
+    let mut todos: Vec<_> = app
         .selected_session()
-        .map(|s| s.todos.iter().collect())
+        .map(|s| sort_todos(&s.todos))
         .unwrap_or_default();
+    let total = todos.len();
+    if app.hide_done_todos {
+        todos.retain(|t| t.status != "completed");
+    }
+    let done_hidden = total - todos.len();
 
-    let title = format!("Todos ({})", todos.len());
+    let title = format!("Todos ({total})");
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(super::BORDER_COLOR))
@@ -168,7 +291,7 @@ fn draw_todos_preview(f: &mut Frame, app: &mut App, area: Rect) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    if todos.is_empty() {
+    if total == 0 {
         let empty = Paragraph::new("No todos")
             .style(Style::default().fg(MUTED))
             .alignment(Alignment::Center);
@@ -176,27 +299,11 @@ fn draw_todos_preview(f: &mut Frame, app: &mut App, area: Rect) {
         return;
     }
 
-    // Sort: in_progress → pending → completed
-    let mut sorted_todos = todos;
-    sorted_todos.sort_by(|a, b| {
-        let status_order = |s: &str| match s {
-            "in_progress" => 0,
-            "pending" => 1,
-            "completed" => 2,
-            _ => 1,
-        };
-        status_order(&a.status).cmp(&status_order(&b.status))
-    });
-
     let max_width = inner.width.saturating_sub(4) as usize;
-    let lines: Vec<Line> = sorted_todos
+    let mut lines: Vec<Line> = todos
         .iter()
         .map(|todo| {
-            let (icon, style) = match todo.status.as_str() {
-                "in_progress" => ("▶", Style::default().fg(Color::Cyan)),
-                "completed" => ("✓", Style::default().fg(MUTED)),
-                _ => ("○", Style::default().fg(Color::Gray)),
-            };
+            let (icon, style) = todo_style(&todo.status);
 
             Line::from(vec![
                 Span::styled(format!(" {} ", icon), style),
@@ -204,6 +311,9 @@ fn draw_todos_preview(f: &mut Frame, app: &mut App, area: Rect) {
             ])
         })
         .collect();
+    if done_hidden > 0 {
+        lines.push(super::done_summary_line(done_hidden));
+    }
 
     // Calculate scroll
     let total_lines = lines.len() as u16;
@@ -223,9 +333,12 @@ fn draw_todos_preview(f: &mut Frame, app: &mut App, area: Rect) {
 
 fn draw_diff_view(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
     let file = app.current_file_changes.get(app.selected_file_idx);
-    let title = file
-        .map(|f| f.path.clone())
-        .unwrap_or_else(|| "No file selected".to_string());
+    let path = file.map(|f| f.path.clone());
+    let title = match file {
+        Some(f) if app.raw_file_view => format!("{} [file]", app.redactor().path(&f.path)),
+        Some(f) => app.redactor().path(&f.display_path()).into_owned(),
+        None => "No file selected".to_string(),
+    };
 
     // Show active border only when actually in diff_mode (entered with Enter)
     let show_active = is_focused && app.diff_mode;
@@ -253,7 +366,8 @@ fn draw_diff_view(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
     let inner = block.inner(area);
     f.render_widget(block, area);
 
-    if app.current_diff.is_empty() {
+    let text = app.diff_view_text().to_string();
+    if text.is_empty() {
         let empty = Paragraph::new("No diff available\n\nSelect a file with j/k")
             .style(Style::default().fg(MUTED))
             .alignment(Alignment::Center);
@@ -261,61 +375,113 @@ fn draw_diff_view(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
         return;
     }
 
-    // Parse and colorize diff with line wrapping
-    let max_width = inner.width as usize;
-    let mut lines: Vec<Line> = Vec::new();
-
-    for line in app.current_diff.lines() {
-        let style = if line.starts_with('+') && !line.starts_with("+++") {
-            Style::default().fg(Color::Green)
-        } else if line.starts_with('-') && !line.starts_with("---") {
-            Style::default().fg(Color::Red)
-        } else if line.starts_with("@@") {
-            Style::default().fg(Color::Cyan)
-        } else if line.starts_with("diff") || line.starts_with("index") {
-            Style::default().fg(Color::Yellow)
-        } else {
-            Style::default().fg(Color::Gray)
-        };
+    // Minimap column on the right when the diff overflows the pane
+    // CRLF files leave a `\r` on some lines; it must not reach width measuring
+    let source: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    app.diff_pane = inner;
+    let show_minimap = app.diff_shows_minimap(source.len());
+    let (inner, minimap_area) = if show_minimap {
+        let chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Min(0), Constraint::Length(1)])
+            .split(inner);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (inner, None)
+    };
+    app.minimap_area = minimap_area;
 
-        // Wrap long lines
-        if line.chars().count() <= max_width {
-            lines.push(Line::from(Span::styled(line, style)));
-        } else {
-            let mut remaining = line;
-            while !remaining.is_empty() {
-                let (chunk, rest) = if remaining.chars().count() <= max_width {
-                    (remaining, "")
-                } else {
-                    let byte_idx = remaining
-                        .char_indices()
-                        .nth(max_width)
-                        .map(|(i, _)| i)
-                        .unwrap_or(remaining.len());
-                    (&remaining[..byte_idx], &remaining[byte_idx..])
-                };
-                lines.push(Line::from(Span::styled(chunk, style)));
-                remaining = rest;
-            }
-        }
-    }
+    // Wrap long lines to the pane's display width; rows remember their source line
+    let max_width = inner.width as usize;
+    let rows: Vec<(usize, &str)> = source
+        .iter()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            wrap_columns(line, max_width)
+                .into_iter()
+                .map(move |c| (i, c))
+        })
+        .collect();
 
     // Calculate scroll
-    let total_lines = lines.len() as u16;
+    let total_lines = rows.len() as u16;
     let visible_lines = inner.height;
     app.chat_scroll_max = total_lines.saturating_sub(visible_lines);
 
-    let scroll_offset = app.chat_scroll.min(app.chat_scroll_max);
-    let visible: Vec<Line> = lines
+    let start = (app.scroll_offset() as usize).min(rows.len());
+    let end = (start + visible_lines as usize).min(rows.len());
+    let window = &rows[start..end];
+
+    if let Some(area) = minimap_area {
+        let row_lines: Vec<&str> = rows.iter().map(|&(i, _)| source[i]).collect();
+        minimap::draw_minimap(f, area, &row_lines, start, visible_lines as usize);
+    }
+
+    // Split each visible row into its diff marker and the code after it
+    let raw = app.raw_file_view;
+    let theme = &app.settings.theme;
+    let parts: Vec<(Style, &str, Option<&str>)> = window
+        .iter()
+        .map(|&(i, chunk)| {
+            let line = source[i];
+            let style = super::diff_line_style(line, raw, theme);
+            let is_code = raw || is_code_line(line);
+            let first_chunk = std::ptr::eq(chunk.as_ptr(), line.as_ptr());
+            match (is_code, raw || !first_chunk) {
+                (false, _) => (style, chunk, None),
+                (true, true) => (style, "", Some(chunk)),
+                (true, false) => (style, &chunk[..1], Some(&chunk[1..])),
+            }
+        })
+        .collect();
+
+    // Highlight only the visible code, in order, so long files stay cheap
+    let code: Vec<&str> = parts.iter().filter_map(|p| p.2).collect();
+    let mut highlighted = path
+        .as_deref()
+        .zip(app.highlighter())
+        .and_then(|(path, h)| h.highlight_window(path, &code))
+        .map(|lines| lines.into_iter());
+
+    let (add, remove) = (super::diff_add(theme), super::diff_remove(theme));
+    let visible: Vec<Line> = parts
         .into_iter()
-        .skip(scroll_offset as usize)
-        .take(visible_lines as usize)
+        .map(|(style, marker, code)| match (code, highlighted.as_mut()) {
+            (Some(_), Some(runs)) => {
+                let bg = match style.fg {
+                    Some(fg) if !raw && fg == add => Some(Color::Rgb(20, 45, 20)),
+                    Some(fg) if !raw && fg == remove => Some(Color::Rgb(55, 20, 20)),
+                    _ => None,
+                };
+                let mut spans = vec![Span::styled(marker.to_string(), style)];
+                for (run_style, text) in runs.next().unwrap_or_default() {
+                    let run_style = match bg {
+                        Some(bg) => run_style.bg(bg),
+                        None => run_style,
+                    };
+                    spans.push(Span::styled(text, run_style));
+                }
+                Line::from(spans)
+            }
+            (Some(code), None) => Line::from(Span::styled(format!("{marker}{code}"), style)),
+            (None, _) => Line::from(Span::styled(marker.to_string(), style)),
+        })
         .collect();
 
     let paragraph = Paragraph::new(visible);
     f.render_widget(paragraph, inner);
 }
 
+/// Context, added or removed source line (as opposed to diff headers)
+fn is_code_line(line: &str) -> bool {
+    line.starts_with(' ')
+        || (line.starts_with('+') && !line.starts_with("+++"))
+        || (line.starts_with('-') && !line.starts_with("---"))
+}
+
 fn draw_embedded_terminal(f: &mut Frame, app: &mut App, area: Rect) {
     let block = Block::default()
         .borders(Borders::ALL)
@@ -389,23 +555,66 @@ fn vt100_to_ratatui_color(color: vt100::Color) -> Color {
     }
 }
 
+/// Color set by the session's `.lazychat.toml`, if it parses
+fn project_color(session: &crate::data::Session) -> Option<Color> {
+    let color = session.project_meta.as_ref()?.color.as_ref()?;
+    color.parse().ok()
+}
+
 fn draw_session_header(f: &mut Frame, app: &App, area: Rect) {
     let session = app.selected_session();
+    let redact = app.redactor();
 
     let content = match session {
         Some(s) => {
             let mut spans = vec![
                 Span::styled(" ", Style::default()),
-                Span::styled(&s.project, Style::default().fg(Color::White).bold()),
-                Span::styled("  │  ", Style::default().fg(Color::DarkGray)),
-                Span::styled(truncate(&s.id, 10), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    redact.path(&s.project),
+                    Style::default()
+                        .fg(project_color(s).unwrap_or(Color::White))
+                        .bold(),
+                ),
                 Span::styled("  │  ", Style::default().fg(Color::DarkGray)),
                 Span::styled(
-                    format!("{} msgs", s.message_count),
-                    Style::default().fg(INFO),
+                    truncate(&redact.id(&s.id), 10),
+                    Style::default().fg(Color::DarkGray),
                 ),
+                Span::styled("  │  ", Style::default().fg(Color::DarkGray)),
             ];
 
+            // Per-role breakdown once the transcript is loaded, else the index count
+            if app.current_messages.is_empty() {
+                spans.push(Span::styled(
+                    format!("{} msgs", s.message_count),
+                    Style::default().fg(INFO),
+                ));
+            } else {
+                let counts = message_counts(&app.current_messages);
+                spans.push(Span::styled(
+                    format!("{}↑ {}↓", counts.user, counts.assistant),
+                    Style::default().fg(INFO),
+                ));
+                spans.push(Span::styled(
+                    format!(" {} tools", counts.tool_calls),
+                    Style::default().fg(MUTED),
+                ));
+            }
+
+            if let Some(duration) = s.duration() {
+                spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(
+                    format!("⏱ {}", short_duration(duration.num_seconds().max(0) as u64)),
+                    Style::default().fg(MUTED),
+                ));
+            }
+
+            // Recomputed every draw, so it keeps counting between refreshes
+            if let Some(idle) = idle_for(s.last_activity, chrono::Utc::now()) {
+                spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(idle, Style::default().fg(MUTED)));
+            }
+
             if !s.todos.is_empty() {
                 spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
                 spans.push(Span::styled(
@@ -414,6 +623,15 @@ fn draw_session_header(f: &mut Frame, app: &App, area: Rect) {
                 ));
             }
 
+            if let Some(note) = app.selected_session_note() {
+                let first_line = note.lines().next().unwrap_or_default();
+                spans.push(Span::styled("  │  ", Style::default().fg(Color::DarkGray)));
+                spans.push(Span::styled(
+                    format!("✎ {}", truncate(first_line, 40)),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+
             spans.push(Span::styled(" ", Style::default()));
             Line::from(spans)
         }
@@ -438,9 +656,35 @@ fn draw_session_header(f: &mut Frame, app: &App, area: Rect) {
 fn draw_messages(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
     let session = app.selected_session();
     let title = match session {
-        Some(s) => format!("Chat - {}", s.project_name),
+        Some(s) => match app.unseen_message_count(s) {
+            0 => format!("Chat - {}", s.project_name),
+            n => format!("Chat - {} (+{n} new, G: jump to newest)", s.project_name),
+        },
         None => "Chat".to_string(),
     };
+    let missing = session.is_some_and(|s| s.transcript_missing());
+    draw_chat(f, app, area, is_focused, title, missing);
+}
+
+/// Chat body for `app.current_messages`; the compare view draws each pane
+/// through this with the pane swapped in
+pub(super) fn draw_chat(
+    f: &mut Frame,
+    app: &mut App,
+    area: Rect,
+    is_focused: bool,
+    title: String,
+    missing: bool,
+) {
+    let title = match app.chat_filter {
+        ChatFilter::All => title,
+        filter => format!("{title} [{}]", filter.label()),
+    };
+    // Uses the previous frame's line count; rendering below refreshes it
+    let title = match scroll_indicator(app.chat_scroll, app.chat_scroll_max) {
+        Some(position) => format!("{title} {position}"),
+        None => title,
+    };
 
     let block = styled_block(&title, is_focused);
     let inner = block.inner(area);
@@ -454,6 +698,14 @@ fn draw_messages(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
         return;
     }
 
+    if missing {
+        let missing = Paragraph::new("Transcript missing\n\nThe session file was deleted")
+            .style(Style::default().fg(MUTED))
+            .alignment(Alignment::Center);
+        f.render_widget(missing, inner);
+        return;
+    }
+
     if app.current_messages.is_empty() {
         let empty = Paragraph::new("No messages\n\nPress 'o' to open Claude")
             .style(Style::default().fg(MUTED))
@@ -462,63 +714,120 @@ fn draw_messages(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
         return;
     }
 
+    let spinner = app.spinner_frame();
     let mut lines: Vec<Line> = Vec::new();
-    let content_width = inner.width.saturating_sub(4) as usize;
-
-    for msg in &app.current_messages {
-        let (role_style, prefix) = if msg.role == "user" {
+    let content_width = capped_width(
+        inner.width.saturating_sub(4) as usize,
+        app.settings.display.max_line_width,
+    );
+
+    let redact = app.redactor();
+    let session_key = app.loaded_session_key.as_deref().unwrap_or_default();
+    let wrapped = app
+        .wrap_cache
+        .lines(session_key, content_width, &app.current_messages);
+
+    let filter = app.chat_filter;
+    let show_thinking = app.settings.display.show_thinking;
+    let shown = filter.select(&app.current_messages);
+    let blocks = message_blocks(
+        &app.current_messages,
+        &shown,
+        app.settings.display.merge_messages,
+    );
+    let mut starts = Vec::with_capacity(shown.len());
+    for block in blocks {
+        let first = &app.current_messages[block[0]];
+        let (role_style, prefix) = if first.role == "user" {
             (Style::default().fg(Color::Cyan).bold(), "▶ You")
         } else {
             (Style::default().fg(Color::Green).bold(), "◀ Claude")
         };
 
-        lines.push(Line::from(vec![
+        let mut header = vec![
             Span::styled(prefix, role_style),
             Span::raw(" "),
             Span::styled(
-                msg.timestamp
+                first
+                    .timestamp
                     .map(|t| t.format("%H:%M").to_string())
                     .unwrap_or_default(),
                 Style::default().fg(MUTED),
             ),
-        ]));
-
-        let display_lines = msg.display_content(content_width);
-        for line in display_lines {
-            let style = if msg.role == "user" {
-                Style::default().fg(Color::White)
+        ];
+        if let Some(note) = first
+            .thinking
+            .filter(|_| show_thinking)
+            .and_then(|t| t.annotation())
+        {
+            header.push(Span::styled(format!(" {note}"), Style::default().fg(MUTED)));
+        }
+        lines.push(Line::from(header));
+
+        for (n, &idx) in block.iter().enumerate() {
+            let (msg, display_lines) = (&app.current_messages[idx], &wrapped[idx]);
+            // The header row belongs to the block's first message
+            starts.push((if n == 0 { lines.len() - 1 } else { lines.len() }, idx));
+
+            // display_content ends with one plain line per tool call; those are
+            // drawn styled below instead
+            let text_lines = match filter {
+                ChatFilter::ToolsOnly => &[],
+                _ => &display_lines[..display_lines.len().saturating_sub(msg.tool_calls.len())],
+            };
+            let text_lines: Vec<Cow<str>> = if redact.hides_content() && !text_lines.is_empty() {
+                vec![Cow::Owned(redact_content(&msg.content))]
             } else {
-                Style::default().fg(Color::Gray)
+                text_lines.iter().map(|line| redact.path(line)).collect()
             };
-            lines.push(Line::from(vec![Span::raw("  "), Span::styled(line, style)]));
-        }
+            for line in text_lines {
+                let style = if msg.role == "user" {
+                    Style::default().fg(Color::White)
+                } else {
+                    Style::default().fg(Color::Gray)
+                };
+                lines.push(Line::from(vec![Span::raw("  "), Span::styled(line, style)]));
+            }
 
-        for tool in &msg.tool_calls {
-            let tool_style = match tool.status.as_str() {
-                "completed" => Style::default().fg(SUCCESS),
-                "error" => Style::default().fg(Color::Red),
-                _ => Style::default().fg(WARNING),
+            let tools: &[_] = if filter == ChatFilter::TextOnly {
+                &[]
+            } else {
+                &msg.tool_calls
             };
-            lines.push(Line::from(vec![
-                Span::raw("  "),
-                Span::styled("└─ ", Style::default().fg(MUTED)),
-                Span::styled(&tool.tool_name, tool_style),
-            ]));
+            for tool in tools {
+                let tool_style = match tool.status.as_str() {
+                    "completed" => Style::default().fg(SUCCESS),
+                    "error" => Style::default().fg(Color::Red),
+                    _ => Style::default().fg(WARNING),
+                };
+                let mut spans = vec![
+                    Span::raw("  "),
+                    Span::styled("└─ ", Style::default().fg(MUTED)),
+                    Span::styled(redact.path(&tool.summary()).into_owned(), tool_style),
+                ];
+                if tool.status == "running" {
+                    spans.push(Span::styled(format!(" {spinner}"), tool_style));
+                }
+                lines.push(Line::from(spans));
+            }
         }
 
         lines.push(Line::from(""));
     }
 
+    // After a filter change, bring the previous top message (or the next shown) back up
+    if let Some(anchor) = app.scroll_anchor.take() {
+        if let Some(&(row, _)) = starts.iter().find(|&&(_, idx)| idx >= anchor) {
+            app.chat_scroll = row as u16;
+        }
+    }
+    app.message_line_starts = starts;
     let total_lines = lines.len() as u16;
     let visible_lines = inner.height;
     app.chat_scroll_max = total_lines.saturating_sub(visible_lines);
 
-    let scroll_offset = app.chat_scroll;
-    let start_line = if total_lines <= visible_lines {
-        0
-    } else {
-        (total_lines - visible_lines).saturating_sub(scroll_offset) as usize
-    };
+    // Same as scroll_offset(), but by field while the wrap cache is borrowed
+    let start_line = app.chat_scroll.min(app.chat_scroll_max) as usize;
 
     let visible: Vec<Line> = lines
         .into_iter()
@@ -535,7 +844,7 @@ fn draw_messages(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
             .end_symbol(Some("↓"));
 
         let mut scrollbar_state = ScrollbarState::new(app.chat_scroll_max as usize)
-            .position((app.chat_scroll_max - app.chat_scroll) as usize);
+            .position(app.scroll_offset() as usize);
 
         f.render_stateful_widget(
             scrollbar,
@@ -547,3 +856,95 @@ fn draw_messages(f: &mut Frame, app: &mut App, area: Rect, is_focused: bool) {
         );
     }
 }
+
+/// Scrollable popup with the selected message's source JSON
+pub fn draw_raw_json_popup(f: &mut Frame, app: &mut App, area: Rect) {
+    if app.raw_json.is_none() {
+        return;
+    }
+    let width = 100.min(area.width.saturating_sub(4));
+    let height = area.height.saturating_sub(4);
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(INFO))
+        .title(" Raw JSON (j/k scroll, Esc close) ")
+        .title_style(Style::default().fg(INFO).bold());
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // Long string values are split at the popup width so scrolling counts rows
+    let json = app.raw_json.as_deref().unwrap_or_default();
+    let rows: Vec<&str> = json
+        .lines()
+        .flat_map(|l| wrap_columns(l, inner.width as usize))
+        .collect();
+    let max = (rows.len() as u16).saturating_sub(inner.height);
+    let scroll = app.raw_json_scroll.min(max);
+
+    let visible: Vec<Line> = rows
+        .iter()
+        .skip(scroll as usize)
+        .take(inner.height as usize)
+        .map(|&row| Line::from(row))
+        .collect();
+    f.render_widget(
+        Paragraph::new(visible).style(Style::default().fg(Color::White)),
+        inner,
+    );
+    app.raw_json_scroll = scroll;
+}
+
+/// Multi-line note editor for the selected session, drawn as a centered popup
+pub fn draw_note_editor(f: &mut Frame, app: &App, area: Rect) {
+    let width = 60.min(area.width.saturating_sub(4));
+    let height = 12.min(area.height.saturating_sub(4));
+    let popup = Rect {
+        x: area.x + (area.width.saturating_sub(width)) / 2,
+        y: area.y + (area.height.saturating_sub(height)) / 2,
+        width,
+        height,
+    };
+
+    f.render_widget(Clear, popup);
+
+    let title = match app.selected_session() {
+        Some(s) => format!(" Note - {} (^s save, Esc cancel) ", s.project_name),
+        None => " Note (^s save, Esc cancel) ".to_string(),
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(title)
+        .title_style(Style::default().fg(Color::Yellow).bold());
+
+    let inner = block.inner(popup);
+    f.render_widget(block, popup);
+
+    // Keep the cursor line visible by showing the last lines of the buffer
+    let lines: Vec<&str> = app.note_buffer.split('\n').collect();
+    let skip = lines.len().saturating_sub(inner.height as usize);
+    let visible: Vec<Line> = lines[skip..]
+        .iter()
+        .map(|l| Line::from(l.to_string()))
+        .collect();
+    f.render_widget(
+        Paragraph::new(visible).style(Style::default().fg(Color::White)),
+        inner,
+    );
+
+    let last = lines.last().copied().unwrap_or_default();
+    let cursor_x = inner.x + last.chars().count() as u16;
+    let cursor_y = inner.y + (lines.len() - skip).saturating_sub(1) as u16;
+    if cursor_x < inner.x + inner.width && cursor_y < inner.y + inner.height {
+        f.set_cursor_position(ratatui::layout::Position::new(cursor_x, cursor_y));
+    }
+}
@@ -0,0 +1,1899 @@
+//! Tests for App state transitions
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use lazychat::app::{
+    App, Focus, FILTER_DEBOUNCE, FILTER_DEBOUNCE_SESSIONS, OPEN_ALL_CONFIRM_OVER, SCROLL_BOTTOM,
+};
+use lazychat::data::{sidecar::SidecarStore, FileChange, FileStatus, Session};
+use lazychat::events::handle_key;
+
+fn session(id: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        project: "/home/user/dev/project".to_string(),
+        project_name: "project".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 1,
+        status: "idle".to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    }
+}
+
+/// App with the detail view focused and `max` lines of scrollable content
+fn detail_app(diff_mode: bool, max: u16) -> App {
+    let mut app = App::new();
+    app.focus = Focus::Detail;
+    app.diff_mode = diff_mode;
+    app.chat_scroll_max = max;
+    app
+}
+
+async fn press(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    handle_key(app, KeyEvent::new(code, modifiers))
+        .await
+        .unwrap();
+}
+
+#[test]
+fn test_new_app_starts_at_bottom() {
+    let app = detail_app(false, 50);
+    assert_eq!(app.chat_scroll, SCROLL_BOTTOM);
+    assert_eq!(app.scroll_offset(), 50);
+}
+
+#[test]
+fn test_scroll_lines_moves_offset_and_clamps() {
+    let mut app = detail_app(false, 10);
+    app.scroll_to_top();
+    assert_eq!(app.scroll_offset(), 0);
+
+    app.scroll_lines(-3);
+    assert_eq!(app.scroll_offset(), 0);
+
+    app.scroll_lines(4);
+    assert_eq!(app.scroll_offset(), 4);
+
+    app.scroll_lines(-1);
+    assert_eq!(app.scroll_offset(), 3);
+
+    app.scroll_lines(100);
+    assert_eq!(app.scroll_offset(), 10);
+}
+
+#[test]
+fn test_scroll_to_bottom_follows_growing_content() {
+    let mut app = detail_app(false, 10);
+    app.scroll_to_bottom();
+    assert_eq!(app.scroll_offset(), 10);
+
+    // New lines rendered: still at the bottom
+    app.chat_scroll_max = 25;
+    assert_eq!(app.scroll_offset(), 25);
+
+    // Scrolling down onto the last line pins again
+    app.scroll_lines(-5);
+    assert_eq!(app.scroll_offset(), 20);
+    app.chat_scroll_max = 30;
+    assert_eq!(app.scroll_offset(), 20);
+    app.scroll_lines(10);
+    app.chat_scroll_max = 40;
+    assert_eq!(app.scroll_offset(), 40);
+}
+
+#[test]
+fn test_scroll_up_from_pinned_bottom() {
+    let mut app = detail_app(true, 12);
+    app.scroll_lines(-3);
+    assert_eq!(app.scroll_offset(), 9);
+    assert_eq!(app.chat_scroll, 9);
+}
+
+#[test]
+fn test_no_scroll_when_content_fits() {
+    let mut app = detail_app(false, 0);
+    app.scroll_lines(3);
+    assert_eq!(app.scroll_offset(), 0);
+    app.scroll_lines(-3);
+    assert_eq!(app.scroll_offset(), 0);
+}
+
+#[tokio::test]
+async fn test_j_moves_viewport_down_in_chat_and_diff() {
+    for diff_mode in [false, true] {
+        let mut app = detail_app(diff_mode, 20);
+        app.scroll_to_top();
+
+        press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 3, "j in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::Down, KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 6, "Down in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::Char('k'), KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 3, "k in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::Up, KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 0, "Up in diff_mode={diff_mode}");
+    }
+}
+
+#[tokio::test]
+async fn test_page_keys_in_chat_and_diff() {
+    for diff_mode in [false, true] {
+        let mut app = detail_app(diff_mode, 100);
+        app.scroll_to_top();
+
+        press(&mut app, KeyCode::Char('d'), KeyModifiers::CONTROL).await;
+        assert_eq!(app.scroll_offset(), 30, "^d in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::PageDown, KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 60, "PageDown in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::Char('u'), KeyModifiers::CONTROL).await;
+        assert_eq!(app.scroll_offset(), 30, "^u in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::PageUp, KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 0, "PageUp in diff_mode={diff_mode}");
+    }
+}
+
+#[tokio::test]
+async fn test_g_and_shift_g_in_chat_and_diff() {
+    for diff_mode in [false, true] {
+        let mut app = detail_app(diff_mode, 40);
+        app.scroll_lines(-10);
+
+        press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE).await;
+        assert_eq!(app.scroll_offset(), 0, "g in diff_mode={diff_mode}");
+
+        press(&mut app, KeyCode::Char('G'), KeyModifiers::SHIFT).await;
+        assert_eq!(app.scroll_offset(), 40, "G in diff_mode={diff_mode}");
+    }
+}
+
+#[test]
+fn test_hunk_jumps_use_top_offset() {
+    let mut app = detail_app(true, 50);
+    app.current_diff = (0..60)
+        .map(|i| {
+            if i == 5 || i == 30 {
+                "@@ -1 +1 @@".to_string()
+            } else {
+                format!(" line {i}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    app.scroll_to_top();
+
+    app.jump_to_next_hunk();
+    assert_eq!(app.scroll_offset(), 5);
+    app.jump_to_next_hunk();
+    assert_eq!(app.scroll_offset(), 30);
+    app.jump_to_next_hunk();
+    assert_eq!(app.scroll_offset(), 30);
+
+    app.jump_to_prev_hunk();
+    assert_eq!(app.scroll_offset(), 5);
+    app.jump_to_prev_hunk();
+    assert_eq!(app.scroll_offset(), 5);
+}
+
+#[tokio::test]
+async fn test_scroll_restored_when_reselecting_session() {
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b")];
+
+    app.session_list_state.select(Some(0));
+    app.load_session_messages().await.unwrap();
+    app.chat_scroll_max = 40;
+    app.scroll_lines(-12);
+    assert_eq!(app.scroll_offset(), 28);
+
+    app.session_list_state.select(Some(1));
+    app.load_session_messages().await.unwrap();
+    assert_eq!(app.chat_scroll, SCROLL_BOTTOM);
+    app.scroll_to_top();
+
+    app.session_list_state.select(Some(0));
+    app.load_session_messages().await.unwrap();
+    assert_eq!(app.scroll_offset(), 28);
+
+    // Shorter content after a reload clamps the restored position
+    app.chat_scroll_max = 10;
+    assert_eq!(app.scroll_offset(), 10);
+
+    app.session_list_state.select(Some(1));
+    app.load_session_messages().await.unwrap();
+    assert_eq!(app.scroll_offset(), 0);
+}
+
+#[tokio::test]
+async fn test_crlf_transcript_content_is_normalized_on_parse() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("crlf.jsonl");
+    let lines = [
+        r#"{"type":"user","message":{"content":"one\r\ntwo\r"}}"#,
+        r#"{"type":"assistant","message":{"content":[{"type":"text","text":"a\r\nb"},{"type":"text","text":"c\r"}]}}"#,
+    ];
+    std::fs::write(&path, lines.join("\r\n")).unwrap();
+
+    let mut s = session("crlf");
+    s.file_path = Some(path);
+    let mut app = App::with_sessions(vec![s]);
+    app.load_session_messages().await.unwrap();
+
+    let contents: Vec<&str> = app
+        .current_messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(contents, ["one\ntwo", "a\nb\nc"]);
+}
+
+#[test]
+fn test_prune_session_scrolls_drops_deleted_sessions() {
+    let mut app = App::new();
+    app.sessions = vec![session("a")];
+    app.session_scrolls.insert(session("a").key(), 5);
+    app.session_scrolls.insert(session("gone").key(), 7);
+
+    app.prune_session_scrolls();
+
+    assert_eq!(app.session_scrolls.get(&session("a").key()), Some(&5));
+    assert!(!app.session_scrolls.contains_key(&session("gone").key()));
+}
+
+#[tokio::test]
+async fn test_note_editor_saves_multiline_note() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = App::new();
+    app.sidecar = Some(SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap());
+    app.sessions = vec![session("a")];
+    app.focus = Focus::Sessions;
+
+    press(&mut app, KeyCode::Char('N'), KeyModifiers::SHIFT).await;
+    assert!(app.editing_note);
+    for c in "ab".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('c'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('s'), KeyModifiers::CONTROL).await;
+
+    assert!(!app.editing_note);
+    assert_eq!(app.selected_session_note(), Some("ab\nc"));
+
+    // Reopening starts from the saved note; Esc discards edits
+    press(&mut app, KeyCode::Char('N'), KeyModifiers::SHIFT).await;
+    assert_eq!(app.note_buffer, "ab\nc");
+    press(&mut app, KeyCode::Backspace, KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert_eq!(app.selected_session_note(), Some("ab\nc"));
+}
+
+#[tokio::test]
+async fn test_session_filter_limits_visible_sessions() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = App::new();
+    app.sidecar = Some(SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap());
+    app.sessions = vec![session("a"), session("b"), session("c")];
+    app.focus = Focus::Sessions;
+
+    // Tag "b" through the editor
+    app.session_list_state.select(Some(1));
+    press(&mut app, KeyCode::Char('t'), KeyModifiers::NONE).await;
+    for c in "api, bug".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+    assert_eq!(app.session_tags(&app.sessions[1]), ["api", "bug"]);
+
+    press(&mut app, KeyCode::Char('#'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('b'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+
+    assert_eq!(app.visible_sessions(), vec![1]);
+    assert_eq!(app.selected_session().unwrap().id, "b");
+    assert_eq!(app.focus, Focus::Detail);
+    assert!(app.fullscreen);
+
+    app.focus = Focus::Sessions;
+    app.fullscreen = false;
+    app.list_next();
+    assert_eq!(app.selected_session().unwrap().id, "b");
+
+    // Esc in the list clears the filter
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert_eq!(app.visible_sessions(), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn test_session_filter_narrows_live_and_selects_top_match() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut sidecar = SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap();
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b"), session("c")];
+    for (s, tag) in app.sessions.iter().zip(["backend", "rapid", "api"]) {
+        sidecar.set_tags(&s.key(), vec![tag.to_string()]).unwrap();
+    }
+    app.sidecar = Some(sidecar);
+    app.focus = Focus::Sessions;
+
+    press(&mut app, KeyCode::Char('#'), KeyModifiers::NONE).await;
+    let mut counts = Vec::new();
+    for c in "api".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+        counts.push(app.visible_sessions().len());
+    }
+    assert_eq!(counts, [3, 2, 2]);
+    // "api" ranks above "rapid" even though it's listed later
+    assert_eq!(app.selected_session().unwrap().id, "c");
+
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+    assert!(!app.session_filter_active);
+    assert_eq!(app.session_filter, "api");
+    assert_eq!(app.selected_session().unwrap().id, "c");
+}
+
+#[tokio::test]
+async fn test_session_filter_matches_names_and_opens_best_match() {
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b"), session("c")];
+    app.sessions[0].custom_name = Some("refactor parser".to_string());
+    app.sessions[1].description = Some("Fix login redirect".to_string());
+    app.sessions[2].custom_name = Some("login".to_string());
+    app.focus = Focus::Sessions;
+
+    press(&mut app, KeyCode::Char('#'), KeyModifiers::NONE).await;
+    for c in "lgn".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    assert_eq!(app.visible_sessions(), vec![1, 2]);
+
+    // The tighter match on "login" wins over the longer description
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+    assert_eq!(app.selected_session().unwrap().id, "c");
+    assert_eq!(app.focus, Focus::Detail);
+    assert!(app.fullscreen && !app.diff_mode);
+}
+
+#[tokio::test]
+async fn test_session_filter_debounces_large_lists() {
+    use std::time::Instant;
+
+    let mut app = App::new();
+    app.sessions = (0..=FILTER_DEBOUNCE_SESSIONS)
+        .map(|i| session(&i.to_string()))
+        .collect();
+    app.focus = Focus::Sessions;
+
+    press(&mut app, KeyCode::Char('#'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+    assert_eq!(app.session_filter_draft, "x");
+    assert_eq!(app.visible_sessions().len(), FILTER_DEBOUNCE_SESSIONS + 1);
+
+    app.settle_session_filter(Instant::now() + FILTER_DEBOUNCE);
+    assert_eq!(app.session_filter, "x");
+    assert!(app.visible_sessions().is_empty());
+}
+
+#[tokio::test]
+async fn test_new_session_prompts_for_directory_when_configured() {
+    let mut app = App::new();
+    app.settings.new_session.prompt_cwd = true;
+    app.sessions = vec![session("a")];
+    app.focus = Focus::Sessions;
+
+    press(&mut app, KeyCode::Char('n'), KeyModifiers::NONE).await;
+    assert!(app.new_session_prompt && app.input_active());
+    assert_eq!(app.new_session_dir, "/home/user/dev/project");
+    assert!(app.embedded_terminal.is_none());
+
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert!(!app.new_session_prompt);
+    assert!(app.embedded_terminal.is_none());
+}
+
+#[tokio::test]
+async fn test_spawning_disabled_without_claude() {
+    let mut app = App::new();
+    app.claude_available = false;
+    app.sessions = vec![session("a")];
+    app.focus = Focus::Sessions;
+
+    app.open_new_embedded_terminal(80, 24, None).unwrap();
+    assert!(app.embedded_terminal.is_none());
+    assert!(app.status_is_error);
+
+    app.clear_status();
+    app.open_embedded_terminal(80, 24, &[]).unwrap();
+    assert!(app.embedded_terminal.is_none());
+    assert!(app.status_message.unwrap().contains("claude not found"));
+}
+
+fn file(path: &str) -> FileChange {
+    FileChange {
+        path: path.to_string(),
+        filename: path.rsplit('/').next().unwrap().to_string(),
+        status: FileStatus::Modified,
+        additions: 0,
+        deletions: 0,
+        renamed_from: None,
+    }
+}
+
+#[test]
+fn test_all_file_paths_respects_filter() {
+    let mut app = App::new();
+    app.current_file_changes = vec![
+        file("/repo/src/app.rs"),
+        file("/repo/src/ui/mod.rs"),
+        file("/repo/README.md"),
+    ];
+    assert_eq!(
+        app.all_file_paths_text(),
+        "/repo/src/app.rs\n/repo/src/ui/mod.rs\n/repo/README.md"
+    );
+
+    app.file_filter = "src".to_string();
+    assert_eq!(
+        app.all_file_paths_text(),
+        "/repo/src/app.rs\n/repo/src/ui/mod.rs"
+    );
+
+    app.file_filter = "nothing".to_string();
+    assert_eq!(app.all_file_paths_text(), "");
+    assert_eq!(app.yank_all_file_paths(), Some(0));
+}
+
+#[tokio::test]
+async fn test_copy_actions_use_clipboard() {
+    use lazychat::clipboard::MemoryClipboard;
+    use lazychat::data::ChatMessage;
+
+    let clipboard = MemoryClipboard::default();
+    let mut app = App::new();
+    app.clipboard = Box::new(clipboard.clone());
+    app.sessions = vec![session("a")];
+    app.current_file_changes = vec![file("/repo/src/app.rs"), file("/repo/README.md")];
+    app.current_messages = vec![ChatMessage {
+        role: "user".to_string(),
+        content: "Fix the bug".to_string(),
+        timestamp: None,
+        tool_calls: Vec::new(),
+        line: None,
+        thinking: None,
+    }];
+
+    app.focus = Focus::Files;
+    app.selected_file_idx = 1;
+    press(&mut app, KeyCode::Char('y'), KeyModifiers::NONE).await;
+    assert_eq!(clipboard.last().as_deref(), Some("/repo/README.md"));
+
+    press(&mut app, KeyCode::Char('Y'), KeyModifiers::SHIFT).await;
+    assert_eq!(
+        clipboard.last().as_deref(),
+        Some("/repo/src/app.rs\n/repo/README.md")
+    );
+
+    app.focus = Focus::Sessions;
+    press(&mut app, KeyCode::Char('y'), KeyModifiers::NONE).await;
+    assert_eq!(
+        clipboard.last().as_deref(),
+        Some("cd /home/user/dev/project && claude --resume a --dangerously-skip-permissions")
+    );
+
+    app.focus = Focus::Detail;
+    press(&mut app, KeyCode::Char('C'), KeyModifiers::SHIFT).await;
+    assert_eq!(clipboard.last().as_deref(), Some("User:\nFix the bug\n\n"));
+    assert_eq!(clipboard.history().len(), 4);
+    assert!(!app.status_is_error);
+}
+
+#[tokio::test]
+async fn test_file_jump_selects_best_fuzzy_match() {
+    let mut app = App::new();
+    app.current_file_changes = vec![
+        file("/repo/src/app.rs"),
+        file("/repo/src/ui/mod.rs"),
+        file("/repo/src/ui/sessions.rs"),
+        file("/repo/tests/ui_tests.rs"),
+    ];
+
+    assert_eq!(app.best_file_match("sessions"), Some(2));
+    assert_eq!(app.best_file_match("uimod"), Some(1));
+    assert_eq!(app.best_file_match("xyz"), None);
+    assert_eq!(app.best_file_match(""), None);
+
+    // Typing previews the match, even past a filter that hides it
+    app.focus = Focus::Files;
+    app.file_filter = "app".to_string();
+    press(&mut app, KeyCode::Char('/'), KeyModifiers::NONE).await;
+    assert!(app.file_jump_active);
+    for c in "uitest".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    assert_eq!(app.selected_file_idx, 3);
+    assert!(app.file_filter.is_empty());
+
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert!(!app.file_jump_active && app.file_jump_query.is_empty());
+}
+
+#[tokio::test]
+async fn test_raw_file_view_switches_content_source() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.txt");
+    std::fs::write(&path, "first\nsecond\n").unwrap();
+
+    let mut app = detail_app(true, 0);
+    app.current_file_changes = vec![file(path.to_str().unwrap())];
+    app.load_file_diff().await;
+    app.current_diff = "@@ -1 +1,2 @@\n first\n+second\n".to_string();
+    app.chat_scroll = 1;
+
+    assert!(app.diff_view_text().starts_with("@@"));
+
+    press(&mut app, KeyCode::Char('v'), KeyModifiers::NONE).await;
+    assert!(app.raw_file_view);
+    assert_eq!(app.diff_view_text(), "first\nsecond\n");
+    assert_eq!(app.chat_scroll, 1);
+
+    press(&mut app, KeyCode::Char('v'), KeyModifiers::NONE).await;
+    assert!(!app.raw_file_view);
+    assert!(app.diff_view_text().starts_with("@@"));
+}
+
+#[tokio::test]
+async fn test_raw_file_view_falls_back_for_deleted_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("gone.txt");
+
+    let mut app = detail_app(true, 0);
+    app.current_file_changes = vec![file(path.to_str().unwrap())];
+    app.load_file_diff().await;
+    app.toggle_raw_file_view();
+
+    assert!(app.current_file_content.is_none());
+    assert!(app.diff_view_text().contains("deleted"));
+}
+
+#[test]
+fn test_resolve_open_path_relative_to_project() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/lib.rs"), "").unwrap();
+
+    let mut app = App::new();
+    let mut s = session("a");
+    s.project = dir.path().to_string_lossy().to_string();
+    app.sessions = vec![s];
+
+    let resolved = app.resolve_open_path(" src/lib.rs ").unwrap();
+    assert_eq!(resolved, dir.path().join("src/lib.rs"));
+
+    let absolute = dir.path().join("src/lib.rs");
+    assert_eq!(
+        app.resolve_open_path(absolute.to_str().unwrap()).unwrap(),
+        absolute
+    );
+
+    assert!(app.resolve_open_path("src/missing.rs").is_err());
+    assert!(app.resolve_open_path("src").is_err());
+    assert!(app.resolve_open_path("").is_err());
+}
+
+#[tokio::test]
+async fn test_open_path_prompt_spawns_editor_for_entered_path() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("notes.md"), "hi").unwrap();
+
+    let mut app = App::new();
+    let mut s = session("a");
+    s.project = dir.path().to_string_lossy().to_string();
+    app.sessions = vec![s];
+
+    press(&mut app, KeyCode::Char('E'), KeyModifiers::SHIFT).await;
+    assert!(app.opening_path);
+    for c in "notes.md".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    std::env::set_var("EDITOR", "true");
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+
+    assert!(!app.opening_path);
+    assert!(app.terminal_mode);
+    assert!(app.editor_mode);
+    app.close_embedded_terminal();
+}
+
+#[tokio::test]
+async fn test_terminal_write_error_leaves_terminal_mode() {
+    use lazychat::terminal::EmbeddedTerminal;
+
+    struct BrokenPipe;
+    impl std::io::Write for BrokenPipe {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let mut app = App::new();
+    let terminal = EmbeddedTerminal::new(80, 24)
+        .unwrap()
+        .with_writer(Box::new(BrokenPipe));
+    app.embedded_terminal = Some(terminal);
+    app.terminal_mode = true;
+
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+
+    assert!(!app.terminal_mode);
+    assert!(app.embedded_terminal.is_none());
+    assert!(app.status_is_error);
+    assert!(app
+        .status_message
+        .as_deref()
+        .unwrap()
+        .starts_with("Terminal closed"));
+}
+
+#[tokio::test]
+async fn test_open_path_prompt_rejects_missing_file() {
+    let mut app = App::new();
+    press(&mut app, KeyCode::Char('E'), KeyModifiers::SHIFT).await;
+    for c in "/definitely/not/here.rs".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+
+    assert!(!app.opening_path);
+    assert!(!app.terminal_mode);
+    assert!(app.status_message.is_some());
+}
+
+#[tokio::test]
+async fn test_rename_follows_session_across_reorder() {
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b")];
+    app.focus = Focus::Sessions;
+    app.session_list_state.select(Some(0));
+
+    press(&mut app, KeyCode::Char('r'), KeyModifiers::NONE).await;
+    assert!(app.renaming);
+    assert!(app.input_active());
+    for c in "first".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+
+    // A refresh reorders the list while the rename input is open
+    app.sessions.reverse();
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+
+    assert!(!app.input_active());
+    let a = app.sessions.iter().find(|s| s.id == "a").unwrap();
+    let b = app.sessions.iter().find(|s| s.id == "b").unwrap();
+    assert!(a.custom_name.as_deref().unwrap().ends_with("first"));
+    assert_eq!(b.custom_name, None);
+
+    // Without a sidecar the name can't persist, and the user is told so
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Session sidecar not loaded")
+    );
+    assert!(app.status_is_error);
+}
+
+#[test]
+fn test_pager_skips_empty_transcript() {
+    let mut app = App::new();
+    app.sessions = vec![session("a")];
+    app.open_pager(80, 24).unwrap();
+
+    assert!(!app.terminal_mode);
+    assert_eq!(app.status_message.as_deref(), Some("Transcript is empty"));
+}
+
+#[tokio::test]
+async fn test_resume_in_shell_quits_with_command() {
+    let mut app = App::new();
+    app.sessions = vec![session("a")];
+    app.focus = Focus::Sessions;
+
+    handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('O'), KeyModifiers::SHIFT),
+    )
+    .await
+    .unwrap();
+
+    assert!(app.should_quit);
+    let resume = app.resume_on_exit.expect("resume command set");
+    assert_eq!(resume.args, vec!["--resume", "a"]);
+}
+
+#[test]
+fn test_spawned_terminal_uses_configured_shell() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("notes.md");
+    std::fs::write(&path, "hi").unwrap();
+
+    let mut app = App::new();
+    app.settings.shell = "sh".to_string();
+    std::env::set_var("EDITOR", "true");
+    app.open_editor_at(&path, 80, 24).unwrap();
+
+    assert_eq!(app.embedded_terminal.as_ref().unwrap().shell(), "sh");
+    app.close_embedded_terminal();
+}
+
+#[tokio::test]
+async fn test_mark_and_jump_selects_marked_session() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = App::new();
+    app.sidecar = Some(SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap());
+    app.sessions = vec![session("a"), session("b"), session("c")];
+    app.focus = Focus::Sessions;
+    app.session_list_state.select(Some(1));
+
+    press(&mut app, KeyCode::Char('m'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('w'), KeyModifiers::NONE).await;
+    assert_eq!(app.session_mark(&app.sessions[1]), Some('w'));
+
+    app.session_list_state.select(Some(2));
+    press(&mut app, KeyCode::Char('\''), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('w'), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_session().unwrap().id, "b");
+
+    // Unknown marks leave the selection alone
+    press(&mut app, KeyCode::Char('\''), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('z'), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_session().unwrap().id, "b");
+    assert!(app.status_is_error);
+}
+
+#[test]
+fn test_unseen_message_delta_across_refreshes() {
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b")];
+    app.sessions[0].message_count = 10;
+    app.sessions[1].message_count = 4;
+    app.session_list_state.select(Some(0));
+
+    // First refresh sets the baseline
+    app.record_message_counts();
+    assert_eq!(app.unseen_message_count(&app.sessions[1]), 0);
+
+    // Growth shows up after later refreshes, and is not re-baselined
+    app.sessions[1].message_count = 7;
+    app.record_message_counts();
+    assert_eq!(app.unseen_message_count(&app.sessions[1]), 3);
+    app.sessions[1].message_count = 9;
+    app.record_message_counts();
+    assert_eq!(app.unseen_message_count(&app.sessions[1]), 5);
+    assert_eq!(app.unseen_message_count(&app.sessions[0]), 0);
+
+    // Viewing clears it
+    app.session_list_state.select(Some(1));
+    app.mark_selected_seen();
+    assert_eq!(app.unseen_message_count(&app.sessions[1]), 0);
+
+    // Sessions that disappear are forgotten
+    app.sessions.truncate(1);
+    app.record_message_counts();
+    assert!(!app.seen_message_counts.contains_key(&session("b").key()));
+}
+
+#[tokio::test]
+async fn test_editor_close_restores_where_it_was_opened() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("main.rs");
+    std::fs::write(&path, "fn main() {}\n").unwrap();
+    std::env::set_var("EDITOR", "true");
+
+    // From the diff in the detail pane
+    let mut app = detail_app(true, 0);
+    app.current_file_changes = vec![file(path.to_str().unwrap())];
+    press(&mut app, KeyCode::Char('e'), KeyModifiers::NONE).await;
+    assert!(app.editor_mode);
+    assert!(app.fullscreen);
+    app.close_embedded_terminal();
+    assert_eq!(app.focus, Focus::Detail);
+    assert!(app.diff_mode);
+    assert!(!app.fullscreen);
+
+    // From the Files panel
+    app.focus = Focus::Files;
+    app.diff_mode = false;
+    press(&mut app, KeyCode::Char('e'), KeyModifiers::NONE).await;
+    app.close_embedded_terminal();
+    assert_eq!(app.focus, Focus::Files);
+    assert!(!app.diff_mode);
+}
+
+#[test]
+fn test_idle_screen_after_timeout() {
+    use std::time::{Duration, Instant};
+
+    let start = Instant::now();
+    let mut app = App::new();
+    app.last_input = start;
+
+    // Disabled by default
+    app.update_idle(start + Duration::from_secs(3600));
+    assert!(!app.dimmed);
+
+    app.settings.display.dim_after_secs = 60;
+    app.update_idle(start + Duration::from_secs(59));
+    assert!(!app.dimmed);
+    app.update_idle(start + Duration::from_secs(60));
+    assert!(app.dimmed);
+
+    // The waking key is swallowed; the next one is handled normally
+    let woke = start + Duration::from_secs(61);
+    assert!(app.register_input(woke));
+    assert!(!app.dimmed);
+    assert!(!app.register_input(woke));
+    app.update_idle(woke + Duration::from_secs(30));
+    assert!(!app.dimmed);
+}
+
+#[tokio::test]
+async fn test_next_changed_file_lands_on_first_hunk() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().canonicalize().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    };
+
+    git(&["init", "-q"]);
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(repo.join(name), "one\ntwo\n").unwrap();
+    }
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    std::fs::write(repo.join("a.txt"), "one\nthree\n").unwrap();
+    std::fs::write(repo.join("c.txt"), "zero\none\ntwo\n").unwrap();
+
+    let changed = |name: &str| FileChange {
+        additions: 1,
+        ..file(repo.join(name).to_str().unwrap())
+    };
+    let mut app = App::new();
+    app.focus = Focus::Files;
+    // b.txt was touched but has no changes
+    app.current_file_changes = vec![
+        changed("a.txt"),
+        file(repo.join("b.txt").to_str().unwrap()),
+        changed("c.txt"),
+    ];
+
+    let hunk_line = |app: &App| {
+        app.current_diff
+            .lines()
+            .nth(app.chat_scroll as usize)
+            .unwrap()
+            .to_string()
+    };
+
+    press(&mut app, KeyCode::Char(']'), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_file_idx, 2);
+    assert!(app.diff_mode);
+    assert!(app.current_diff.contains("+zero"));
+    assert!(hunk_line(&app).starts_with("@@"));
+
+    press(&mut app, KeyCode::Char(']'), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_file_idx, 0);
+    assert!(app.current_diff.contains("+three"));
+    assert!(hunk_line(&app).starts_with("@@"));
+
+    press(&mut app, KeyCode::Char('['), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_file_idx, 2);
+}
+
+#[tokio::test]
+async fn test_missing_transcript_is_flagged_and_skipped() {
+    let dir = tempfile::tempdir().unwrap();
+    let present = dir.path().join("present.jsonl");
+    std::fs::write(&present, "").unwrap();
+
+    let mut kept = session("kept");
+    kept.file_path = Some(present);
+    let mut gone = session("gone");
+    gone.file_path = Some(dir.path().join("deleted.jsonl"));
+    assert!(!kept.transcript_missing());
+    assert!(gone.transcript_missing());
+    // Sessions without a known transcript are not flagged
+    assert!(!session("unknown").transcript_missing());
+
+    let mut app = App::new();
+    app.sessions = vec![gone];
+    app.session_list_state.select(Some(0));
+    app.current_messages = vec![lazychat::data::ChatMessage {
+        role: "user".to_string(),
+        content: "stale".to_string(),
+        timestamp: None,
+        tool_calls: Vec::new(),
+        line: None,
+        thinking: None,
+    }];
+    app.load_session_messages().await.unwrap();
+    assert!(app.current_messages.is_empty());
+    assert!(!app.messages_loading);
+}
+
+#[test]
+fn test_auto_follow_waits_after_manual_navigation() {
+    use lazychat::app::FOLLOW_PAUSE;
+    use std::time::{Duration, Instant};
+
+    let now = Instant::now();
+    let mut app = App::new();
+    app.sessions = vec![session("a"), session("b"), session("c")];
+    app.session_list_state.select(Some(2));
+
+    // Off by default
+    app.follow_newest(now);
+    assert_eq!(app.session_list_state.selected(), Some(2));
+
+    app.settings.auto_follow = true;
+    assert!(app.should_follow(now));
+
+    app.last_manual_nav = Some(now);
+    assert!(!app.should_follow(now + FOLLOW_PAUSE - Duration::from_millis(1)));
+    app.follow_newest(now + Duration::from_secs(1));
+    assert_eq!(app.session_list_state.selected(), Some(2));
+
+    app.follow_newest(now + FOLLOW_PAUSE);
+    assert_eq!(app.session_list_state.selected(), Some(0));
+
+    // Moving the selection records the time
+    app.list_next();
+    assert!(app.last_manual_nav.unwrap() >= now);
+    assert!(!app.should_follow(Instant::now()));
+}
+
+#[tokio::test]
+async fn test_with_sessions_keeps_injected_data() {
+    let mut app = App::with_sessions(vec![session("a"), session("b")]);
+    app.load_data().await.unwrap();
+    assert_eq!(app.sessions.len(), 2);
+    assert_eq!(app.selected_session().unwrap().id, "a");
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = App::with_sessions(vec![session("a")]).with_claude_dir(dir.path());
+    app.load_data().await.unwrap();
+    assert!(app.sessions.is_empty());
+}
+
+#[tokio::test]
+async fn test_load_failure_is_visible_on_app() {
+    let dir = tempfile::tempdir().unwrap();
+    // `projects` exists but cannot be listed
+    std::fs::write(dir.path().join("projects"), "not a directory").unwrap();
+    let mut app = App::with_sessions(vec![session("a")]).with_claude_dir(dir.path());
+
+    assert!(app.load_data().await.is_err());
+    let error = app.load_error.clone().expect("load error recorded");
+    assert!(error.starts_with("Failed to load sessions"));
+    assert!(app.status_is_error);
+    assert_eq!(app.status_message.as_deref(), Some(error.as_str()));
+    // The last good data stays on screen
+    assert_eq!(app.sessions.len(), 1);
+
+    // An empty but readable directory is "no data", not a failure
+    std::fs::remove_file(dir.path().join("projects")).unwrap();
+    std::fs::create_dir(dir.path().join("projects")).unwrap();
+    app.load_data().await.unwrap();
+    assert!(app.load_error.is_none());
+    assert!(app.sessions.is_empty());
+}
+
+#[test]
+fn test_list_next_and_prev_clamp_to_visible_sessions() {
+    let mut app = App::with_sessions(vec![session("a"), session("b"), session("c")]);
+
+    app.list_prev();
+    assert_eq!(app.session_list_state.selected(), Some(0));
+    app.list_next();
+    app.list_next();
+    assert_eq!(app.selected_session().unwrap().id, "c");
+    app.list_next();
+    assert_eq!(app.session_list_state.selected(), Some(2));
+    app.list_prev();
+    assert_eq!(app.selected_session().unwrap().id, "b");
+
+    let mut empty = App::with_sessions(Vec::new());
+    empty.list_next();
+    assert_eq!(empty.session_list_state.selected(), Some(0));
+    assert!(empty.selected_session().is_none());
+}
+
+#[test]
+fn test_toggle_focus_enters_and_leaves_detail() {
+    let mut app = App::with_sessions(vec![session("a")]);
+    for from in [Focus::Presets, Focus::Sessions, Focus::Todos, Focus::Files] {
+        app.focus = from;
+        app.toggle_focus();
+        assert_eq!(app.focus, Focus::Detail);
+    }
+
+    app.diff_mode = true;
+    app.toggle_focus();
+    assert_eq!(app.focus, Focus::Sessions);
+    assert!(!app.diff_mode);
+}
+
+#[test]
+fn test_filtered_files_matches_name_or_path_case_insensitively() {
+    let mut app = App::with_sessions(vec![session("a")]);
+    app.current_file_changes = vec![
+        file("/repo/src/App.rs"),
+        file("/repo/src/ui/mod.rs"),
+        file("/repo/README.md"),
+    ];
+
+    assert_eq!(app.filtered_files().len(), 3);
+
+    app.file_filter = "app".to_string();
+    let paths: Vec<&str> = app
+        .filtered_files()
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+    assert_eq!(paths, ["/repo/src/App.rs"]);
+
+    app.file_filter = "/UI/".to_string();
+    let paths: Vec<&str> = app
+        .filtered_files()
+        .iter()
+        .map(|f| f.path.as_str())
+        .collect();
+    assert_eq!(paths, ["/repo/src/ui/mod.rs"]);
+}
+
+#[test]
+fn test_refresh_indicator_flips_on_each_refresh() {
+    let mut app = App::new();
+    assert_eq!(app.refresh_indicator(), "·");
+
+    let now = std::time::Instant::now();
+    app.record_refresh(now);
+    let first = app.refresh_indicator();
+    assert_eq!(app.last_refresh, Some(now));
+    app.record_refresh(now);
+    assert_ne!(app.refresh_indicator(), first);
+    app.record_refresh(now);
+    assert_eq!(app.refresh_indicator(), first);
+}
+
+#[test]
+fn test_spinner_frame_cycles_with_ticks() {
+    use lazychat::app::SPINNER_FRAMES;
+
+    let mut app = App::new();
+    assert_eq!(app.spinner_frame(), SPINNER_FRAMES[0]);
+    app.tick();
+    assert_eq!(app.spinner_frame(), SPINNER_FRAMES[1]);
+    for _ in 1..SPINNER_FRAMES.len() {
+        app.tick();
+    }
+    assert_eq!(app.spinner_frame(), SPINNER_FRAMES[0]);
+
+    // The counter wraps instead of overflowing
+    app.frame = usize::MAX;
+    app.tick();
+    assert_eq!(app.frame, 0);
+}
+
+#[tokio::test]
+async fn test_safe_mode_refuses_spawn_and_kill_keys() {
+    use lazychat::process::registry::ProcessRegistry;
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut registry = ProcessRegistry::load_from(dir.path().join("processes.json")).unwrap();
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    registry
+        .register_process(child.id(), "a".to_string(), None, 0, "/tmp".into(), vec![])
+        .unwrap();
+
+    let mut app = App::with_sessions(vec![session("a"), session("b")]);
+    app.process_registry = Some(registry);
+    app.current_file_changes = vec![file("/repo/src/app.rs")];
+    app.settings.safe_mode = true;
+
+    let attempts = [
+        (Focus::Sessions, KeyCode::Char('o')),
+        (Focus::Sessions, KeyCode::Char('O')),
+        (Focus::Sessions, KeyCode::Char('n')),
+        (Focus::Sessions, KeyCode::Char('d')),
+        (Focus::Sessions, KeyCode::Char('D')),
+        (Focus::Sessions, KeyCode::Char('E')),
+        (Focus::Sessions, KeyCode::Char('p')),
+        (Focus::Detail, KeyCode::Char('p')),
+        (Focus::Files, KeyCode::Char('e')),
+        (Focus::Presets, KeyCode::Enter),
+        (Focus::Presets, KeyCode::Char('n')),
+    ];
+    for (focus, code) in attempts {
+        app.focus = focus;
+        press(&mut app, code, KeyModifiers::NONE).await;
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Disabled in safe mode"),
+            "{code:?}"
+        );
+        assert!(!app.should_quit && !app.terminal_mode && !app.opening_path);
+        assert!(app.resume_on_exit.is_none() && app.pending_kill.is_none());
+        assert!(app.embedded_terminal.is_none());
+    }
+
+    // Shift+Q quits like q but leaves processes running
+    app.focus = Focus::Sessions;
+    press(&mut app, KeyCode::Char('Q'), KeyModifiers::SHIFT).await;
+    assert!(app.should_quit);
+    assert_eq!(app.status_message, None);
+    app.should_quit = false;
+    assert_eq!(
+        app.process_registry
+            .as_ref()
+            .unwrap()
+            .get_all_processes()
+            .len(),
+        1
+    );
+    assert!(child.try_wait().unwrap().is_none());
+    child.kill().unwrap();
+    child.wait().unwrap();
+
+    // Browsing still works
+    app.focus = Focus::Sessions;
+    press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE).await;
+    assert_eq!(app.selected_session().unwrap().id, "b");
+    press(&mut app, KeyCode::Char('d'), KeyModifiers::CONTROL).await;
+    assert_eq!(app.status_message, None);
+}
+
+#[tokio::test]
+async fn test_tailing_appends_only_new_messages() {
+    use std::io::Write;
+
+    let user = |text: &str| format!(r#"{{"type":"user","message":{{"content":"{text}"}}}}"#);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("live.jsonl");
+    std::fs::write(&path, format!("{}\n{}\n", user("one"), user("two"))).unwrap();
+
+    let mut live = session("live");
+    live.file_path = Some(path.clone());
+    let mut app = App::with_sessions(vec![live]);
+    app.load_session_messages().await.unwrap();
+    assert_eq!(app.current_messages.len(), 2);
+
+    // Parsed messages are kept as-is, not re-read
+    app.current_messages[0].content = "kept".to_string();
+
+    let mut file = std::fs::OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .unwrap();
+    let three = user("three");
+    write!(file, "{three}\n{}", &three[..10]).unwrap();
+    app.tail_selected_session().await.unwrap();
+    let contents: Vec<&str> = app
+        .current_messages
+        .iter()
+        .map(|m| m.content.as_str())
+        .collect();
+    assert_eq!(contents, ["kept", "two", "three"]);
+    assert_eq!(app.current_messages[2].line, Some(3));
+    assert_eq!(app.chat_scroll, SCROLL_BOTTOM);
+
+    // The half-written line is picked up once it is finished
+    writeln!(file, "{}", &three[10..]).unwrap();
+    app.tail_selected_session().await.unwrap();
+    assert_eq!(app.current_messages.len(), 4);
+    assert_eq!(app.current_messages[3].line, Some(4));
+
+    // Nothing new: nothing changes
+    app.tail_selected_session().await.unwrap();
+    assert_eq!(app.current_messages.len(), 4);
+}
+
+#[test]
+fn test_chat_filter_selects_text_or_tools() {
+    use lazychat::app::ChatFilter;
+    use lazychat::data::{ChatMessage, ToolCall};
+
+    let msg = |content: &str, tools: &[&str]| ChatMessage {
+        role: "assistant".to_string(),
+        content: content.to_string(),
+        timestamp: None,
+        tool_calls: tools
+            .iter()
+            .map(|name| ToolCall {
+                tool_name: name.to_string(),
+                status: "completed".to_string(),
+                file_path: None,
+            })
+            .collect(),
+        line: None,
+        thinking: None,
+    };
+    let messages = vec![
+        msg("Fix the bug", &[]),
+        msg("", &["Read", "Edit"]),
+        msg("Done, running tests", &["Bash"]),
+        msg("", &[]),
+    ];
+
+    assert_eq!(ChatFilter::All.select(&messages), [0, 1, 2, 3]);
+    assert_eq!(ChatFilter::TextOnly.select(&messages), [0, 2]);
+    assert_eq!(ChatFilter::ToolsOnly.select(&messages), [1, 2]);
+
+    // Cycling keeps the message at the top of the view as the scroll anchor
+    let mut app = detail_app(false, 100);
+    app.current_messages = messages;
+    app.message_line_starts = vec![(0, 0), (3, 1), (8, 2), (13, 3)];
+    app.chat_scroll = 9;
+    app.cycle_chat_filter();
+    assert_eq!(app.chat_filter, ChatFilter::TextOnly);
+    assert_eq!(app.scroll_anchor, Some(2));
+    app.cycle_chat_filter();
+    app.cycle_chat_filter();
+    assert_eq!(app.chat_filter, ChatFilter::All);
+}
+
+#[test]
+fn test_conversation_text_lists_messages_in_order() {
+    use lazychat::app::ChatFilter;
+    use lazychat::data::{ChatMessage, ToolCall};
+
+    let msg = |role: &str, content: &str, tool: Option<&str>| ChatMessage {
+        role: role.to_string(),
+        content: content.to_string(),
+        timestamp: None,
+        tool_calls: tool
+            .map(|name| ToolCall {
+                tool_name: name.to_string(),
+                status: "completed".to_string(),
+                file_path: Some("/repo/src/app.rs".to_string()),
+            })
+            .into_iter()
+            .collect(),
+        line: None,
+        thinking: None,
+    };
+    let mut app = detail_app(false, 0);
+    app.current_messages = vec![
+        msg("user", "Fix the bug", None),
+        msg("assistant", "", Some("Edit")),
+        msg("assistant", "Fixed it.", None),
+    ];
+
+    assert_eq!(
+        app.conversation_text(),
+        "User:\nFix the bug\n\nAssistant:\n- [Edit] /repo/src/app.rs\n\nAssistant:\nFixed it.\n\n"
+    );
+
+    // Only the messages the chat filter shows are copied
+    app.chat_filter = ChatFilter::TextOnly;
+    assert_eq!(
+        app.conversation_text(),
+        "User:\nFix the bug\n\nAssistant:\nFixed it.\n\n"
+    );
+
+    // ... and only the filtered part of a message with prose and tools
+    app.current_messages = vec![msg("assistant", "Let me edit.", Some("Edit"))];
+    app.chat_filter = ChatFilter::All;
+    assert_eq!(
+        app.conversation_text(),
+        "Assistant:\nLet me edit.\n- [Edit] /repo/src/app.rs\n\n"
+    );
+    app.chat_filter = ChatFilter::TextOnly;
+    assert_eq!(app.conversation_text(), "Assistant:\nLet me edit.\n\n");
+    app.chat_filter = ChatFilter::ToolsOnly;
+    assert_eq!(
+        app.conversation_text(),
+        "Assistant:\n- [Edit] /repo/src/app.rs\n\n"
+    );
+}
+
+#[test]
+fn test_message_blocks_merge_adjacent_same_role() {
+    use chrono::{TimeZone, Utc};
+    use lazychat::app::message_blocks;
+    use lazychat::data::ChatMessage;
+
+    let msg = |role: &str, secs: i64| ChatMessage {
+        role: role.to_string(),
+        content: "text".to_string(),
+        timestamp: Some(Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()),
+        tool_calls: Vec::new(),
+        line: None,
+        thinking: None,
+    };
+    let messages = vec![
+        msg("user", 0),
+        msg("assistant", 5),
+        msg("assistant", 20),
+        msg("assistant", 600),
+        msg("user", 610),
+    ];
+    let shown = [0, 1, 2, 3, 4];
+
+    assert_eq!(
+        message_blocks(&messages, &shown, true),
+        vec![vec![0], vec![1, 2], vec![3], vec![4]]
+    );
+    assert_eq!(message_blocks(&messages, &shown, false).len(), 5);
+}
+
+#[tokio::test]
+async fn test_reload_presets_picks_up_file_changes() {
+    use lazychat::config::PresetManager;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("presets.toml");
+    std::fs::write(&path, "[[preset]]\nname = \"one\"\ncwd = \"/tmp\"\n").unwrap();
+
+    let mut app = App::new();
+    let manager = PresetManager::load_from(path.clone()).unwrap();
+    app.presets = manager.all().to_vec();
+    app.preset_manager = Some(manager);
+    app.selected_preset_idx = 0;
+    assert_eq!(app.presets.len(), 1);
+
+    std::fs::write(
+        &path,
+        "[[preset]]\nname = \"one\"\ncwd = \"/tmp\"\n\n[[preset]]\nname = \"two\"\ncwd = \"/tmp\"\ninstances = 3\n",
+    )
+    .unwrap();
+    press(&mut app, KeyCode::Char('R'), KeyModifiers::NONE).await;
+    let names: Vec<&str> = app.presets.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["one", "two"]);
+    assert_eq!(app.presets[1].instances, 3);
+    assert_eq!(app.status_message.as_deref(), Some("Reloaded 2 presets"));
+
+    // A broken file keeps the previous presets and reports the error
+    std::fs::write(&path, "[[preset]\n").unwrap();
+    press(&mut app, KeyCode::Char('R'), KeyModifiers::NONE).await;
+    assert_eq!(app.presets.len(), 2);
+    assert!(app.status_is_error);
+}
+
+#[tokio::test]
+async fn test_files_cheatsheet_lists_only_file_keys() {
+    let mut app = App::new();
+    app.focus = Focus::Files;
+    press(&mut app, KeyCode::Char('?'), KeyModifiers::NONE).await;
+    assert!(app.show_cheatsheet);
+    assert!(!app.show_help);
+
+    let actions: Vec<&str> = app.cheatsheet_bindings().iter().map(|b| b.action).collect();
+    for wanted in ["Filter", "Tree/flat", "Yank path", "Yank all paths", "Quit"] {
+        assert!(actions.contains(&wanted), "missing {wanted}: {actions:?}");
+    }
+    for session_only in [
+        "Rename",
+        "Copy resume command",
+        "Edit tags",
+        "Spawn preset instances",
+    ] {
+        assert!(!actions.contains(&session_only), "{session_only} shown");
+    }
+
+    // Typing searches keys and actions; `?` goes to the full help only when empty
+    for c in "yank".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    let actions: Vec<&str> = app.cheatsheet_bindings().iter().map(|b| b.action).collect();
+    assert_eq!(actions, ["Yank path", "Yank all paths"]);
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert!(app.show_cheatsheet);
+    assert!(app.cheatsheet_query.is_empty());
+    press(&mut app, KeyCode::Char('?'), KeyModifiers::NONE).await;
+    assert!(!app.show_cheatsheet);
+    assert!(app.show_help);
+}
+
+#[tokio::test]
+async fn test_add_dir_prompt_threads_dirs_into_resume_command() {
+    use lazychat::terminal::resume_claude_script;
+
+    let root = tempfile::tempdir().unwrap();
+    let root = root.path().canonicalize().unwrap();
+    let project = root.join("project");
+    std::fs::create_dir(&project).unwrap();
+
+    let mut app = App::new();
+    let mut s = session("a");
+    s.project = project.display().to_string();
+    app.sessions = vec![s];
+    app.session_list_state.select(Some(0));
+    app.focus = Focus::Sessions;
+
+    // Relative entries resolve against the session's project
+    let dirs = app.resolve_add_dirs("  ..  ").unwrap();
+    assert_eq!(dirs, [root.display().to_string()]);
+
+    let shared = root.join("shared");
+    std::fs::create_dir(&shared).unwrap();
+    let dirs = app.resolve_add_dirs("../shared").unwrap();
+    let script = resume_claude_script(&app.sessions[0].project_dir(), "a", &dirs);
+    assert!(
+        script.starts_with(&format!("cd '{}'", project.display())),
+        "{script}"
+    );
+    assert!(
+        script.ends_with(&format!(
+            "claude --resume a --dangerously-skip-permissions --add-dir '{}'",
+            shared.display()
+        )),
+        "{script}"
+    );
+
+    // A bad entry keeps the terminal closed and says which one
+    press(&mut app, KeyCode::Char('A'), KeyModifiers::NONE).await;
+    assert!(app.add_dir_prompt && app.input_active());
+    for c in "../shared nope".chars() {
+        press(&mut app, KeyCode::Char(c), KeyModifiers::NONE).await;
+    }
+    press(&mut app, KeyCode::Enter, KeyModifiers::NONE).await;
+    assert!(!app.add_dir_prompt);
+    assert!(app.embedded_terminal.is_none());
+    assert!(app.status_is_error);
+    assert_eq!(app.status_message.as_deref(), Some("Not a directory: nope"));
+}
+
+#[test]
+fn test_selection_debounce_waits_for_rapid_changes_to_settle() {
+    use lazychat::events::{SelectionDebounce, SELECT_DEBOUNCE};
+    use std::time::{Duration, Instant};
+
+    let key = |id: &str| Some(session(id).key());
+    let start = Instant::now();
+    let at = |ms: u64| start + Duration::from_millis(ms);
+    let mut debounce = SelectionDebounce::default();
+
+    // Holding j: every selection is replaced before it settles
+    for (i, id) in ["a", "b", "c", "d"].into_iter().enumerate() {
+        assert!(!debounce.should_load(key(id), at(i as u64 * 30)));
+    }
+    assert!(!debounce.should_load(key("d"), at(90 + 100)));
+    assert!(debounce.should_load(key("d"), at(90) + SELECT_DEBOUNCE));
+    // Loaded once; staying put doesn't reload
+    assert!(!debounce.should_load(key("d"), at(1000)));
+
+    // Moving away and back before it settles keeps the loaded session
+    assert!(!debounce.should_load(key("e"), at(1100)));
+    assert!(!debounce.should_load(key("d"), at(1120)));
+    assert!(!debounce.should_load(key("d"), at(2000)));
+
+    // Clearing the selection also settles before it counts
+    assert!(!debounce.should_load(None, at(3000)));
+    assert!(debounce.should_load(None, at(3200)));
+}
+
+#[tokio::test]
+async fn test_presets_merge_includes_directory() {
+    use lazychat::config::PresetManager;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("presets.toml");
+    let includes = dir.path().join("presets.d");
+    std::fs::create_dir(&includes).unwrap();
+    std::fs::write(
+        &path,
+        "[[preset]]\nname = \"api\"\ncwd = \"/tmp/api\"\n\n[[preset]]\nname = \"web\"\ncwd = \"/tmp/web\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        includes.join("10-team.toml"),
+        "[[preset]]\nname = \"web\"\ncwd = \"/tmp/web\"\ninstances = 3\n\n[[preset]]\nname = \"docs\"\ncwd = \"/tmp/docs\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        includes.join("20-mine.toml"),
+        "[[preset]]\nname = \"docs\"\ncwd = \"/tmp/mine\"\n",
+    )
+    .unwrap();
+    std::fs::write(includes.join("notes.txt"), "not toml").unwrap();
+
+    let manager = PresetManager::load_from(path.clone()).unwrap();
+    let names: Vec<&str> = manager.all().iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, ["api", "web", "docs"]);
+    // Later files win, keeping the first position
+    assert_eq!(manager.find_by_name("web").unwrap().instances, 3);
+    assert_eq!(manager.find_by_name("docs").unwrap().cwd, "/tmp/mine");
+    assert_eq!(manager.duplicates(), ["web", "docs"]);
+
+    let mut app = App::new();
+    app.preset_manager = Some(manager);
+    std::fs::remove_file(includes.join("20-mine.toml")).unwrap();
+    press(&mut app, KeyCode::Char('R'), KeyModifiers::NONE).await;
+    assert_eq!(app.presets.len(), 3);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Reloaded 3 presets (overridden: web)")
+    );
+
+    // A broken include names the file
+    std::fs::write(includes.join("30-bad.toml"), "[[preset]\n").unwrap();
+    press(&mut app, KeyCode::Char('R'), KeyModifiers::NONE).await;
+    assert!(app.status_is_error);
+    assert!(app.status_message.unwrap().contains("30-bad.toml"));
+}
+
+#[tokio::test]
+async fn test_reloads_clamp_selection_indices() {
+    use lazychat::config::PresetManager;
+
+    // Sessions: the claude dir now holds a single transcript
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("projects").join("-a-proj");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(project.join("only.jsonl"), "").unwrap();
+    let mut app = App::with_sessions(vec![session("a"), session("b"), session("c")])
+        .with_claude_dir(dir.path());
+    app.session_list_state.select(Some(2));
+    app.load_data().await.unwrap();
+    assert_eq!(app.session_list_state.selected(), Some(0));
+    assert_eq!(app.selected_session().unwrap().id, "only");
+
+    // Presets: the file shrinks from three entries to one
+    let path = dir.path().join("presets.toml");
+    let preset = |name: &str| format!("[[preset]]\nname = \"{name}\"\ncwd = \"/tmp\"\n\n");
+    std::fs::write(&path, preset("one") + &preset("two") + &preset("three")).unwrap();
+    let manager = PresetManager::load_from(path.clone()).unwrap();
+    app.presets = manager.all().to_vec();
+    app.preset_manager = Some(manager);
+    app.selected_preset_idx = 2;
+    std::fs::write(&path, preset("one")).unwrap();
+    app.reload_presets();
+    assert_eq!(app.selected_preset_idx, 0);
+    assert_eq!(app.selected_preset().unwrap().name, "one");
+
+    // Files: fewer changed files than the selected index
+    app.current_file_changes = vec![file("a.rs"), file("b.rs"), file("c.rs")];
+    app.selected_file_idx = 2;
+    app.current_file_changes.truncate(1);
+    app.clamp_selections();
+    assert_eq!(app.selected_file_idx, 0);
+}
+
+#[tokio::test]
+async fn test_compare_holds_two_sessions_side_by_side() {
+    use lazychat::ui::compare::compare_layout;
+    use ratatui::layout::Rect;
+
+    let mut app = App::with_sessions(vec![session("a"), session("b"), session("c")]);
+    app.focus = Focus::Sessions;
+
+    // Pressing x twice on the same session only toggles the mark
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+    assert_eq!(app.compare_mark, Some(session("a").key()));
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+    assert!(app.compare_mark.is_none() && app.compare.is_none());
+
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('x'), KeyModifiers::NONE).await;
+    let compare = app.compare.as_ref().expect("compare view open");
+    assert_eq!(
+        compare.session_keys(),
+        [session("a").key().as_str(), session("c").key().as_str()]
+    );
+    assert_eq!(compare.active, 0);
+
+    // Each pane scrolls on its own
+    app.compare.as_mut().unwrap().panes[1].scroll_max = 50;
+    press(&mut app, KeyCode::Tab, KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('g'), KeyModifiers::NONE).await;
+    press(&mut app, KeyCode::Char('j'), KeyModifiers::NONE).await;
+    let panes = &app.compare.as_ref().unwrap().panes;
+    assert_eq!(panes[1].scroll, 3);
+    assert_eq!(panes[0].scroll, SCROLL_BOTTOM);
+    assert_eq!(app.chat_scroll, SCROLL_BOTTOM);
+
+    let [left, right] = compare_layout(Rect::new(0, 0, 120, 40));
+    assert_eq!((left.x, left.width, left.height), (0, 60, 40));
+    assert_eq!((right.x, right.width, right.height), (60, 60, 40));
+
+    press(&mut app, KeyCode::Esc, KeyModifiers::NONE).await;
+    assert!(app.compare.is_none());
+}
+
+#[tokio::test]
+async fn test_ctrl_r_reloads_file_stats() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().canonicalize().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    };
+    let file = repo.join("a.txt");
+    std::fs::write(&file, "one\n").unwrap();
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+
+    // A chat that edited a.txt
+    let transcript = dir.path().join("a.jsonl");
+    std::fs::write(
+        &transcript,
+        format!(
+            r#"{{"type":"assistant","message":{{"content":[{{"type":"tool_use","name":"Edit","input":{{"file_path":"{}"}}}}]}}}}"#,
+            file.display()
+        ),
+    )
+    .unwrap();
+    let mut sess = session("a");
+    sess.project = repo.display().to_string();
+    sess.file_path = Some(transcript);
+    let mut app = App::with_sessions(vec![sess]);
+    app.focus = Focus::Sessions;
+    app.load_session_messages().await.unwrap();
+    let additions = |app: &App| app.current_file_changes[0].additions;
+    assert_eq!(additions(&app), 0);
+
+    std::fs::write(&file, "one\ntwo\n").unwrap();
+    press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL).await;
+    assert_eq!(additions(&app), 1);
+    assert!(!app.renaming);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Refreshed 1 changed files")
+    );
+
+    // Works from the chat too, not from presets
+    std::fs::write(&file, "one\ntwo\nthree\n").unwrap();
+    app.focus = Focus::Detail;
+    press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL).await;
+    assert_eq!(additions(&app), 2);
+    std::fs::write(&file, "one\ntwo\nthree\nfour\n").unwrap();
+    app.focus = Focus::Presets;
+    press(&mut app, KeyCode::Char('r'), KeyModifiers::CONTROL).await;
+    assert_eq!(additions(&app), 2);
+}
+
+#[test]
+fn test_config_edits_reload_theme_live() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    // Bump the mtime explicitly so the change is seen on coarse-clock filesystems
+    let write = |content: &str, secs: u64| {
+        std::fs::write(&path, content).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    };
+    write("[theme]\ndiff_add = \"green\"\n", 1_000);
+
+    let mut app = App::new();
+    app.settings_path = path.clone();
+    app.load_settings().unwrap();
+    app.reload_theme_if_changed();
+    assert_eq!(app.settings.theme.diff_add, "green");
+    assert_eq!(app.status_message, None);
+
+    write("[theme]\ndiff_add = \"#00ff88\"\n", 2_000);
+    app.reload_theme_if_changed();
+    assert_eq!(app.settings.theme.diff_add, "#00ff88");
+
+    // A broken config keeps the old theme and reports why
+    write("[theme\ndiff_add = \"blue\"\n", 3_000);
+    app.reload_theme_if_changed();
+    assert_eq!(app.settings.theme.diff_add, "#00ff88");
+    assert!(app.status_is_error);
+}
+
+#[tokio::test]
+async fn test_brace_keys_hop_between_user_prompts() {
+    use lazychat::app::user_message_target;
+    use lazychat::data::ChatMessage;
+
+    let msg = |role: &str| ChatMessage {
+        role: role.to_string(),
+        content: String::new(),
+        timestamp: None,
+        tool_calls: Vec::new(),
+        line: None,
+        thinking: None,
+    };
+    let messages = vec![msg("user"), msg("assistant"), msg("user"), msg("assistant")];
+    let starts = vec![(0, 0), (4, 1), (20, 2), (25, 3)];
+
+    assert_eq!(user_message_target(&starts, &messages, 0, true), Some(20));
+    assert_eq!(user_message_target(&starts, &messages, 20, true), None);
+    assert_eq!(user_message_target(&starts, &messages, 25, false), Some(20));
+    assert_eq!(user_message_target(&starts, &messages, 20, false), Some(0));
+    assert_eq!(user_message_target(&starts, &messages, 0, false), None);
+
+    // From the bottom of a 40-line chat (max offset 30), `{` lands on row 20
+    let mut app = App::new();
+    app.focus = Focus::Detail;
+    app.current_messages = messages;
+    app.message_line_starts = starts;
+    app.chat_scroll_max = 30;
+    assert_eq!(app.chat_scroll, SCROLL_BOTTOM);
+    press(&mut app, KeyCode::Char('{'), KeyModifiers::NONE).await;
+    assert_eq!(app.scroll_offset(), 20);
+    press(&mut app, KeyCode::Char('{'), KeyModifiers::NONE).await;
+    assert_eq!(app.scroll_offset(), 0);
+    press(&mut app, KeyCode::Char('}'), KeyModifiers::NONE).await;
+    assert_eq!(app.scroll_offset(), 20);
+}
+
+#[tokio::test]
+async fn test_open_all_files_asks_first_when_many() {
+    let mut app = App::with_sessions(vec![session("a")]);
+    app.focus = Focus::Files;
+    app.current_file_changes = (0..=OPEN_ALL_CONFIRM_OVER)
+        .map(|i| file(&format!("/repo/src/f{i}.rs")))
+        .collect();
+
+    press(&mut app, KeyCode::Char('O'), KeyModifiers::NONE).await;
+    assert!(app.pending_open_all && !app.terminal_mode);
+    assert_eq!(
+        app.status_message.as_deref(),
+        Some("Open all 21 files in the editor? (y/n)")
+    );
+
+    press(&mut app, KeyCode::Char('n'), KeyModifiers::NONE).await;
+    assert!(!app.pending_open_all && !app.terminal_mode);
+    assert_eq!(app.status_message.as_deref(), Some("Open cancelled"));
+}
+
+#[tokio::test]
+async fn test_reload_keeps_selected_session_when_list_reorders() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("projects").join("-tmp-p");
+    std::fs::create_dir_all(&project).unwrap();
+    let touch = |id: &str, secs: u64| {
+        let path = project.join(format!("{id}.jsonl"));
+        std::fs::write(
+            &path,
+            r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+        )
+        .unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    };
+    touch("old", 1_000);
+    touch("new", 2_000);
+
+    let mut app = App::with_sessions(Vec::new()).with_claude_dir(dir.path());
+    app.load_data().await.unwrap();
+    app.session_list_state.select(Some(1));
+    assert_eq!(app.selected_session().unwrap().id, "old");
+
+    // "old" becomes the most recent and moves to the top
+    touch("old", 3_000);
+    app.load_data().await.unwrap();
+    assert_eq!(app.sessions[0].id, "old");
+    assert_eq!(app.session_list_state.selected(), Some(0));
+    assert_eq!(app.selected_session().unwrap().id, "old");
+
+    // A vanished session leaves the selection on the nearest row
+    std::fs::remove_file(project.join("old.jsonl")).unwrap();
+    app.load_data().await.unwrap();
+    assert_eq!(app.selected_session().unwrap().id, "new");
+}
+
+#[test]
+fn test_kill_all_processes_reports_count() {
+    use lazychat::process::registry::ProcessRegistry;
+
+    let mut app = App::with_sessions(vec![session("a")]);
+    assert_eq!(app.kill_all_processes().unwrap(), 0);
+
+    let dir = tempfile::tempdir().unwrap();
+    let mut registry = ProcessRegistry::load_from(dir.path().join("processes.json")).unwrap();
+    let mut children: Vec<_> = (0..2)
+        .map(|i| {
+            let child = std::process::Command::new("sleep")
+                .arg("30")
+                .spawn()
+                .unwrap();
+            registry
+                .register_process(child.id(), format!("s{i}"), None, i, "/tmp".into(), vec![])
+                .unwrap();
+            child
+        })
+        .collect();
+    app.process_registry = Some(registry);
+
+    assert_eq!(app.kill_all_processes().unwrap(), 2);
+    assert_eq!(app.status_message.as_deref(), Some("Killed 2 processes"));
+    assert!(!app.status_is_error);
+    assert!(app
+        .process_registry
+        .as_ref()
+        .unwrap()
+        .get_all_processes()
+        .is_empty());
+    for child in &mut children {
+        assert!(!child.wait().unwrap().success());
+    }
+}
+
+#[tokio::test]
+async fn test_broken_project_file_is_reported_once() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let project = dir.path().join("claude").join("projects").join("-repo");
+    std::fs::create_dir_all(&project).unwrap();
+    std::fs::write(
+        project.join("s.jsonl"),
+        format!(r#"{{"type":"user","cwd":"{}"}}"#, repo.display()) + "\n",
+    )
+    .unwrap();
+    let meta_path = repo.join(".lazychat.toml");
+    let write_meta = |content: &str, secs: u64| {
+        std::fs::write(&meta_path, content).unwrap();
+        std::fs::File::options()
+            .write(true)
+            .open(&meta_path)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+            .unwrap();
+    };
+    write_meta("name = ", 1_000);
+
+    let mut app = App::with_sessions(Vec::new()).with_claude_dir(dir.path().join("claude"));
+    app.load_data().await.unwrap();
+    assert!(app.status_is_error);
+    let status = app.status_message.take().unwrap();
+    assert!(status.starts_with("Failed to parse"), "{status}");
+    assert_eq!(app.sessions[0].project_name, "repo");
+
+    // Same broken file: not reported again
+    app.load_data().await.unwrap();
+    assert_eq!(app.status_message, None);
+
+    write_meta("name = \"Billing\"\n", 2_000);
+    app.load_data().await.unwrap();
+    assert_eq!(app.sessions[0].project_name, "Billing");
+}
@@ -256,7 +256,7 @@ instances = 1
         let home = dirs::home_dir().expect("Should have home dir");
         let home_str = home.to_string_lossy();
 
-        let paths = vec![
+        let paths = [
             "~/dev/shared".to_string(),
             "/absolute/lib".to_string(),
             "relative/path".to_string(),
@@ -274,9 +274,9 @@ instances = 1
 
     // Helper function for tilde expansion
     fn expand_tilde(path: &str) -> String {
-        if path.starts_with("~/") {
+        if let Some(rest) = path.strip_prefix("~/") {
             if let Some(home) = dirs::home_dir() {
-                return home.join(&path[2..]).to_string_lossy().to_string();
+                return home.join(rest).to_string_lossy().to_string();
             }
         } else if path == "~" {
             if let Some(home) = dirs::home_dir() {
@@ -289,7 +289,7 @@ instances = 1
 
 #[cfg(test)]
 mod preset_find_tests {
-    use super::*;
+
     use serde::{Deserialize, Serialize};
 
     #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -484,7 +484,7 @@ mod fuzzy_search_tests {
             .collect();
 
         // Sort by score descending
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by_key(|r| std::cmp::Reverse(r.1));
         results
     }
 
@@ -586,12 +586,11 @@ mod fuzzy_search_tests {
 
 #[cfg(test)]
 mod expand_tilde_tests {
-    use super::*;
 
     fn expand_tilde(path: &str) -> String {
-        if path.starts_with("~/") {
+        if let Some(rest) = path.strip_prefix("~/") {
             if let Some(home) = dirs::home_dir() {
-                return home.join(&path[2..]).to_string_lossy().to_string();
+                return home.join(rest).to_string_lossy().to_string();
             }
         } else if path == "~" {
             if let Some(home) = dirs::home_dir() {
@@ -754,7 +753,7 @@ instances = 1
         .iter()
         .filter_map(|p| matcher.fuzzy_match(&p.name, "lazy").map(|score| (p, score)))
         .collect();
-    results.sort_by(|a, b| b.1.cmp(&a.1));
+    results.sort_by_key(|r| std::cmp::Reverse(r.1));
 
     assert!(!results.is_empty());
     assert_eq!(results[0].0.name, "lazychat");
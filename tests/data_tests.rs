@@ -0,0 +1,1155 @@
+//! Tests for session data structures and loading
+
+use lazychat::config::settings::{DescriptionRules, Settings};
+use lazychat::data::git::{file_changes, file_changes_from_stats, parse_git_stats};
+use lazychat::data::sidecar::{parse_tags, tags_match, SidecarStore};
+use lazychat::data::{
+    claude::{ClaudeData, TailCursor},
+    edited_files, message_counts, transcript_markdown, ChatMessage, FileStatus, MessageCounts,
+    Session, ThinkingStats, TodoItem, ToolCall,
+};
+use std::fs;
+
+fn session(id: &str, todos: Vec<TodoItem>) -> Session {
+    Session {
+        id: id.to_string(),
+        project: "/home/user/dev/project".to_string(),
+        project_name: "project".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 1,
+        status: "idle".to_string(),
+        todos,
+        file_path: None,
+    }
+}
+
+fn todo(id: &str, status: &str) -> TodoItem {
+    TodoItem {
+        id: id.to_string(),
+        content: format!("todo {id}"),
+        status: status.to_string(),
+    }
+}
+
+#[test]
+fn test_todo_progress_counts_completed() {
+    let s = session(
+        "abc",
+        vec![
+            todo("1", "completed"),
+            todo("2", "in_progress"),
+            todo("3", "pending"),
+            todo("4", "completed"),
+        ],
+    );
+    assert_eq!(s.todo_progress(), Some((2, 4)));
+}
+
+#[test]
+fn test_todo_progress_none_without_todos() {
+    assert_eq!(session("abc", vec![]).todo_progress(), None);
+}
+
+#[test]
+fn test_parse_agent_tree_two_levels() {
+    let transcript = [
+        r#"{"type":"user","uuid":"m1","parentUuid":null,"message":{"role":"user","content":"Refactor the parser"}}"#,
+        r#"{"type":"assistant","uuid":"m2","parentUuid":"m1","message":{"role":"assistant","content":[]}}"#,
+        r#"{"type":"user","isSidechain":true,"agentId":"explore1","uuid":"a1","parentUuid":"m2","timestamp":"2025-01-01T10:00:00Z","message":{"role":"user","content":"Explore the lexer\nin depth"}}"#,
+        r#"{"type":"assistant","isSidechain":true,"agentId":"explore1","uuid":"a2","parentUuid":"a1","message":{"role":"assistant","content":[]}}"#,
+        r#"{"type":"user","isSidechain":true,"agentId":"nested1","uuid":"b1","parentUuid":"a2","message":{"role":"user","content":[{"type":"text","text":"Check token spans"}]}}"#,
+        r#"{"type":"user","isSidechain":true,"agentId":"review1","uuid":"c1","parentUuid":"m2","message":{"role":"user","content":"Review the diff"}}"#,
+        "not json",
+    ]
+    .join("\n");
+
+    let tree = ClaudeData::parse_agent_tree("sess", &transcript);
+
+    assert_eq!(tree.len(), 2);
+    assert_eq!(tree[0].id, "explore1");
+    assert_eq!(tree[0].parent_id, None);
+    assert_eq!(tree[0].session_id, "sess");
+    assert_eq!(tree[0].description, "Explore the lexer");
+    assert!(tree[0].started_at.is_some());
+    assert_eq!(tree[1].id, "review1");
+    assert!(tree[1].children.is_empty());
+
+    let nested = &tree[0].children;
+    assert_eq!(nested.len(), 1);
+    assert_eq!(nested[0].id, "nested1");
+    assert_eq!(nested[0].parent_id.as_deref(), Some("explore1"));
+    assert_eq!(nested[0].description, "Check token spans");
+    assert!(nested[0].children.is_empty());
+}
+
+#[tokio::test]
+async fn test_cached_agent_tree_follows_new_subagent_files() {
+    use lazychat::data::claude::LoadCache;
+
+    let dir = tempfile::tempdir().unwrap();
+    let transcript = dir.path().join("sess.jsonl");
+    fs::write(
+        &transcript,
+        r#"{"type":"user","isSidechain":true,"agentId":"explore1","uuid":"a1","parentUuid":"m1","message":{"role":"user","content":"Explore"}}"#,
+    )
+    .unwrap();
+    let mut s = session("sess", vec![]);
+    s.file_path = Some(transcript.clone());
+
+    let mut cache = LoadCache::default();
+    let ids = |agents: Vec<lazychat::data::Agent>| -> Vec<String> {
+        agents.into_iter().map(|a| a.id).collect()
+    };
+    assert_eq!(ids(cache.session_agents(&s).await.unwrap()), ["explore1"]);
+    assert_eq!(ids(cache.session_agents(&s).await.unwrap()), ["explore1"]);
+
+    let subagents = dir.path().join("sess").join("subagents");
+    fs::create_dir_all(&subagents).unwrap();
+    fs::write(
+        subagents.join("agent-review1.jsonl"),
+        r#"{"type":"user","isSidechain":true,"agentId":"review1","uuid":"b1","parentUuid":"m1","message":{"role":"user","content":"Review"}}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        ids(cache.session_agents(&s).await.unwrap()),
+        ["explore1", "review1"]
+    );
+}
+
+#[tokio::test]
+async fn test_colliding_session_ids_stay_distinct() {
+    let dir = tempfile::tempdir().unwrap();
+    let claude_dir = dir.path();
+    for project in ["-a-proj", "-b-proj"] {
+        let project_dir = claude_dir.join("projects").join(project);
+        fs::create_dir_all(&project_dir).unwrap();
+        fs::write(project_dir.join("dup.jsonl"), "").unwrap();
+    }
+    fs::write(
+        claude_dir.join("history.jsonl"),
+        [
+            r#"{"display":"Fix the alpha build","sessionId":"dup","project":"/a/proj"}"#,
+            r#"{"display":"Write the beta docs","sessionId":"dup","project":"/b/proj"}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let mut data = ClaudeData::load_from(claude_dir, &Settings::default())
+        .await
+        .unwrap();
+    data.sessions.sort_by(|a, b| a.project.cmp(&b.project));
+
+    assert_eq!(data.sessions.len(), 2);
+    let (a, b) = (&data.sessions[0], &data.sessions[1]);
+    assert_eq!(a.id, "dup");
+    assert_eq!(b.id, "dup");
+    assert_ne!(a.key(), b.key());
+    assert_eq!(a.description.as_deref(), Some("Fix the alpha build"));
+    assert_eq!(b.description.as_deref(), Some("Write the beta docs"));
+
+    // Names stored for one project's session don't leak to the other
+    let mut sidecar = SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap();
+    sidecar
+        .set_name(&a.key(), Some("alpha".to_string()))
+        .unwrap();
+    let reloaded = SidecarStore::load_from(dir.path().join("sidecar.json")).unwrap();
+    reloaded.apply(&mut data.sessions);
+    assert_eq!(data.sessions[0].custom_name.as_deref(), Some("alpha"));
+    assert_eq!(data.sessions[1].custom_name, None);
+}
+
+#[test]
+fn test_corrupted_sidecar_is_an_error_and_kept() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sidecar.json");
+    fs::write(&path, "{not json").unwrap();
+
+    let err = SidecarStore::load_from(path.clone()).err().unwrap();
+    assert!(format!("{err:#}").starts_with("Corrupted session sidecar"));
+    assert_eq!(fs::read_to_string(&path).unwrap(), "{not json");
+}
+
+#[tokio::test]
+async fn test_description_falls_back_to_transcript() {
+    let dir = tempfile::tempdir().unwrap();
+    let project_dir = dir.path().join("projects").join("-a-proj");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::write(
+        project_dir.join("s1.jsonl"),
+        [
+            r#"{"type":"user","message":{"role":"user","content":"<command-name>/clear</command-name>"}}"#,
+            r#"{"type":"user","message":{"role":"user","content":"hi"}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Hello there, friend"}]}}"#,
+            r#"{"type":"user","message":{"role":"user","content":[{"type":"text","text":"Add retries to the uploader"}]}}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let data = ClaudeData::load_from(dir.path(), &Settings::default())
+        .await
+        .unwrap();
+    assert_eq!(
+        data.sessions[0].description.as_deref(),
+        Some("Add retries to the uploader")
+    );
+
+    // A lower threshold accepts the short greeting first
+    let settings = Settings {
+        descriptions: DescriptionRules {
+            min_length: 2,
+            ..DescriptionRules::default()
+        },
+        ..Settings::default()
+    };
+    let data = ClaudeData::load_from(dir.path(), &settings).await.unwrap();
+    assert_eq!(data.sessions[0].description.as_deref(), Some("hi"));
+}
+
+#[tokio::test]
+async fn test_started_at_comes_from_first_message() {
+    let dir = tempfile::tempdir().unwrap();
+    let project_dir = dir.path().join("projects").join("-a-proj");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::write(
+        project_dir.join("s1.jsonl"),
+        [
+            r#"{"type":"summary","summary":"Uploader retries"}"#,
+            r#"{"type":"user","timestamp":"2024-03-01T09:00:00Z","message":{"role":"user","content":"Add retries"}}"#,
+            r#"{"type":"assistant","timestamp":"2024-03-01T09:05:00Z","message":{"role":"assistant","content":"Done"}}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+    fs::write(project_dir.join("s2.jsonl"), "{}").unwrap();
+
+    let data = ClaudeData::load_from(dir.path(), &Settings::default())
+        .await
+        .unwrap();
+    let s1 = data.sessions.iter().find(|s| s.id == "s1").unwrap();
+    assert_eq!(
+        s1.started_at.unwrap().to_rfc3339(),
+        "2024-03-01T09:00:00+00:00"
+    );
+    assert_ne!(s1.started_at, s1.last_activity);
+    assert!(s1.duration().unwrap().num_seconds() > 0);
+
+    // Without any timestamp the file's mtime is used for both
+    let s2 = data.sessions.iter().find(|s| s.id == "s2").unwrap();
+    assert!(s2.started_at.is_some());
+    assert_eq!(s2.started_at, s2.last_activity);
+}
+
+#[tokio::test]
+async fn test_history_description_uses_rules() {
+    let dir = tempfile::tempdir().unwrap();
+    let project_dir = dir.path().join("projects").join("-a-proj");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::write(project_dir.join("s1.jsonl"), "").unwrap();
+    fs::write(
+        dir.path().join("history.jsonl"),
+        [
+            r#"{"display":"!ls -la","sessionId":"s1","project":"/a/proj"}"#,
+            r#"{"display":"Review the PR","sessionId":"s1","project":"/a/proj"}"#,
+        ]
+        .join("\n"),
+    )
+    .unwrap();
+
+    let data = ClaudeData::load_from(dir.path(), &Settings::default())
+        .await
+        .unwrap();
+    assert_eq!(data.sessions[0].description.as_deref(), Some("!ls -la"));
+
+    let settings = Settings {
+        descriptions: DescriptionRules {
+            skip_prefixes: vec!["!".to_string()],
+            ..DescriptionRules::default()
+        },
+        ..Settings::default()
+    };
+    let data = ClaudeData::load_from(dir.path(), &settings).await.unwrap();
+    assert_eq!(
+        data.sessions[0].description.as_deref(),
+        Some("Review the PR")
+    );
+}
+
+#[tokio::test]
+async fn test_note_round_trips_through_sidecar() {
+    let dir = tempfile::tempdir().unwrap();
+    let project_dir = dir.path().join("projects").join("-a-proj");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::write(project_dir.join("s1.jsonl"), "").unwrap();
+    let sidecar_path = dir.path().join("sidecar.json");
+
+    let data = ClaudeData::load_from(dir.path(), &Settings::default())
+        .await
+        .unwrap();
+    let key = data.sessions[0].key();
+
+    let mut sidecar = SidecarStore::load_from(sidecar_path.clone()).unwrap();
+    sidecar
+        .set_note(&key, Some("Migrating auth\nblocked on review".to_string()))
+        .unwrap();
+
+    // Fresh load of both sessions and sidecar finds the note again
+    let data = ClaudeData::load_from(dir.path(), &Settings::default())
+        .await
+        .unwrap();
+    let sidecar = SidecarStore::load_from(sidecar_path.clone()).unwrap();
+    assert_eq!(
+        sidecar.note(&data.sessions[0].key()),
+        Some("Migrating auth\nblocked on review")
+    );
+
+    // Blank notes clear the entry
+    let mut sidecar = sidecar;
+    sidecar.set_note(&key, Some("  \n".to_string())).unwrap();
+    let sidecar = SidecarStore::load_from(sidecar_path).unwrap();
+    assert_eq!(sidecar.note(&key), None);
+    assert!(sidecar.get(&key).is_none());
+}
+
+#[test]
+fn test_parse_tags_trims_and_dedupes() {
+    assert_eq!(
+        parse_tags(" backend, urgent,,backend , ui "),
+        vec!["backend", "urgent", "ui"]
+    );
+    assert!(parse_tags(" , ").is_empty());
+}
+
+#[test]
+fn test_tags_persist_in_sidecar() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sidecar.json");
+    let key = session("s1", Vec::new()).key();
+
+    let mut sidecar = SidecarStore::load_from(path.clone()).unwrap();
+    sidecar
+        .set_tags(&key, parse_tags("backend, urgent"))
+        .unwrap();
+
+    let mut sidecar = SidecarStore::load_from(path.clone()).unwrap();
+    assert_eq!(sidecar.tags(&key), ["backend", "urgent"]);
+    assert!(sidecar.tags("other:s1").is_empty());
+
+    sidecar.set_tags(&key, Vec::new()).unwrap();
+    let sidecar = SidecarStore::load_from(path).unwrap();
+    assert!(sidecar.get(&key).is_none());
+}
+
+#[test]
+fn test_tags_match_predicate() {
+    let tags = vec!["backend".to_string(), "urgent".to_string()];
+    assert!(tags_match(&tags, ""));
+    assert!(tags_match(&tags, "backend"));
+    assert!(tags_match(&tags, "bknd"));
+    assert!(tags_match(&tags, "urg back"));
+    assert!(tags_match(&tags, "urg,back"));
+    assert!(!tags_match(&tags, "frontend"));
+    assert!(!tags_match(&tags, "urg front"));
+    assert!(!tags_match(&[], "backend"));
+}
+
+#[test]
+fn test_parse_combined_git_stats_into_file_changes() {
+    let numstat = "3\t1\tsrc/main.rs\0-\t-\tassets/logo.png\x0010\t0\tdocs/with space.md\0";
+    let status = [
+        " M src/main.rs",
+        "?? notes.txt",
+        "A  src/new.rs",
+        " D old.rs",
+        "R  after.rs\0before.rs",
+        " M assets/logo.png",
+        " M docs/with space.md",
+    ]
+    .join("\0");
+
+    let stats = parse_git_stats(numstat, &status);
+    let root = std::path::Path::new("/repo");
+    let files: Vec<String> = [
+        "src/main.rs",
+        "notes.txt",
+        "src/new.rs",
+        "old.rs",
+        "after.rs",
+        "assets/logo.png",
+        "docs/with space.md",
+        "clean.rs",
+    ]
+    .iter()
+    .map(|p| format!("/repo/{p}"))
+    .collect();
+
+    let changes = file_changes_from_stats(root, &files, &stats);
+    let summary: Vec<(&str, FileStatus, u32, u32)> = changes
+        .iter()
+        .map(|c| {
+            (
+                c.filename.as_str(),
+                c.status.clone(),
+                c.additions,
+                c.deletions,
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        summary,
+        vec![
+            ("main.rs", FileStatus::Modified, 3, 1),
+            ("notes.txt", FileStatus::Untracked, 0, 0),
+            ("new.rs", FileStatus::Added, 0, 0),
+            ("old.rs", FileStatus::Deleted, 0, 0),
+            ("after.rs", FileStatus::Renamed, 0, 0),
+            ("logo.png", FileStatus::Modified, 0, 0),
+            ("with space.md", FileStatus::Modified, 10, 0),
+            ("clean.rs", FileStatus::Modified, 0, 0),
+        ]
+    );
+    assert_eq!(changes[0].path, "/repo/src/main.rs");
+}
+
+#[test]
+fn test_parse_rename_keeps_old_and_new_paths() {
+    let status = "R  src/new.rs\0src/old name.rs\0RM core.rs\0lib.rs\0";
+    let numstat = "2\t1\tcore.rs\0";
+    let stats = parse_git_stats(numstat, status);
+
+    let files = vec!["/repo/src/new.rs".to_string(), "/repo/core.rs".to_string()];
+    let changes = file_changes_from_stats(std::path::Path::new("/repo"), &files, &stats);
+
+    assert_eq!(changes[0].status, FileStatus::Renamed);
+    assert_eq!(
+        changes[0].renamed_from.as_deref(),
+        Some("/repo/src/old name.rs")
+    );
+    assert_eq!(
+        changes[0].display_path(),
+        "/repo/src/old name.rs → /repo/src/new.rs"
+    );
+
+    // Worktree edits on top of a rename keep the rename
+    assert_eq!(changes[1].status, FileStatus::Renamed);
+    assert_eq!(changes[1].renamed_from.as_deref(), Some("/repo/lib.rs"));
+    assert_eq!((changes[1].additions, changes[1].deletions), (2, 1));
+}
+
+#[tokio::test]
+async fn test_file_changes_from_real_repo() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().canonicalize().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    };
+
+    git(&["init", "-q"]);
+    fs::write(repo.join("tracked.txt"), "one\ntwo\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    fs::write(repo.join("tracked.txt"), "one\nthree\nfour\n").unwrap();
+    fs::write(repo.join("fresh.txt"), "new\n").unwrap();
+
+    let files = vec![
+        repo.join("tracked.txt").display().to_string(),
+        repo.join("fresh.txt").display().to_string(),
+    ];
+    // A lossy project dir falls back to the files' directory
+    let changes = file_changes(&repo.join("missing-project"), &files).await;
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].status, FileStatus::Modified);
+    assert_eq!((changes[0].additions, changes[0].deletions), (2, 1));
+    assert_eq!(changes[1].status, FileStatus::Untracked);
+}
+
+#[test]
+fn test_parse_numstat_rename_record() {
+    // `-z` renames leave the path empty and follow with old and new paths
+    let numstat = "4\t2\t\0old.rs\0new.rs\0";
+    let stats = parse_git_stats(numstat, "");
+    let stat = &stats["new.rs"];
+    assert_eq!((stat.additions, stat.deletions), (4, 2));
+    assert!(!stats.contains_key("old.rs"));
+}
+
+#[tokio::test]
+async fn test_file_changes_untracked_dir_and_quoted_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().canonicalize().unwrap();
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {args:?}");
+    };
+
+    git(&["init", "-q"]);
+    fs::write(repo.join("mod é.rs"), "one\n").unwrap();
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "init"]);
+    fs::write(repo.join("mod é.rs"), "one\ntwo\n").unwrap();
+    fs::write(repo.join("spa ce.rs"), "new\n").unwrap();
+    fs::create_dir(repo.join("newdir")).unwrap();
+    fs::write(repo.join("newdir/lib.rs"), "new\n").unwrap();
+
+    let files: Vec<String> = ["mod é.rs", "spa ce.rs", "newdir/lib.rs"]
+        .iter()
+        .map(|name| repo.join(name).display().to_string())
+        .collect();
+    let changes = file_changes(&repo, &files).await;
+
+    let summary: Vec<(FileStatus, u32)> = changes
+        .iter()
+        .map(|c| (c.status.clone(), c.additions))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (FileStatus::Modified, 1),
+            (FileStatus::Untracked, 0),
+            (FileStatus::Untracked, 0),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_file_changes_across_submodule() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let git = |repo: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(["-c", "protocol.file.allow=always"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?}: {output:?}");
+    };
+
+    let inner = root.join("inner");
+    fs::create_dir(&inner).unwrap();
+    git(&inner, &["init", "-q"]);
+    fs::write(inner.join("lib.rs"), "one\n").unwrap();
+    git(&inner, &["add", "."]);
+    git(&inner, &["commit", "-q", "-m", "init"]);
+
+    let outer = root.join("outer");
+    fs::create_dir(&outer).unwrap();
+    git(&outer, &["init", "-q"]);
+    fs::write(outer.join("main.rs"), "one\n").unwrap();
+    git(&outer, &["add", "."]);
+    git(&outer, &["commit", "-q", "-m", "init"]);
+    git(
+        &outer,
+        &[
+            "submodule",
+            "add",
+            "-q",
+            inner.to_str().unwrap(),
+            "vendor/inner",
+        ],
+    );
+    git(&outer, &["commit", "-q", "-m", "add submodule"]);
+
+    fs::write(outer.join("main.rs"), "one\ntwo\n").unwrap();
+    let sub_file = outer.join("vendor/inner/lib.rs");
+    fs::write(&sub_file, "uno\ndos\ntres\n").unwrap();
+
+    let files = vec![
+        sub_file.display().to_string(),
+        outer.join("main.rs").display().to_string(),
+    ];
+    let changes = file_changes(&outer, &files).await;
+
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0].path, files[0]);
+    assert_eq!(changes[0].status, FileStatus::Modified);
+    assert_eq!((changes[0].additions, changes[0].deletions), (3, 1));
+    assert_eq!((changes[1].additions, changes[1].deletions), (1, 0));
+}
+
+#[test]
+fn test_transcript_markdown() {
+    let messages = vec![
+        ChatMessage {
+            role: "user".to_string(),
+            content: "Fix the bug".to_string(),
+            timestamp: None,
+            tool_calls: Vec::new(),
+            line: None,
+            thinking: None,
+        },
+        ChatMessage {
+            role: "assistant".to_string(),
+            content: "Done.".to_string(),
+            timestamp: None,
+            tool_calls: vec![ToolCall {
+                tool_name: "Edit".to_string(),
+                status: "completed".to_string(),
+                file_path: Some("/repo/src/app.rs".to_string()),
+            }],
+            line: None,
+            thinking: None,
+        },
+    ];
+
+    assert_eq!(
+        transcript_markdown(&messages),
+        "## User\n\nFix the bug\n\n## Assistant\n\nDone.\n\n- `Edit` /repo/src/app.rs\n\n"
+    );
+    assert_eq!(transcript_markdown(&[]), "");
+}
+
+#[test]
+fn test_message_counts_by_role() {
+    let tool = |name: &str| ToolCall {
+        tool_name: name.to_string(),
+        status: "completed".to_string(),
+        file_path: None,
+    };
+    let msg = |role: &str, tools: Vec<ToolCall>| ChatMessage {
+        role: role.to_string(),
+        content: String::new(),
+        timestamp: None,
+        tool_calls: tools,
+        line: None,
+        thinking: None,
+    };
+    let messages = vec![
+        msg("user", Vec::new()),
+        msg("assistant", vec![tool("Read"), tool("Edit")]),
+        msg("user", Vec::new()),
+        msg("assistant", vec![tool("Bash")]),
+        msg("assistant", Vec::new()),
+    ];
+
+    assert_eq!(
+        message_counts(&messages),
+        MessageCounts {
+            user: 2,
+            assistant: 3,
+            tool_calls: 3,
+        }
+    );
+    assert_eq!(message_counts(&[]), MessageCounts::default());
+}
+
+#[test]
+fn test_edited_files_by_path_or_recency() {
+    let edit = |path: &str| ChatMessage {
+        role: "assistant".to_string(),
+        content: String::new(),
+        timestamp: None,
+        tool_calls: vec![ToolCall {
+            tool_name: "Edit".to_string(),
+            status: "completed".to_string(),
+            file_path: Some(path.to_string()),
+        }],
+        line: None,
+        thinking: None,
+    };
+    let messages = vec![
+        edit("/repo/b.rs"),
+        edit("/repo/a.rs"),
+        edit("/repo/c.rs"),
+        edit("/repo/b.rs"),
+    ];
+
+    assert_eq!(
+        edited_files(&messages, false),
+        ["/repo/a.rs", "/repo/b.rs", "/repo/c.rs"]
+    );
+    // b.rs was edited again last, so it moves back to the top
+    assert_eq!(
+        edited_files(&messages, true),
+        ["/repo/b.rs", "/repo/c.rs", "/repo/a.rs"]
+    );
+}
+
+#[test]
+fn test_marks_are_unique_and_persist() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sessions.json");
+
+    let mut store = SidecarStore::load_from(path.clone()).unwrap();
+    store.set_mark("p:a", 'w').unwrap();
+    store.set_mark("p:b", 'x').unwrap();
+    assert_eq!(store.find_mark('w'), Some("p:a"));
+
+    // Reusing a mark moves it; the old holder has nothing left and is dropped
+    store.set_mark("p:b", 'w').unwrap();
+    assert_eq!(store.find_mark('w'), Some("p:b"));
+    assert_eq!(store.mark("p:a"), None);
+    assert!(store.get("p:a").is_none());
+
+    let reloaded = SidecarStore::load_from(path).unwrap();
+    assert_eq!(reloaded.mark("p:b"), Some('w'));
+    assert_eq!(reloaded.find_mark('x'), None);
+}
+
+#[tokio::test]
+async fn test_stale_state_file_is_downgraded() {
+    use std::time::{Duration, SystemTime};
+
+    let dir = tempfile::tempdir().unwrap();
+    let claude_dir = dir.path();
+    let project_dir = claude_dir.join("projects").join("-repo");
+    let state_dir = claude_dir.join("session-state");
+    fs::create_dir_all(&project_dir).unwrap();
+    fs::create_dir_all(&state_dir).unwrap();
+
+    // (session, hook state, state file age in seconds)
+    let cases = [
+        ("fresh", "working", 5),
+        ("stuck", "working", 15 * 60),
+        ("dead", "active", 2 * 60 * 60),
+        ("waiting", "waiting", 2 * 60 * 60),
+    ];
+    for (id, state, age) in cases {
+        fs::write(project_dir.join(format!("{id}.jsonl")), "").unwrap();
+        let path = state_dir.join(format!("{id}.state"));
+        fs::write(&path, format!("{state}\n")).unwrap();
+        let file = fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(age))
+            .unwrap();
+    }
+
+    let data = ClaudeData::load_from(claude_dir, &Settings::default())
+        .await
+        .unwrap();
+    let status = |id: &str| {
+        data.sessions
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.status.clone())
+            .unwrap()
+    };
+
+    assert_eq!(status("fresh"), "working");
+    assert_eq!(status("stuck"), "idle");
+    assert_eq!(status("dead"), "inactive");
+    // Only working/active are downgraded; waiting is a real, lasting state
+    assert_eq!(status("waiting"), "waiting");
+}
+
+#[tokio::test]
+async fn test_infer_waiting_from_transcript_tail() {
+    let dir = tempfile::tempdir().unwrap();
+    let claude_dir = dir.path();
+    let project_dir = claude_dir.join("projects").join("-repo");
+    fs::create_dir_all(&project_dir).unwrap();
+
+    let user = r#"{"type":"user","message":{"role":"user","content":"fix it"}}"#;
+    let reply = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"Done."}]}}"#;
+    let tool = r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"tool_use","name":"Bash","input":{}}]}}"#;
+    let summary = r#"{"type":"summary","summary":"Fixing it"}"#;
+    // (session, transcript lines)
+    let cases: [(&str, &[&str]); 4] = [
+        ("replied", &[user, reply]),
+        ("replied-then-summary", &[user, reply, summary]),
+        ("asked", &[reply, user]),
+        ("running-tool", &[user, tool]),
+    ];
+    for (id, lines) in cases {
+        let content: String = lines.iter().map(|l| format!("{l}\n")).collect();
+        fs::write(project_dir.join(format!("{id}.jsonl")), content).unwrap();
+    }
+
+    let status = |data: &ClaudeData, id: &str| {
+        data.sessions
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.status.clone())
+            .unwrap()
+    };
+
+    // Off by default: just written = working
+    let data = ClaudeData::load_from(claude_dir, &Settings::default())
+        .await
+        .unwrap();
+    assert_eq!(status(&data, "replied"), "working");
+
+    let mut settings = Settings::default();
+    settings.status.infer_waiting = true;
+    let data = ClaudeData::load_from(claude_dir, &settings).await.unwrap();
+    assert_eq!(status(&data, "replied"), "waiting");
+    assert_eq!(status(&data, "replied-then-summary"), "waiting");
+    assert_eq!(status(&data, "asked"), "working");
+    assert_eq!(status(&data, "running-tool"), "working");
+}
+
+#[tokio::test]
+async fn test_load_cache_rereads_only_grown_transcripts() {
+    use chrono::{TimeZone, Utc};
+    use lazychat::data::claude::LoadCache;
+
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("projects").join("-repo");
+    fs::create_dir_all(&project).unwrap();
+    let path = project.join("s.jsonl");
+    // Same length; only the second has a start time
+    let without = r#"{"type":"user","timesXamp":"2024-03-01T09:00:00Z"}"#;
+    let with = r#"{"type":"user","timestamp":"2024-03-01T09:00:00Z"}"#;
+    let started = Some(Utc.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap());
+
+    let mut cache = LoadCache::default();
+    let settings = Settings::default();
+    let started_at = |data: ClaudeData| data.sessions[0].started_at;
+
+    fs::write(&path, format!("{without}\n")).unwrap();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(data.sessions[0].started_at, data.sessions[0].last_activity);
+
+    // A head without a start time is remembered until the file grows
+    fs::write(&path, format!("{with}\n")).unwrap();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_ne!(started_at(data), started);
+    fs::write(&path, format!("{with}\n{with}\n")).unwrap();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(started_at(data), started);
+}
+
+#[tokio::test]
+async fn test_transcript_description_is_cached_until_found() {
+    use lazychat::data::claude::LoadCache;
+
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("projects").join("-repo");
+    fs::create_dir_all(&project).unwrap();
+    let path = project.join("s.jsonl");
+    let user = |text: &str| format!(r#"{{"type":"user","message":{{"content":"{text}"}}}}"#);
+    let mut cache = LoadCache::default();
+    let mut settings = Settings::default();
+    let description = |data: ClaudeData| data.sessions[0].description.clone();
+
+    // No history entry and only a command so far
+    fs::write(&path, user("/clear") + "\n").unwrap();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(description(data), None);
+
+    fs::write(
+        &path,
+        user("/clear") + "\n" + &user("Add a login page") + "\n",
+    )
+    .unwrap();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(description(data).as_deref(), Some("Add a login page"));
+
+    // New rules pick again
+    settings.descriptions.skip_prefixes.clear();
+    let data = ClaudeData::load_cached(dir.path(), &settings, &mut cache)
+        .await
+        .unwrap();
+    assert_eq!(description(data).as_deref(), Some("/clear"));
+}
+
+mod stats_tests {
+    use lazychat::data::stats::{aggregate_daily_stats, stats_to_csv, stats_to_json};
+    use lazychat::data::DailyStats;
+
+    fn day(date: &str, messages: u64, sessions: u64, tools: u64) -> DailyStats {
+        DailyStats {
+            date: date.to_string(),
+            message_count: messages,
+            session_count: sessions,
+            tool_call_count: tools,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_daily_stats() {
+        let a = [
+            r#"{"type":"user","timestamp":"2026-01-01T10:00:00Z","message":{"content":"hi"}}"#,
+            r#"{"type":"assistant","timestamp":"2026-01-01T10:00:05Z","message":{"content":[{"type":"text","text":"ok"},{"type":"tool_use","name":"Edit"},{"type":"tool_use","name":"Bash"}]}}"#,
+            r#"{"type":"summary","summary":"ignored"}"#,
+            r#"{"type":"user","timestamp":"2026-01-02T09:00:00Z","message":{"content":"more"}}"#,
+        ]
+        .join("\n");
+        let b = [
+            r#"{"type":"user","timestamp":"2026-01-02T11:00:00Z","message":{"content":"x"}}"#,
+            "not json",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            stats_to_csv(&aggregate_daily_stats([a.as_str(), b.as_str()])),
+            "date,messages,sessions,tool_calls\n2026-01-01,2,1,2\n2026-01-02,2,2,0\n"
+        );
+    }
+
+    #[test]
+    fn test_export_rows() {
+        let stats = vec![day("2026-03-01", 40, 3, 12), day("2026-03-02", 5, 1, 0)];
+
+        assert_eq!(
+            stats_to_csv(&stats),
+            "date,messages,sessions,tool_calls\n2026-03-01,40,3,12\n2026-03-02,5,1,0\n"
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&stats_to_json(&stats).unwrap()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"date": "2026-03-01", "messages": 40, "sessions": 3, "tool_calls": 12},
+                {"date": "2026-03-02", "messages": 5, "sessions": 1, "tool_calls": 0},
+            ])
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_messages_keep_their_source_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sess.jsonl");
+    let source = [
+        r#"{"type":"user","message":{"content":"Fix the parser"}}"#,
+        r#"{"type":"summary","summary":"skipped"}"#,
+        "",
+        r#"{"type":"assistant","message":{"content":[{"type":"text","text":"On it"}]}}"#,
+    ];
+    fs::write(&path, source.join("\n")).unwrap();
+    let mut sess = session("sess", Vec::new());
+    sess.file_path = Some(path);
+
+    let messages = ClaudeData::load_session_messages(&sess).await.unwrap();
+    let lines: Vec<Option<usize>> = messages.iter().map(|m| m.line).collect();
+    assert_eq!(lines, [Some(1), Some(4)]);
+
+    for msg in &messages {
+        let line = msg.line.unwrap();
+        let raw = ClaudeData::load_raw_message(&sess, line)
+            .await
+            .unwrap()
+            .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&raw).unwrap();
+        let expected: serde_json::Value = serde_json::from_str(source[line - 1]).unwrap();
+        assert_eq!(parsed, expected);
+        assert!(raw.contains('\n'), "pretty-printed");
+    }
+    assert_eq!(ClaudeData::load_raw_message(&sess, 99).await.unwrap(), None);
+}
+
+#[tokio::test]
+async fn test_tool_only_turn_renders_line_per_tool() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("sess.jsonl");
+    let source = [
+        r#"{"type":"user","message":{"content":"Fix the parser"}}"#,
+        r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Read","input":{"file_path":"/repo/src/parser.rs"}},{"type":"tool_use","name":"Edit","input":{"file_path":"/repo/src/parser.rs"}},{"type":"tool_use","name":"Bash","input":{"command":"cargo test"}}]}}"#,
+    ];
+    fs::write(&path, source.join("\n")).unwrap();
+    let mut sess = session("sess", Vec::new());
+    sess.file_path = Some(path);
+
+    let messages = ClaudeData::load_session_messages(&sess).await.unwrap();
+    let turn = &messages[1];
+    assert!(
+        turn.content.is_empty(),
+        "no placeholder: {:?}",
+        turn.content
+    );
+    assert!(!turn.has_text());
+    assert_eq!(
+        turn.display_content(80),
+        [
+            "  └─ Read [completed]",
+            "  └─ Edit /repo/src/parser.rs [completed]",
+            "  └─ Bash [completed]",
+        ]
+    );
+}
+
+#[test]
+fn test_churn_rows_aggregate_per_session() {
+    use lazychat::data::churn::{churn_rows, ChurnRow};
+    use lazychat::redact::Redactor;
+    use std::collections::HashMap;
+
+    let mut api = session("api", Vec::new());
+    api.project = "/work/api".to_string();
+    api.status = "working".to_string();
+    api.custom_name = Some("API refactor".to_string());
+    let mut web = session("web", Vec::new());
+    web.project = "/work/web".to_string();
+    web.description = Some("Fix login".to_string());
+    let mut old = session("old", Vec::new());
+    old.project = "/work/api".to_string();
+    old.status = "inactive".to_string();
+
+    let stats = HashMap::from([
+        (
+            "/work/api".to_string(),
+            parse_git_stats("1\t1\tsrc/a.rs\0", "?? notes.md\0"),
+        ),
+        (
+            "/work/web".to_string(),
+            parse_git_stats("10\t2\tapp.ts\x005\t0\tstyle.css\0", ""),
+        ),
+    ]);
+
+    let row = |id: &str, name: &str, project: &str, files, additions, deletions| ChurnRow {
+        session_id: id.to_string(),
+        name: name.to_string(),
+        project: project.to_string(),
+        files,
+        additions,
+        deletions,
+    };
+    let sessions = [api, web, old];
+    assert_eq!(
+        churn_rows(&sessions, &stats, Redactor::default()),
+        [
+            row("web", "Fix login", "/work/web", 2, 15, 2),
+            row("api", "API refactor", "/work/api", 2, 1, 1),
+        ]
+    );
+
+    // Descriptions follow the redaction the rest of the UI uses
+    let hide = Redactor {
+        on: true,
+        content: true,
+    };
+    assert_eq!(churn_rows(&sessions, &stats, hide)[0].name, "[9 chars]");
+}
+
+#[test]
+fn test_touched_today_uses_local_calendar_day() {
+    use chrono::{Local, TimeZone, Utc};
+
+    let now = Local.with_ymd_and_hms(2024, 3, 15, 14, 30, 0).unwrap();
+    let at = |y, m, d, h, min| {
+        let mut s = session("s", vec![]);
+        s.last_activity = Some(
+            Local
+                .with_ymd_and_hms(y, m, d, h, min, 0)
+                .unwrap()
+                .with_timezone(&Utc),
+        );
+        s
+    };
+
+    assert!(at(2024, 3, 15, 0, 0).touched_today(now));
+    assert!(at(2024, 3, 15, 14, 29).touched_today(now));
+    assert!(at(2024, 3, 15, 23, 59).touched_today(now));
+    assert!(!at(2024, 3, 14, 23, 59).touched_today(now));
+    assert!(!at(2024, 3, 16, 0, 0).touched_today(now));
+    assert!(!at(2023, 3, 15, 14, 30).touched_today(now));
+    assert!(!session("none", vec![]).touched_today(now));
+}
+
+#[tokio::test]
+async fn test_project_file_overrides_project_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("billing-api");
+    fs::create_dir_all(&repo).unwrap();
+    fs::write(
+        repo.join(".lazychat.toml"),
+        "name = \"Billing API\"\ncolor = \"cyan\"\nadd_dirs = [\"../shared\"]\n",
+    )
+    .unwrap();
+
+    // The dash-encoded directory name loses the real path; the cwd keeps it
+    let projects = dir.path().join("claude").join("projects");
+    fs::create_dir_all(projects.join("-tmp-billing-api")).unwrap();
+    fs::write(
+        projects.join("-tmp-billing-api").join("s1.jsonl"),
+        format!(
+            r#"{{"type":"user","cwd":"{}","timestamp":"2024-03-01T09:00:00Z","message":{{"role":"user","content":"hi"}}}}"#,
+            repo.display()
+        ),
+    )
+    .unwrap();
+    fs::create_dir_all(projects.join("-other")).unwrap();
+    fs::write(projects.join("-other").join("s2.jsonl"), "").unwrap();
+
+    let data = ClaudeData::load_from(&dir.path().join("claude"), &Settings::default())
+        .await
+        .unwrap();
+    let s1 = data.sessions.iter().find(|s| s.id == "s1").unwrap();
+    assert_eq!(s1.project_dir(), repo.display().to_string());
+    assert_eq!(s1.project_name, "Billing API");
+    let meta = s1.project_meta.as_ref().unwrap();
+    assert_eq!(meta.color.as_deref(), Some("cyan"));
+    assert_eq!(
+        meta.add_dirs,
+        [repo.join("../shared").display().to_string()]
+    );
+
+    let s2 = data.sessions.iter().find(|s| s.id == "s2").unwrap();
+    assert_eq!(s2.project_name, "other");
+    assert!(s2.project_meta.is_none());
+}
+
+#[tokio::test]
+async fn test_thinking_stats_from_transcript() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("s.jsonl");
+    fs::write(
+        &path,
+        [
+            r#"{"type":"user","message":{"role":"user","content":"why?"}}"#,
+            r#"{"type":"assistant","thinkingDurationMs":4200,"message":{"role":"assistant","content":[{"type":"text","text":"because"}],"usage":{"thinking_tokens":320}}}"#,
+            r#"{"type":"assistant","message":{"role":"assistant","content":[{"type":"text","text":"plain"}]}}"#,
+        ]
+        .map(|line| format!("{line}\n"))
+        .concat(),
+    )
+    .unwrap();
+
+    let (messages, _) = ClaudeData::tail_messages(&path, TailCursor::default())
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[0].thinking, None);
+    let stats = messages[1].thinking.unwrap();
+    assert_eq!(
+        stats.annotation().as_deref(),
+        Some("(thought 4.2s, 320 tok)")
+    );
+    assert_eq!(messages[2].thinking, None);
+
+    let tokens_only = ThinkingStats {
+        duration_secs: None,
+        tokens: Some(12),
+    };
+    assert_eq!(
+        tokens_only.annotation().as_deref(),
+        Some("(thought 12 tok)")
+    );
+    assert_eq!(ThinkingStats::default().annotation(), None);
+}
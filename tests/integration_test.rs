@@ -44,6 +44,10 @@
 //! - ✓ Status changes persist across reloads
 //! - ✓ Initial status is "running"
 //!
+//! ### test_preset_worktree_lifecycle
+//! - ✓ worktree::create_worktree() creates one checkout per instance
+//! - ✓ worktree::remove_worktree() removes them on teardown
+//!
 //! ## Running Tests
 //!
 //! NOTE: These tests share a global registry file (~/.cache/lazychat/processes.json).
@@ -54,7 +58,7 @@
 //! ```
 
 use lazychat::config::PresetManager;
-use lazychat::process::{discover_orphan_sessions, ManagedProcess, ProcessRegistry};
+use lazychat::process::{discover_orphan_sessions, worktree, ManagedProcess, ProcessRegistry};
 use std::collections::HashSet;
 use tempfile::TempDir;
 use serial_test::serial;
@@ -329,3 +333,56 @@ fn test_update_process_status() {
 
     println!("✓ Process status update test passed!");
 }
+
+#[test]
+fn test_preset_worktree_lifecycle() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    // Canonicalize so paths match what git reports (e.g. /private/var on macOS)
+    let root = temp_dir.path().canonicalize().unwrap();
+    let repo = root.join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let repo_str = repo.to_string_lossy().to_string();
+
+    let git = |args: &[&str]| {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(&repo)
+            .args(["-c", "user.name=lazychat", "-c", "user.email=lazychat@example.com"])
+            .args(args)
+            .output()
+            .expect("Failed to run git");
+        assert!(status.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&status.stdout).to_string()
+    };
+    git(&["init", "-q"]);
+    git(&["commit", "-q", "--allow-empty", "-m", "init"]);
+
+    // One worktree per instance, as siblings of the repo
+    let mut paths = Vec::new();
+    for i in 0..2 {
+        let path = worktree::create_worktree(&repo_str, "myproj", i).expect("Failed to create worktree");
+        assert_eq!(
+            std::path::Path::new(&path),
+            root.join(format!("myproj-wt{i}")),
+            "Worktree should live next to the repo"
+        );
+        assert!(std::path::Path::new(&path).is_dir(), "Worktree dir should exist");
+        paths.push(path);
+    }
+
+    let listed = git(&["worktree", "list"]);
+    for path in &paths {
+        assert!(listed.contains(path.as_str()), "git should know about {path}");
+    }
+
+    // Unsafe names never reach the filesystem
+    assert!(worktree::create_worktree(&repo_str, "../evil", 0).is_err());
+
+    // Teardown removes every checkout
+    for path in &paths {
+        worktree::remove_worktree(&repo_str, path).expect("Failed to remove worktree");
+        assert!(!std::path::Path::new(path).exists(), "Worktree dir should be removed");
+    }
+    let listed = git(&["worktree", "list"]);
+    assert_eq!(listed.lines().count(), 1, "Only the main checkout should remain");
+}
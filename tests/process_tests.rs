@@ -1,10 +1,30 @@
 //! Comprehensive tests for Phase 1 process management module
 
 use anyhow::Result;
+use lazychat::data::Session;
 use std::collections::HashSet;
 use std::fs;
 use tempfile::TempDir;
 
+/// Minimal idle session for tests that only care about its id and project
+fn session(id: &str, project: &str) -> Session {
+    Session {
+        id: id.to_string(),
+        project: project.to_string(),
+        project_name: "app".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 1,
+        status: "idle".to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    }
+}
+
 // Import the modules we're testing
 // Note: These paths assume the modules are properly exposed in lib.rs
 // For now, we'll include the source files directly in the test
@@ -393,7 +413,7 @@ mod adoption_tests {
         fs::write(state_dir.join("working-session.state"), "working")?;
         fs::write(state_dir.join("completed-session.state"), "completed")?; // Should be ignored
 
-        let registered_pids: HashSet<u32> = HashSet::new();
+        let _registered_pids: HashSet<u32> = HashSet::new();
 
         // Simulate orphan discovery logic
         let mut orphan_count = 0;
@@ -520,3 +540,468 @@ fn test_full_workflow_integration() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod which_tests {
+    use super::*;
+    use lazychat::process::which::find_executable;
+    use std::ffi::OsString;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn write_file(dir: &std::path::Path, name: &str, mode: u32) {
+        let path = dir.join(name);
+        fs::write(&path, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+    }
+
+    #[test]
+    fn test_find_executable_on_stubbed_path() {
+        let empty = TempDir::new().unwrap();
+        let bin = TempDir::new().unwrap();
+        write_file(bin.path(), "claude", 0o755);
+
+        let path = std::env::join_paths([empty.path(), bin.path()]).unwrap();
+        assert_eq!(
+            find_executable("claude", &path),
+            Some(bin.path().join("claude"))
+        );
+    }
+
+    #[test]
+    fn test_find_executable_missing() {
+        let empty = TempDir::new().unwrap();
+        let path = std::env::join_paths([empty.path()]).unwrap();
+        assert_eq!(find_executable("claude", &path), None);
+        assert_eq!(find_executable("claude", &OsString::new()), None);
+    }
+
+    #[test]
+    fn test_find_executable_skips_non_executable() {
+        let bin = TempDir::new().unwrap();
+        write_file(bin.path(), "claude", 0o644);
+        fs::create_dir(bin.path().join("dir")).unwrap();
+
+        let path = std::env::join_paths([bin.path()]).unwrap();
+        assert_eq!(find_executable("claude", &path), None);
+        assert_eq!(find_executable("dir", &path), None);
+    }
+}
+
+mod resume_tests {
+    use super::session;
+    use lazychat::config::presets::Preset;
+    use lazychat::process::ResumeCommand;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_resume_command_for_session() {
+        let cmd = ResumeCommand::for_session(&session("abc-123", "/home/user/dev/app"));
+        assert_eq!(cmd.program, "claude");
+        assert_eq!(cmd.args, vec!["--resume", "abc-123"]);
+        assert_eq!(cmd.cwd, PathBuf::from("/home/user/dev/app"));
+    }
+
+    #[test]
+    fn test_resume_command_decodes_project_dir() {
+        let cmd = ResumeCommand::for_session(&session("abc-123", "-home-user-app"));
+        assert_eq!(cmd.cwd, PathBuf::from("/home/user/app"));
+    }
+
+    #[test]
+    fn test_clipboard_resume_command_with_preset() {
+        let preset = Preset {
+            name: "app".to_string(),
+            shortcut: None,
+            cwd: "/home/user/my app".to_string(),
+            add_dirs: vec!["/home/user/shared".to_string()],
+            instances: 1,
+            extra_args: vec![
+                "--dangerously-skip-permissions".to_string(),
+                "--model=opus".to_string(),
+            ],
+            worktree: false,
+        };
+        let cmd =
+            ResumeCommand::for_clipboard(&session("abc-123", "/home/user/my app"), Some(&preset));
+        assert_eq!(
+            cmd.shell_line(),
+            "cd '/home/user/my app' && claude --resume abc-123 --dangerously-skip-permissions \
+             --add-dir /home/user/shared --model=opus"
+        );
+
+        let plain = ResumeCommand::for_clipboard(&session("abc-123", "/home/user/dev/app"), None);
+        assert_eq!(
+            plain.shell_line(),
+            "cd /home/user/dev/app && claude --resume abc-123 --dangerously-skip-permissions"
+        );
+    }
+}
+
+mod kill_session_tests {
+    use super::session;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use lazychat::app::{App, Focus};
+    use lazychat::events::handle_key;
+    use lazychat::process::registry::ProcessRegistry;
+
+    async fn press(app: &mut App, c: char) {
+        handle_key(app, KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_kill_targets_selected_sessions_process() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = ProcessRegistry::load_from(dir.path().join("processes.json")).unwrap();
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        let other_pid = std::process::id();
+        for (pid, session_id) in [(other_pid, "other"), (child.id(), "target")] {
+            registry
+                .register_process(pid, session_id.to_string(), None, 0, "/tmp".into(), vec![])
+                .unwrap();
+        }
+
+        let mut app = App::with_sessions(vec![
+            session("other", "/tmp"),
+            session("target", "/tmp"),
+            session("none", "/tmp"),
+        ]);
+        app.process_registry = Some(registry);
+        app.focus = Focus::Sessions;
+
+        // No managed process: nothing to confirm
+        app.session_list_state.select(Some(2));
+        press(&mut app, 'd').await;
+        assert_eq!(app.pending_kill, None);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("No managed process for this session")
+        );
+
+        // Anything but `y` cancels
+        app.session_list_state.select(Some(1));
+        press(&mut app, 'd').await;
+        assert_eq!(app.pending_kill, Some(child.id()));
+        press(&mut app, 'n').await;
+        assert_eq!(app.pending_kill, None);
+        assert!(child.try_wait().unwrap().is_none());
+
+        press(&mut app, 'd').await;
+        press(&mut app, 'y').await;
+        assert!(!child.wait().unwrap().success());
+
+        let remaining: Vec<&str> = app
+            .process_registry
+            .as_ref()
+            .unwrap()
+            .get_all_processes()
+            .iter()
+            .map(|p| p.session_id.as_str())
+            .collect();
+        assert_eq!(remaining, ["other"]);
+    }
+}
+
+mod spawn_preset_tests {
+    use lazychat::app::App;
+    use lazychat::config::presets::Preset;
+    use lazychat::process::registry::ProcessRegistry;
+    use std::path::Path;
+
+    fn git(repo: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .arg("-C")
+            .arg(repo)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_spawn_preset_reports_partial_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        let repo = root.join("repo");
+        std::fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-q"]);
+        std::fs::write(repo.join("README.md"), "hi\n").unwrap();
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+
+        // The second instance's worktree path is already taken
+        std::fs::create_dir(root.join("demo-wt1")).unwrap();
+        std::fs::write(root.join("demo-wt1/keep.txt"), "busy").unwrap();
+
+        let mut app = App::new();
+        app.claude_available = true;
+        app.claude_program = "true".to_string();
+        app.process_registry =
+            Some(ProcessRegistry::load_from(root.join("processes.json")).unwrap());
+        app.presets = vec![Preset {
+            name: "demo".to_string(),
+            shortcut: None,
+            cwd: repo.display().to_string(),
+            add_dirs: Vec::new(),
+            instances: 3,
+            extra_args: Vec::new(),
+            worktree: true,
+        }];
+        app.selected_preset_idx = 0;
+
+        app.spawn_preset().unwrap();
+
+        let registered: Vec<u32> = app
+            .process_registry
+            .as_ref()
+            .unwrap()
+            .get_all_processes()
+            .iter()
+            .map(|p| p.instance_index)
+            .collect();
+        assert_eq!(registered, [0, 2]);
+        assert!(app.status_is_error);
+        let status = app.status_message.unwrap();
+        assert!(
+            status.starts_with("Launched 2/3, 1 failed: #2: failed to create worktree"),
+            "{status}"
+        );
+    }
+
+    #[test]
+    fn test_dry_run_records_commands_without_spawning() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        // Would leave a marker if the command actually ran
+        let marker = root.join("ran");
+        let program = root.join("fake-claude");
+        std::fs::write(&program, format!("#!/bin/sh\ntouch {}\n", marker.display())).unwrap();
+        std::fs::set_permissions(
+            &program,
+            std::os::unix::fs::PermissionsExt::from_mode(0o755),
+        )
+        .unwrap();
+
+        let mut app = App::new();
+        app.claude_available = false;
+        app.claude_program = program.display().to_string();
+        app.settings.dry_run = true;
+        app.process_registry =
+            Some(ProcessRegistry::load_from(root.join("processes.json")).unwrap());
+        app.presets = vec![Preset {
+            name: "demo".to_string(),
+            shortcut: None,
+            cwd: "/work/demo".to_string(),
+            add_dirs: vec!["/work/shared".to_string()],
+            instances: 2,
+            extra_args: vec!["--model".to_string(), "opus".to_string()],
+            worktree: false,
+        }];
+        app.selected_preset_idx = 0;
+
+        app.spawn_preset().unwrap();
+
+        let expected = format!(
+            "cd '/work/demo' && {} '--add-dir' '/work/shared' '--model' 'opus' '--session-id' '<new-session-id>'",
+            program.display()
+        );
+        assert_eq!(app.dry_run_log, [expected.clone(), expected.clone()]);
+        assert!(!app.status_is_error);
+        assert!(app
+            .status_message
+            .as_deref()
+            .unwrap()
+            .starts_with("Dry run: cd '/work/demo'"));
+        assert!(app
+            .process_registry
+            .as_ref()
+            .unwrap()
+            .get_all_processes()
+            .is_empty());
+        assert!(!root.join("processes.json").exists());
+
+        // `n` is recorded the same way, even without the CLI installed
+        app.settings.new_session.args = vec!["--model".to_string(), "opus".to_string()];
+        app.open_new_embedded_terminal(80, 24, Some("/work/demo"))
+            .unwrap();
+        assert_eq!(
+            app.dry_run_log.last().unwrap(),
+            "cd '/work/demo' && claude '--model' 'opus'"
+        );
+        assert!(!app.status_is_error && !app.terminal_mode);
+
+        std::thread::sleep(std::time::Duration::from_millis(50));
+        assert!(!marker.exists());
+    }
+}
+
+#[cfg(test)]
+mod cleanup_dead_tests {
+    use lazychat::app::App;
+    use lazychat::process::registry::ProcessRegistry;
+
+    #[test]
+    fn test_cleanup_dead_processes_reports_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut registry = ProcessRegistry::load_from(dir.path().join("processes.json")).unwrap();
+
+        // A child that has already exited and been reaped
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = child.id();
+        child.wait().unwrap();
+
+        let alive_pid = std::process::id();
+        for (pid, session) in [(alive_pid, "alive"), (dead_pid, "dead")] {
+            registry
+                .register_process(
+                    pid,
+                    session.to_string(),
+                    None,
+                    0,
+                    "/tmp".to_string(),
+                    vec![],
+                )
+                .unwrap();
+        }
+
+        let mut app = App::new();
+        app.process_registry = Some(registry);
+        app.refresh_process_counts();
+        assert_eq!(app.process_summary(), "1 running / 2 total");
+
+        assert_eq!(app.cleanup_dead_processes(), 1);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Cleaned up 1 dead processes")
+        );
+        assert_eq!(app.process_summary(), "1 running / 1 total");
+        let left: Vec<u32> = app
+            .process_registry
+            .as_ref()
+            .unwrap()
+            .get_all_processes()
+            .iter()
+            .map(|p| p.pid)
+            .collect();
+        assert_eq!(left, [alive_pid]);
+
+        assert_eq!(app.cleanup_dead_processes(), 0);
+        assert_eq!(app.status_message.as_deref(), Some("No dead processes"));
+    }
+}
+
+#[cfg(test)]
+mod select_hook_tests {
+    use super::session;
+    use lazychat::app::App;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_select_hook_runs_with_session_args() {
+        let dir = tempfile::tempdir().unwrap();
+        let out = dir.path().join("out.txt");
+        // Stub that records its arguments instead of doing real work
+        let stub = dir.path().join("hook.sh");
+        std::fs::write(
+            &stub,
+            format!(
+                "#!/bin/sh\necho \"$@\" > '{}.tmp' && mv '{0}.tmp' '{0}'\n",
+                out.display()
+            ),
+        )
+        .unwrap();
+        std::fs::set_permissions(&stub, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let mut app = App::new();
+        app.sessions = vec![
+            session("a", "/home/user/dev/app"),
+            session("b", "/home/user/dev/app"),
+        ];
+        app.session_list_state.select(Some(1));
+
+        // Off by default
+        assert_eq!(app.run_select_hook(), None);
+
+        app.settings.on_select_command = stub.display().to_string();
+        let hook = app.run_select_hook().expect("hook ran");
+        assert_eq!(hook.program, stub);
+        assert_eq!(hook.args, ["b", "/home/user/dev/app"]);
+        let cmd = hook.command();
+        let args: Vec<_> = cmd.get_args().collect();
+        assert_eq!(args, ["b", "/home/user/dev/app"]);
+
+        // Fire-and-forget: the stub finishes on its own
+        for _ in 0..200 {
+            if out.exists() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+        assert_eq!(
+            std::fs::read_to_string(&out).unwrap(),
+            "b /home/user/dev/app\n"
+        );
+
+        // Missing programs are reported, and safe mode skips the hook
+        app.settings.on_select_command = dir.path().join("missing").display().to_string();
+        assert_eq!(app.run_select_hook(), None);
+        assert!(app.status_is_error);
+        app.settings.on_select_command = stub.display().to_string();
+        app.settings.safe_mode = true;
+        assert_eq!(app.run_select_hook(), None);
+    }
+}
+
+mod process_error_tests {
+    use lazychat::process::{HeadlessTerminal, ProcessError};
+    use std::os::unix::fs::PermissionsExt;
+
+    fn spawn(program: &str, cwd: &str) -> ProcessError {
+        match HeadlessTerminal::spawn_program(program, cwd, vec![], vec![]) {
+            Ok(terminal) => panic!("spawned {program} as pid {}", terminal.pid()),
+            Err(e) => e,
+        }
+    }
+
+    #[test]
+    fn test_spawn_missing_binary_is_not_found() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("no-such-claude");
+        let err = spawn(&program.to_string_lossy(), &dir.path().to_string_lossy());
+        assert!(matches!(err, ProcessError::NotFound { .. }), "{err:?}");
+    }
+
+    #[test]
+    fn test_spawn_non_executable_is_permission_denied() {
+        let dir = tempfile::tempdir().unwrap();
+        let program = dir.path().join("claude");
+        std::fs::write(&program, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&program, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = spawn(&program.to_string_lossy(), &dir.path().to_string_lossy());
+        assert!(
+            matches!(err, ProcessError::PermissionDenied { .. }),
+            "{err:?}"
+        );
+    }
+
+    #[test]
+    fn test_spawn_rejects_bad_working_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("gone");
+        let err = spawn("true", &missing.to_string_lossy());
+        assert!(matches!(err, ProcessError::MissingCwd(_)), "{err:?}");
+
+        let err = spawn("true", "/tmp/../etc");
+        assert!(matches!(err, ProcessError::PathTraversal(_)), "{err:?}");
+
+        // Existing anyhow call sites keep the message and can downcast
+        let err = anyhow::Error::from(err);
+        assert_eq!(err.to_string(), "Path traversal not allowed: /tmp/../etc");
+        assert!(err.downcast_ref::<ProcessError>().is_some());
+    }
+}
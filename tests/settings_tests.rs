@@ -0,0 +1,183 @@
+//! Tests for general settings (config.toml)
+
+use lazychat::config::settings::{
+    DescriptionRules, DiffOptions, LayoutSettings, Panel, PanelHeight, Settings,
+};
+use std::fs;
+
+#[test]
+fn test_default_description_rules() {
+    let rules = DescriptionRules::default();
+    assert!(rules.accepts("Fix the login bug"));
+    assert!(rules.accepts("  Fix it  "));
+    assert!(!rules.accepts("  Fix  "));
+    assert!(!rules.accepts("/clear"));
+    assert!(!rules.accepts("<command-name>"));
+    assert!(!rules.accepts("hey"));
+    assert!(!rules.accepts(""));
+}
+
+#[test]
+fn test_min_length_counts_characters() {
+    let rules = DescriptionRules {
+        min_length: 3,
+        skip_prefixes: Vec::new(),
+    };
+    assert!(rules.accepts("äöü"));
+    assert!(!rules.accepts("äö"));
+    assert!(rules.accepts("/go"));
+}
+
+#[test]
+fn test_empty_skip_prefix_is_ignored() {
+    let rules = DescriptionRules {
+        min_length: 1,
+        skip_prefixes: vec![String::new(), "#".to_string()],
+    };
+    assert!(rules.accepts("note"));
+    assert!(!rules.accepts("# heading"));
+}
+
+#[test]
+fn test_load_partial_config_keeps_defaults() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "[descriptions]\nmin_length = 12\n").unwrap();
+
+    let settings = Settings::load_from(&path).unwrap();
+    assert_eq!(settings.descriptions.min_length, 12);
+    assert_eq!(settings.descriptions.skip_prefixes, vec!["/", "<"]);
+}
+
+#[test]
+fn test_load_missing_config_is_default() {
+    let dir = tempfile::tempdir().unwrap();
+    let settings = Settings::load_from(&dir.path().join("config.toml")).unwrap();
+    assert_eq!(settings.descriptions.min_length, 5);
+}
+
+#[test]
+fn test_load_invalid_config_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "[descriptions]\nmin_length = \"many\"\n").unwrap();
+    assert!(Settings::load_from(&path).is_err());
+}
+
+#[test]
+fn test_syntax_highlighting_toggle() {
+    assert!(Settings::default().display.syntax_highlighting);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "[display]\nsyntax_highlighting = false\n").unwrap();
+    let settings = Settings::load_from(&path).unwrap();
+    assert!(!settings.display.syntax_highlighting);
+
+    let mut app = lazychat::app::App::new();
+    assert!(app.highlighter().is_some());
+    app.settings = settings;
+    assert!(app.highlighter().is_none());
+}
+
+#[test]
+fn test_status_thresholds() {
+    use lazychat::config::settings::StatusThresholds;
+
+    let t = StatusThresholds::default();
+    assert_eq!(t.status_for_age(3), "working");
+    assert_eq!(t.status_for_age(60), "active");
+    assert_eq!(t.status_for_age(600), "idle");
+    assert_eq!(t.status_for_age(3600), "inactive");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(&path, "[status]\nstale_state_secs = 30\n").unwrap();
+    let t = Settings::load_from(&path).unwrap().status;
+    assert_eq!(t.active_secs, 120);
+    assert_eq!(t.status_for_state("working", 29), "working");
+    assert_eq!(t.status_for_state("working", 31), "idle");
+}
+
+#[test]
+fn test_diff_options_git_args() {
+    assert_eq!(
+        DiffOptions::default().git_args("src/app.rs"),
+        ["diff", "--color=never", "-U3", "--", "src/app.rs"]
+    );
+    assert_eq!(
+        DiffOptions::default().rename_git_args("old.rs", "new.rs"),
+        [
+            "diff",
+            "--color=never",
+            "-U3",
+            "-M",
+            "HEAD",
+            "--",
+            "old.rs",
+            "new.rs"
+        ]
+    );
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    fs::write(
+        &path,
+        "[diff]\ncontext_lines = 10\nignore_whitespace = true\nignore_blank_lines = true\n",
+    )
+    .unwrap();
+    let settings = Settings::load_from(&path).unwrap();
+    assert_eq!(
+        settings.diff.git_args("a.rs"),
+        [
+            "diff",
+            "--color=never",
+            "-U10",
+            "-w",
+            "--ignore-blank-lines",
+            "--",
+            "a.rs"
+        ]
+    );
+}
+
+#[test]
+fn test_left_panels_parse_order_and_heights() {
+    let layout = |entries: &[&str]| LayoutSettings {
+        left_panels: entries.iter().map(|e| e.to_string()).collect(),
+        ..LayoutSettings::default()
+    };
+
+    assert_eq!(
+        LayoutSettings::default().panels(),
+        [
+            (Panel::Sessions, PanelHeight::Ratio(1)),
+            (Panel::Files, PanelHeight::Ratio(1)),
+            (Panel::Todos, PanelHeight::Ratio(1)),
+        ]
+    );
+
+    let custom = layout(&[
+        "todos:8",
+        "Sessions:2x",
+        "bogus",
+        "files",
+        "files:3",
+        "todos:0x",
+    ]);
+    assert_eq!(
+        custom.panels(),
+        [
+            (Panel::Todos, PanelHeight::Fixed(8)),
+            (Panel::Sessions, PanelHeight::Ratio(2)),
+            (Panel::Files, PanelHeight::Ratio(1)),
+        ]
+    );
+    assert_eq!(custom.unknown_panels(), ["bogus", "todos:0x"]);
+
+    // Nothing usable falls back to the default arrangement
+    assert_eq!(
+        layout(&["nope"]).panels(),
+        LayoutSettings::default().panels()
+    );
+}
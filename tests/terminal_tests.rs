@@ -0,0 +1,140 @@
+use lazychat::config::settings::Settings;
+use lazychat::terminal::{
+    difftool_script, editor_diff_script, multi_editor_script, new_claude_command, pager_command,
+    shell_command, supports_process_substitution,
+};
+
+#[test]
+fn test_pager_command_uses_configured_pager() {
+    assert_eq!(
+        pager_command("less -R", "/tmp/lazychat-abc.md"),
+        "less -R '/tmp/lazychat-abc.md'"
+    );
+    assert_eq!(
+        pager_command("bat --paging=always", "/tmp/it's here.md"),
+        "bat --paging=always '/tmp/it'\\''s here.md'"
+    );
+}
+
+#[test]
+fn test_difftool_script_runs_in_project_for_one_file() {
+    assert_eq!(
+        difftool_script("/repo", "/repo/src/app.rs"),
+        "cd '/repo' && git difftool -y -- '/repo/src/app.rs'"
+    );
+    assert_eq!(
+        difftool_script("/my repo", "/my repo/it's.rs"),
+        "cd '/my repo' && git difftool -y -- '/my repo/it'\\''s.rs'"
+    );
+}
+
+#[test]
+fn test_multi_editor_script_opens_every_file_from_project() {
+    let files = vec!["/repo/src/a.rs".to_string(), "/repo/it's.md".to_string()];
+    assert_eq!(
+        multi_editor_script("nvim", "/repo", &files),
+        "cd '/repo' && nvim -p '/repo/src/a.rs' '/repo/it'\\''s.md'"
+    );
+    assert_eq!(
+        multi_editor_script("/usr/bin/vim", "/repo", &files[..1]),
+        "cd '/repo' && /usr/bin/vim -p '/repo/src/a.rs'"
+    );
+    assert_eq!(
+        multi_editor_script("code --wait", "/my repo", &files[..1]),
+        "cd '/my repo' && code --wait '/repo/src/a.rs'"
+    );
+}
+
+#[test]
+fn test_shell_command_uses_configured_shell() {
+    let cmd = shell_command("/usr/bin/fish", "echo hi");
+    let argv: Vec<String> = cmd
+        .get_argv()
+        .iter()
+        .map(|a| a.to_string_lossy().to_string())
+        .collect();
+    assert_eq!(argv, vec!["/usr/bin/fish", "-c", "echo hi"]);
+}
+
+#[test]
+fn test_new_session_command_uses_configured_args() {
+    let argv = |cmd: &portable_pty::CommandBuilder| -> Vec<String> {
+        cmd.get_argv()
+            .iter()
+            .map(|a| a.to_string_lossy().to_string())
+            .collect()
+    };
+
+    let defaults = Settings::default();
+    let cmd = new_claude_command(&defaults.new_session.args, None);
+    assert_eq!(argv(&cmd), ["claude", "--dangerously-skip-permissions"]);
+    assert!(cmd.get_cwd().is_none());
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(
+        &path,
+        "[new_session]\nargs = [\"--model\", \"opus\"]\nprompt_cwd = true\n",
+    )
+    .unwrap();
+    let settings = Settings::load_from(&path).unwrap();
+    assert!(settings.new_session.prompt_cwd);
+    let cmd = new_claude_command(&settings.new_session.args, Some("/tmp/project"));
+    assert_eq!(argv(&cmd), ["claude", "--model", "opus"]);
+    assert_eq!(
+        cmd.get_cwd().map(|d| d.to_string_lossy().to_string()),
+        Some("/tmp/project".to_string())
+    );
+}
+
+#[test]
+fn test_process_substitution_support() {
+    assert!(supports_process_substitution("bash"));
+    assert!(supports_process_substitution("/bin/zsh"));
+    assert!(!supports_process_substitution("sh"));
+    assert!(!supports_process_substitution("/usr/local/bin/fish"));
+}
+
+#[test]
+fn test_editor_diff_script_per_shell() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("main.rs");
+    std::fs::write(&file, "fn main() {}\n").unwrap();
+    let file = file.to_str().unwrap();
+
+    let (bash, head) = editor_diff_script("bash", "nvim", file).unwrap();
+    assert!(bash.contains("<(git show HEAD:"));
+    assert!(head.is_none());
+
+    // No process substitution: the HEAD side is a real, uniquely named file
+    let (sh, head) = editor_diff_script("sh", "nvim", file).unwrap();
+    let head = head.unwrap();
+    assert!(!sh.contains("<("));
+    let head_path = sh.rsplit(' ').next().unwrap().trim_matches('\'');
+    assert_eq!(head_path, head.path().to_str().unwrap());
+    assert!(head_path.ends_with("-main.rs"));
+    assert_eq!(std::fs::read_to_string(head_path).unwrap(), "New file\n");
+
+    // Same basename, different file: no collision
+    let (_, other) = editor_diff_script("sh", "nvim", file).unwrap();
+    assert_ne!(other.unwrap().path(), head.path());
+
+    let head_path = head.path().to_path_buf();
+    drop(head);
+    assert!(!head_path.exists());
+}
+
+#[test]
+fn test_pager_file_lives_as_long_as_the_terminal() {
+    use lazychat::terminal::EmbeddedTerminal;
+
+    let file = tempfile::NamedTempFile::new().unwrap();
+    let path = file.path().to_path_buf();
+    let mut terminal = EmbeddedTerminal::new(80, 24).unwrap().with_shell("sh");
+    terminal.spawn_pager(file).unwrap();
+    assert!(path.exists());
+
+    terminal.stop();
+    drop(terminal);
+    assert!(!path.exists());
+}
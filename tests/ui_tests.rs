@@ -0,0 +1,1002 @@
+//! Tests for UI rendering helpers
+
+use lazychat::data::TodoItem;
+use lazychat::text::{wrap_columns, wrap_words};
+use lazychat::ui;
+
+fn todo(id: &str, content: &str, status: &str) -> TodoItem {
+    TodoItem {
+        id: id.to_string(),
+        content: content.to_string(),
+        status: status.to_string(),
+    }
+}
+
+#[test]
+fn test_sort_todos_by_status_then_id() {
+    let todos = vec![
+        todo("3", "done early", "completed"),
+        todo("10", "pending later", "pending"),
+        todo("2", "pending first", "pending"),
+        todo("7", "working", "in_progress"),
+        todo("abc", "no numeric id", "pending"),
+        todo("1", "done first", "completed"),
+    ];
+
+    let order: Vec<&str> = ui::sort_todos(&todos)
+        .iter()
+        .map(|t| t.content.as_str())
+        .collect();
+
+    assert_eq!(
+        order,
+        vec![
+            "working",
+            "pending first",
+            "pending later",
+            "no numeric id",
+            "done first",
+            "done early",
+        ]
+    );
+}
+
+#[test]
+fn test_todos_panel_and_preview_render_same_order() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let session = lazychat::data::Session {
+        id: "s".to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 1,
+        status: "idle".to_string(),
+        todos: vec![
+            todo("3", "todo-done", "completed"),
+            todo("10", "todo-later", "pending"),
+            todo("2", "todo-first", "pending"),
+            todo("7", "todo-working", "in_progress"),
+        ],
+        file_path: None,
+    };
+    let mut app = lazychat::app::App::with_sessions(vec![session]);
+    // Focusing the panel shows the preview on the right
+    app.focus = lazychat::app::Focus::Todos;
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let rows: Vec<String> = buffer
+        .content()
+        .chunks(buffer.area.width as usize)
+        .map(|row| row.iter().map(|c| c.symbol()).collect())
+        .collect();
+
+    // Each todo appears once in the panel (left) and once in the preview (right)
+    let contents = ["todo-working", "todo-first", "todo-later", "todo-done"];
+    let mut panel = Vec::new();
+    let mut preview = Vec::new();
+    for content in contents {
+        let mut hits: Vec<(usize, usize)> = rows
+            .iter()
+            .enumerate()
+            .filter_map(|(y, row)| row.find(content).map(|x| (x, y)))
+            .collect();
+        hits.sort();
+        assert_eq!(hits.len(), 2, "{content} rendered in both views");
+        panel.push((hits[0].1, content));
+        preview.push((hits[1].1, content));
+    }
+    panel.sort();
+    preview.sort();
+    let order = |rendered: &[(usize, &'static str)]| -> Vec<&'static str> {
+        rendered.iter().map(|(_, c)| *c).collect()
+    };
+    assert_eq!(order(&panel), contents);
+    assert_eq!(order(&preview), contents);
+}
+
+#[test]
+fn test_todo_style_matches_for_same_status() {
+    // The panel and the preview both render through todo_style
+    let (icon, _) = ui::todo_style("in_progress");
+    assert_eq!(icon, "▶");
+    let (icon, _) = ui::todo_style("completed");
+    assert_eq!(icon, "✓");
+    assert_eq!(ui::todo_style("pending"), ui::todo_style("unknown"));
+}
+
+#[test]
+fn test_todo_lines_wrap_to_panel_width() {
+    let todos = vec![
+        todo("1", "Refactor the session loader module", "in_progress"),
+        todo("2", "ok", "pending"),
+    ];
+    let text = |line: &ratatui::text::Line| {
+        line.spans
+            .iter()
+            .map(|s| s.content.as_ref())
+            .collect::<String>()
+    };
+
+    // 14 columns leave 12 for text after the icon and space
+    let wrapped = ui::todo_lines(&todos, 14, true);
+    let rows: Vec<String> = wrapped.iter().map(text).collect();
+    assert_eq!(
+        rows,
+        vec![
+            "▶ Refactor the",
+            "  session",
+            "  loader",
+            "  module",
+            "○ ok"
+        ]
+    );
+
+    assert_eq!(ui::todo_lines(&todos, 14, false).len(), 2);
+}
+
+#[test]
+fn test_wrap_columns_fits_wide_characters() {
+    use unicode_width::UnicodeWidthStr;
+
+    // Each CJK character takes two columns
+    let line = "+ let 名前 = \"日本語テキスト\";";
+    let chunks = wrap_columns(line, 10);
+
+    assert_eq!(chunks.concat(), line);
+    assert!(chunks.iter().all(|c| c.width() <= 10), "{chunks:?}");
+    assert_eq!(chunks[0], "+ let 名前");
+    // A wide char never straddles the edge: 9 columns + a 2-wide char wraps
+    assert_eq!(wrap_columns("abcdefghi日", 10), vec!["abcdefghi", "日"]);
+}
+
+#[test]
+fn test_wrap_columns_short_and_degenerate() {
+    assert_eq!(wrap_columns("short", 10), vec!["short"]);
+    assert_eq!(wrap_columns("", 10), vec![""]);
+    assert_eq!(wrap_columns("abc", 0), vec!["abc"]);
+    assert_eq!(wrap_columns("abcdef", 3), vec!["abc", "def"]);
+}
+
+#[test]
+fn test_wrap_words_splits_long_words_by_width() {
+    assert_eq!(
+        wrap_words("fix the 🦀🦀🦀🦀 crab", 6),
+        vec!["fix", "the", "🦀🦀🦀", "🦀", "crab"]
+    );
+    assert_eq!(wrap_words("keeps  spacing", 20), vec!["keeps  spacing"]);
+}
+
+#[test]
+fn test_churn_totals_over_all_and_filtered_files() {
+    use lazychat::app::App;
+    use lazychat::data::{FileChange, FileStatus};
+
+    let change = |path: &str, additions, deletions| FileChange {
+        path: path.to_string(),
+        filename: path.rsplit('/').next().unwrap().to_string(),
+        status: FileStatus::Modified,
+        additions,
+        deletions,
+        renamed_from: None,
+    };
+    let mut app = App::new();
+    app.current_file_changes = vec![
+        change("/repo/src/app.rs", 10, 2),
+        change("/repo/src/ui/mod.rs", 5, 7),
+        change("/repo/README.md", 1, 0),
+    ];
+
+    assert_eq!(ui::churn_totals(&app.filtered_files()), (16, 9));
+
+    app.file_filter = "src".to_string();
+    assert_eq!(ui::churn_totals(&app.filtered_files()), (15, 9));
+
+    app.file_filter = "none".to_string();
+    assert_eq!(ui::churn_totals(&app.filtered_files()), (0, 0));
+}
+
+fn render(width: u16, height: u16) -> String {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    let mut app = lazychat::app::App::new();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    buffer.content().iter().map(|c| c.symbol()).collect()
+}
+
+#[test]
+fn test_small_terminal_uses_fallback_render() {
+    use ratatui::layout::Rect;
+
+    assert!(ui::too_small(Rect::new(0, 0, ui::MIN_WIDTH - 1, 40)));
+    assert!(ui::too_small(Rect::new(0, 0, 120, ui::MIN_HEIGHT - 1)));
+    assert!(!ui::too_small(Rect::new(
+        0,
+        0,
+        ui::MIN_WIDTH,
+        ui::MIN_HEIGHT
+    )));
+
+    assert!(render(40, 10).contains("Terminal too small"));
+    assert!(!render(120, 40).contains("Terminal too small"));
+}
+
+#[test]
+fn test_minimap_clicks_only_while_a_diff_shows_it() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let mut app = lazychat::app::App::new();
+    app.fullscreen = true;
+    app.focus = lazychat::app::Focus::Detail;
+    app.diff_mode = true;
+    app.current_diff = (0..200).map(|i| format!("+line {i}\n")).collect();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let area = app.minimap_area.expect("long diff shows a minimap");
+
+    // Back in the chat, the old minimap column is plain chat again
+    app.diff_mode = false;
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    assert_eq!(app.minimap_area, None);
+    app.chat_scroll = 3;
+    assert!(!app.click_minimap(area.x, area.y + 1));
+    assert_eq!(app.chat_scroll, 3);
+}
+
+#[test]
+fn test_hunk_jump_counts_wrapped_rows() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let mut app = lazychat::app::App::new();
+    app.fullscreen = true;
+    app.focus = lazychat::app::Focus::Detail;
+    app.diff_mode = true;
+    // A long line above each hunk wraps onto several rows
+    let long = format!("+{}", "x".repeat(300));
+    let mut diff = vec!["diff --git a/f b/f".to_string(), long.clone()];
+    diff.push("@@ -1,2 +1,2 @@ first".to_string());
+    diff.extend((0..50).map(|i| format!(" line {i}")));
+    diff.push(long);
+    diff.push("@@ -60,2 +60,2 @@ second".to_string());
+    diff.extend((0..50).map(|i| format!(" tail {i}")));
+    app.current_diff = diff.join("\n");
+    app.chat_scroll = 0;
+
+    let top_row = |terminal: &Terminal<TestBackend>| -> String {
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.width)
+            .map(|x| buffer[(x, 1)].symbol())
+            .collect::<String>()
+    };
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    for header in ["first", "second"] {
+        app.jump_to_next_hunk();
+        terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+        let row = top_row(&terminal);
+        assert!(row.contains("@@") && row.contains(header), "{row}");
+    }
+    app.jump_to_prev_hunk();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    assert!(top_row(&terminal).contains("first"));
+}
+
+#[test]
+fn test_help_popup_fits_minimum_terminal() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut terminal = Terminal::new(TestBackend::new(ui::MIN_WIDTH, ui::MIN_HEIGHT)).unwrap();
+    let mut app = lazychat::app::App::new();
+    app.show_help = true;
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+}
+
+#[test]
+fn test_help_popup_scrolls_to_status_legend() {
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let screen = |terminal: &Terminal<TestBackend>| -> String {
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|c| c.symbol()).collect()
+    };
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    let mut app = lazychat::app::App::new();
+    app.toggle_help();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    // Too tall for the frame: the legend starts out of view
+    assert!(screen(&terminal).contains("Help (j/k scroll)"));
+    assert!(!screen(&terminal).contains("needs your input"));
+    // Longest entries are not cut off
+    assert!(screen(&terminal).contains("Compare two sessions side by side"));
+
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    rt.block_on(lazychat::events::handle_key(
+        &mut app,
+        KeyEvent::new(KeyCode::Char('G'), KeyModifiers::NONE),
+    ))
+    .unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    assert!(screen(&terminal).contains("needs your input"));
+    assert!(app.help_scroll < u16::MAX, "clamped to the content");
+}
+
+#[test]
+fn test_help_popup_lists_every_keymap_binding() {
+    use lazychat::keymap::KEYMAP;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 120)).unwrap();
+    let mut app = lazychat::app::App::new();
+    app.toggle_help();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let screen: String = buffer.content().iter().map(|c| c.symbol()).collect();
+
+    for (section, bindings) in KEYMAP {
+        assert!(screen.contains(&format!("─ {section}")), "{section}");
+        for binding in bindings.iter() {
+            assert!(screen.contains(binding.action), "{}", binding.action);
+        }
+    }
+}
+
+#[test]
+fn test_highlighting_covers_only_the_visible_window() {
+    use lazychat::highlight::Highlighter;
+
+    let diff: Vec<String> = (0..100)
+        .map(|i| format!("fn item_{i}(x: u32) -> u32 {{ x + {i} }} // line {i}"))
+        .collect();
+    let lines: Vec<&str> = diff.iter().map(String::as_str).collect();
+
+    let highlighter = Highlighter::new();
+    let highlighted = highlighter
+        .highlight_window("src/lib.rs", &lines[40..60])
+        .unwrap();
+    assert_eq!(highlighted.len(), 20);
+    assert!(highlighter
+        .highlight_window("notes.unknownext", &lines[40..60])
+        .is_none());
+}
+
+// Timing depends on the build profile; run with
+// `cargo test --release -- --ignored` to check the frame budget
+#[test]
+#[ignore]
+fn test_highlighting_visible_slice_of_large_diff_is_fast() {
+    use lazychat::highlight::Highlighter;
+    use std::time::{Duration, Instant};
+
+    let diff: Vec<String> = (0..10_000)
+        .map(|i| format!("fn item_{i}(x: u32) -> u32 {{ x + {i} }} // line {i}"))
+        .collect();
+    let lines: Vec<&str> = diff.iter().map(String::as_str).collect();
+
+    // Loading the syntax sets and compiling the grammar are one-off costs,
+    // excluded from the per-frame budget
+    let highlighter = Highlighter::new();
+    highlighter.highlight_window("src/lib.rs", &lines[..1]);
+    let window = &lines[5_000..5_060];
+
+    let start = Instant::now();
+    let highlighted = highlighter.highlight_window("src/lib.rs", window).unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(highlighted.len(), window.len());
+    // The event loop redraws at most every 100ms; stay well inside that
+    assert!(
+        elapsed < Duration::from_millis(50),
+        "highlighting 60 lines took {elapsed:?}"
+    );
+}
+
+#[test]
+fn test_status_counts_per_status() {
+    let session = |status: &str| lazychat::data::Session {
+        id: status.to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 0,
+        status: status.to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    };
+    let sessions = vec![
+        session("active"),
+        session("working"),
+        session("active"),
+        session("idle"),
+        session("working"),
+        session("active"),
+        session("mystery"),
+    ];
+
+    assert_eq!(
+        ui::status_counts(&sessions),
+        vec![("working", 2), ("active", 3), ("idle", 1), ("inactive", 1)]
+    );
+    assert!(ui::status_counts(&[]).is_empty());
+}
+
+#[test]
+fn test_minimap_buckets_follow_change_density() {
+    use lazychat::ui::minimap::{minimap_buckets, Bucket};
+
+    let mut diff = vec![
+        "diff --git a/x b/x",
+        "--- a/x",
+        "+++ b/x",
+        "@@ -1,8 +1,8 @@",
+    ];
+    diff.extend(["+a", "+b", "+c", "+d"]);
+    diff.extend([" ctx"; 8]);
+    diff.extend(["-x", "-y", "+z", " ctx"]);
+    assert_eq!(diff.len(), 20);
+
+    let buckets = minimap_buckets(&diff, 5);
+    let expected = [(0, 0), (4, 0), (0, 0), (0, 0), (1, 2)];
+    assert_eq!(
+        buckets,
+        expected
+            .iter()
+            .map(|&(additions, deletions)| Bucket {
+                additions,
+                deletions
+            })
+            .collect::<Vec<_>>()
+    );
+
+    // Shorter diffs than the column still land in valid buckets
+    assert_eq!(minimap_buckets(&["+a", "-b"], 10).len(), 10);
+    assert!(minimap_buckets(&[], 4)
+        .iter()
+        .all(|b| *b == Bucket::default()));
+}
+
+#[test]
+fn test_minimap_viewport_and_click_mapping() {
+    use lazychat::ui::minimap::{scroll_for_row, viewport_rows};
+
+    // 100 rows, 20 visible, 20-row minimap
+    assert_eq!(viewport_rows(0, 20, 100, 20), (0, 4));
+    assert_eq!(viewport_rows(50, 20, 100, 20), (10, 14));
+    assert_eq!(viewport_rows(99, 20, 100, 20), (19, 20));
+
+    assert_eq!(scroll_for_row(0, 20, 100, 20), 0);
+    assert_eq!(scroll_for_row(10, 20, 100, 20), 40);
+    assert_eq!(scroll_for_row(19, 20, 100, 20), 80);
+}
+
+#[test]
+fn test_status_legend_covers_rendered_statuses() {
+    use lazychat::config::settings::StatusThresholds;
+
+    let thresholds = StatusThresholds::default();
+    let legend = ui::status_legend(&thresholds);
+    let listed: Vec<&str> = legend.iter().map(|(status, _)| *status).collect();
+
+    // Every status loading can produce: by transcript age, plus the hook-only one
+    let mut rendered: Vec<&str> = [0, 10, 120, 1800, 86_400]
+        .into_iter()
+        .map(|age| thresholds.status_for_age(age))
+        .collect();
+    rendered.push("waiting");
+    for status in rendered {
+        assert!(listed.contains(&status), "{status} missing from legend");
+    }
+
+    let meaning = |status: &str| {
+        legend
+            .iter()
+            .find(|(s, _)| *s == status)
+            .map(|(_, m)| m.clone())
+            .unwrap()
+    };
+    assert_eq!(meaning("working"), "output <10s");
+    assert_eq!(meaning("active"), "output <2m");
+    assert_eq!(meaning("inactive"), "older than 30m");
+}
+
+#[test]
+fn test_chat_wrap_cache_reused_at_same_width() {
+    use lazychat::data::ChatMessage;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let session = lazychat::data::Session {
+        id: "s".to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 2,
+        status: "idle".to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    };
+    let mut app = lazychat::app::App::with_sessions(vec![session]);
+    app.loaded_session_key = Some("s".to_string());
+    app.current_messages = ["user", "assistant"]
+        .iter()
+        .map(|role| ChatMessage {
+            role: role.to_string(),
+            content: "word ".repeat(100),
+            timestamp: None,
+            tool_calls: Vec::new(),
+            line: None,
+            thinking: None,
+        })
+        .collect();
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    assert_eq!(app.wrap_cache.wraps, 1);
+
+    terminal.backend_mut().resize(100, 40);
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    assert_eq!(app.wrap_cache.wraps, 2);
+}
+
+#[test]
+fn test_left_panel_layout_follows_configured_order() {
+    use lazychat::config::settings::{Panel, PanelHeight};
+    use ratatui::layout::Rect;
+
+    let area = Rect::new(0, 0, 30, 40);
+    let panels = [
+        (Panel::Todos, PanelHeight::Fixed(10)),
+        (Panel::Sessions, PanelHeight::Ratio(2)),
+        (Panel::Files, PanelHeight::Ratio(1)),
+    ];
+
+    let chunks = ui::left_panel_layout(&panels, true, true, area);
+    assert_eq!(
+        chunks,
+        [
+            (Panel::Todos, Rect::new(0, 0, 30, 10)),
+            (Panel::Sessions, Rect::new(0, 10, 30, 20)),
+            (Panel::Files, Rect::new(0, 30, 30, 10)),
+        ]
+    );
+
+    // Empty panels are skipped and the rest take their space
+    let chunks = ui::left_panel_layout(&panels, false, false, area);
+    assert_eq!(chunks, [(Panel::Sessions, area)]);
+}
+
+#[test]
+fn test_truncate_uses_single_column_ellipsis() {
+    use unicode_width::UnicodeWidthStr;
+
+    assert_eq!(ui::truncate("abcdef", 1), "…");
+    assert_eq!(ui::truncate("abcdef", 3), "ab…");
+    assert_eq!(ui::truncate("abcdef", 4), "abc…");
+    assert_eq!(ui::truncate("abcdef", 6), "abcdef");
+    assert_eq!(ui::truncate("abcdef", 0), "");
+
+    // Wide characters never push the result past the budget
+    for budget in 1..8 {
+        let cut = ui::truncate("日本語のテキスト", budget);
+        assert!(cut.width() <= budget, "{cut:?} wider than {budget}");
+        assert!(cut.ends_with('…'));
+    }
+    assert_eq!(ui::truncate("日本語", 4), "日…");
+}
+
+#[test]
+fn test_relative_path_strips_project_dir() {
+    let project = "/home/user/dev/app";
+    assert_eq!(
+        ui::relative_path("/home/user/dev/app/src/ui/mod.rs", project),
+        "src/ui/mod.rs"
+    );
+    assert_eq!(
+        ui::relative_path("/home/user/dev/app/README.md", "/home/user/dev/app/"),
+        "README.md"
+    );
+    // Only whole components match, and files elsewhere stay absolute
+    assert_eq!(
+        ui::relative_path("/home/user/dev/app2/lib.rs", project),
+        "/home/user/dev/app2/lib.rs"
+    );
+    assert_eq!(ui::relative_path("/etc/hosts", project), "/etc/hosts");
+    assert_eq!(ui::relative_path(project, project), project);
+}
+
+#[test]
+fn test_custom_theme_colors_added_lines() {
+    use lazychat::config::settings::ThemeSettings;
+    use ratatui::style::Color;
+
+    let default = ThemeSettings::default();
+    assert_eq!(
+        ui::diff_line_style("+new", false, &default).fg,
+        Some(Color::Green)
+    );
+
+    let theme = ThemeSettings {
+        diff_add: "#00ff88".to_string(),
+        diff_hunk: "light-magenta".to_string(),
+        ..ThemeSettings::default()
+    };
+    assert_eq!(
+        ui::diff_line_style("+new", false, &theme).fg,
+        Some(Color::Rgb(0, 255, 136))
+    );
+    assert_eq!(
+        ui::diff_line_style("@@ -1 +1 @@", false, &theme).fg,
+        Some(Color::LightMagenta)
+    );
+    assert_eq!(
+        ui::diff_line_style("-old", false, &theme).fg,
+        Some(Color::Red)
+    );
+    // "+++" file headers and raw file content are not add lines
+    assert_ne!(
+        ui::diff_line_style("+++ b/x", false, &theme).fg,
+        Some(Color::Rgb(0, 255, 136))
+    );
+    assert_eq!(
+        ui::diff_line_style("+new", true, &theme).fg,
+        Some(Color::Gray)
+    );
+
+    // Unknown names keep the default color
+    let bad = ThemeSettings {
+        diff_add: "not-a-color".to_string(),
+        ..ThemeSettings::default()
+    };
+    assert_eq!(
+        ui::diff_line_style("+new", false, &bad).fg,
+        Some(Color::Green)
+    );
+}
+
+#[test]
+fn test_narrow_terminal_uses_single_column() {
+    use lazychat::app::Focus;
+    use lazychat::config::settings::LayoutSettings;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let layout = LayoutSettings::default();
+    assert!(ui::single_column(&layout, layout.single_column_below - 1));
+    assert!(!ui::single_column(&layout, layout.single_column_below));
+    let forced = LayoutSettings {
+        single_column: true,
+        ..LayoutSettings::default()
+    };
+    assert!(ui::single_column(&forced, 200));
+    let never = LayoutSettings {
+        single_column_below: 0,
+        ..LayoutSettings::default()
+    };
+    assert!(!ui::single_column(&never, ui::MIN_WIDTH));
+
+    // Narrow: only the focused side is drawn, using the full width
+    let screen = |width: u16, focus: Focus| -> String {
+        let mut terminal = Terminal::new(TestBackend::new(width, 30)).unwrap();
+        let mut app = lazychat::app::App::new();
+        app.focus = focus;
+        terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer.content().iter().map(|c| c.symbol()).collect()
+    };
+    let sidebar = screen(70, Focus::Sessions);
+    assert!(sidebar.contains("Presets") && !sidebar.contains("No messages"));
+    let detail = screen(70, Focus::Detail);
+    assert!(!detail.contains("Presets") && detail.contains("No messages"));
+    let wide = screen(120, Focus::Sessions);
+    assert!(wide.contains("Presets") && wide.contains("No messages"));
+}
+
+#[test]
+fn test_scroll_indicator_reports_position() {
+    use lazychat::app::SCROLL_BOTTOM;
+
+    assert_eq!(ui::scroll_indicator(0, 0), None);
+    assert_eq!(ui::scroll_indicator(SCROLL_BOTTOM, 0), None);
+    assert_eq!(
+        ui::scroll_indicator(SCROLL_BOTTOM, 100).as_deref(),
+        Some("[bottom]")
+    );
+    assert_eq!(ui::scroll_indicator(100, 100).as_deref(), Some("[bottom]"));
+    assert_eq!(ui::scroll_indicator(0, 100).as_deref(), Some("[top]"));
+    assert_eq!(
+        ui::scroll_indicator(58, 100).as_deref(),
+        Some("[↑ 42 lines]")
+    );
+    assert_eq!(ui::scroll_indicator(99, 100).as_deref(), Some("[↑ 1 line]"));
+}
+
+#[test]
+fn test_configured_status_glyphs_are_rendered() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let session = |id: &str, status: &str| lazychat::data::Session {
+        id: id.to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: None,
+        custom_name: Some(id.to_string()),
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 0,
+        status: status.to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    };
+    let mut app = lazychat::app::App::with_sessions(vec![
+        session("alpha", "idle"),
+        session("beta", "waiting"),
+        session("gamma", "working"),
+    ]);
+    app.settings.status_glyphs.ascii = true;
+    app.settings.status_glyphs.idle = Some("@".to_string());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+
+    assert!(screen.contains("@ alpha"));
+    assert!(screen.contains("! beta"));
+    // ASCII mode also replaces the working spinner
+    assert!(screen.contains("* gamma"));
+    assert!(!screen.contains('◆') && !screen.contains('●'));
+}
+
+#[test]
+fn test_chat_wraps_at_max_line_width_in_wide_pane() {
+    use lazychat::data::ChatMessage;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    assert_eq!(lazychat::text::capped_width(150, 0), 150);
+    assert_eq!(lazychat::text::capped_width(150, 60), 60);
+    assert_eq!(lazychat::text::capped_width(40, 60), 40);
+
+    let mut app = lazychat::app::App::new();
+    app.loaded_session_key = Some("s".to_string());
+    app.current_messages = vec![ChatMessage {
+        role: "user".to_string(),
+        content: "word ".repeat(100),
+        timestamp: None,
+        tool_calls: Vec::new(),
+        line: None,
+        thinking: None,
+    }];
+    app.fullscreen = true;
+    app.settings.display.max_line_width = 30;
+
+    let mut terminal = Terminal::new(TestBackend::new(200, 40)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let rows: Vec<String> = (0..40)
+        .map(|y| {
+            (0..200)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect::<String>()
+        })
+        .filter(|row| row.contains("word"))
+        .collect();
+
+    assert!(rows.len() > 10);
+    for row in rows {
+        assert!(row.trim().trim_matches('│').trim().len() <= 30, "{row}");
+    }
+}
+
+#[test]
+fn test_crlf_diff_lines_render_without_carriage_returns() {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    assert_eq!(lazychat::text::trim_line_cr("a\r\nb\r\r\nc\r"), "a\nb\nc");
+    assert_eq!(lazychat::text::trim_line_cr("plain\nlines"), "plain\nlines");
+
+    let screen = |app: &mut lazychat::app::App| {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        terminal.draw(|f| ui::draw(f, app)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .iter()
+            .map(|c| c.symbol())
+            .collect::<String>()
+    };
+
+    let mut app = lazychat::app::App::new();
+    app.fullscreen = true;
+    app.focus = lazychat::app::Focus::Detail;
+    app.diff_mode = true;
+    app.current_diff = "@@ -1 +1 @@\r\n-old line\r\r\n+new line\r".to_string();
+    let diff = screen(&mut app);
+    assert!(diff.contains("+new line") && diff.contains("-old line"));
+    assert!(!diff.contains('\r'));
+}
+
+#[test]
+fn test_terminal_pane_size_follows_the_screen() {
+    use lazychat::app::App;
+    use ratatui::layout::Rect;
+
+    let mut app = App::new();
+    let screen = Rect::new(0, 0, 200, 50);
+    // 60% detail column minus borders; help bar and borders off the height
+    assert_eq!(ui::terminal_pane_size(&app, screen), (118, 47));
+
+    app.fullscreen = true;
+    assert_eq!(ui::terminal_pane_size(&app, screen), (198, 47));
+
+    app.claude_available = false;
+    assert_eq!(ui::terminal_pane_size(&app, screen), (198, 46));
+}
+
+#[test]
+fn test_help_hints_skip_unavailable_actions() {
+    use lazychat::app::{App, Focus};
+    use lazychat::data::{FileChange, FileStatus};
+
+    let mut app = App::new();
+    app.focus = Focus::Sessions;
+    let hints = ui::help_hints(&app);
+    assert!(hints.contains(&"h: presets"));
+    assert!(!hints.iter().any(|h| h.contains("files")));
+
+    app.current_file_changes = vec![FileChange {
+        path: "/repo/src/app.rs".to_string(),
+        filename: "app.rs".to_string(),
+        status: FileStatus::Modified,
+        additions: 1,
+        deletions: 0,
+        renamed_from: None,
+    }];
+    assert!(ui::help_hints(&app).contains(&"h/l: presets/files"));
+
+    // Safe mode doesn't advertise spawning
+    app.settings.safe_mode = true;
+    assert!(!ui::help_hints(&app).contains(&"n: new"));
+}
+
+#[test]
+fn test_redaction_masks_home_paths_and_session_ids() {
+    use lazychat::redact::{redact_content, redact_home, redact_id, Redactor};
+
+    assert_eq!(
+        redact_home("Edit /Users/alice/code/app.rs and /home/bob"),
+        "Edit /Users/***/code/app.rs and /home/***"
+    );
+    assert_eq!(redact_home("/opt/project/src"), "/opt/project/src");
+    assert_eq!(redact_id("3f2a9c1e-77b0-4d1a"), "3f2a****-****-****");
+    assert_eq!(redact_content("héllo"), "[5 chars]");
+
+    // Off by default: text passes through untouched
+    let off = Redactor::default();
+    assert_eq!(off.path("/Users/alice/x"), "/Users/alice/x");
+    let on = Redactor {
+        on: true,
+        content: false,
+    };
+    assert_eq!(on.id("abcdef"), "abcd**");
+    assert!(!on.hides_content());
+
+    // Every view names sessions through the same choice
+    let mut session = lazychat::data::Session {
+        id: "s".to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: Some("Fix /home/alice/app".to_string()),
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 0,
+        status: "idle".to_string(),
+        todos: Vec::new(),
+        file_path: None,
+    };
+    assert_eq!(off.session_name(&session), "Fix /home/alice/app");
+    assert_eq!(on.session_name(&session), "Fix /home/***/app");
+    let hide = Redactor {
+        on: true,
+        content: true,
+    };
+    assert_eq!(hide.session_name(&session), "[19 chars]");
+    session.custom_name = Some("Login".to_string());
+    assert_eq!(hide.session_name(&session), "Login");
+    session.custom_name = None;
+    session.description = None;
+    assert_eq!(hide.session_name(&session), "p");
+}
+
+#[test]
+fn test_hidden_completed_todos_fold_into_summary() {
+    use lazychat::app::Focus;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let todos = vec![
+        todo("1", "write parser", "completed"),
+        todo("2", "wire up events", "in_progress"),
+        todo("3", "ship docs", "completed"),
+    ];
+    let text: Vec<String> = ui::todo_panel_lines(&todos, 30, false, true)
+        .iter()
+        .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+        .collect();
+    assert_eq!(text, vec!["▶ wire up events", "(+2 done)"]);
+
+    let session = lazychat::data::Session {
+        id: "s".to_string(),
+        project: "/p".to_string(),
+        project_name: "p".to_string(),
+        description: None,
+        custom_name: None,
+        started_at: None,
+        last_activity: None,
+        cwd: None,
+        project_meta: None,
+        message_count: 0,
+        status: "idle".to_string(),
+        todos,
+        file_path: None,
+    };
+    let mut app = lazychat::app::App::with_sessions(vec![session]);
+    app.focus = Focus::Todos;
+    app.hide_done_todos = true;
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+    terminal.draw(|f| ui::draw(f, &mut app)).unwrap();
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(!screen.contains("write parser"));
+    assert!(!screen.contains("ship docs"));
+    assert!(screen.contains("wire up events"));
+    // Once in the left panel, once in the preview
+    assert_eq!(screen.matches("(+2 done)").count(), 2);
+}
+
+#[test]
+fn test_idle_for_counts_from_last_activity() {
+    use chrono::{Duration, TimeZone, Utc};
+
+    let now = Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap();
+    let idle = |ago: Duration| ui::idle_for(Some(now - ago), now);
+
+    assert_eq!(idle(Duration::seconds(30)).as_deref(), Some("idle for <1m"));
+    assert_eq!(idle(Duration::minutes(12)).as_deref(), Some("idle for 12m"));
+    assert_eq!(idle(Duration::hours(3)).as_deref(), Some("idle for 3h"));
+    assert_eq!(idle(Duration::days(2)).as_deref(), Some("idle for 2d"));
+    assert_eq!(ui::idle_for(None, now), None);
+}